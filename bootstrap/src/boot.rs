@@ -12,8 +12,14 @@ pub struct BootContext {
     pub redis: core_db::infra::cache::Cache,
     pub storage: std::sync::Arc<dyn core_db::infra::storage::Storage>,
     pub queue: core_jobs::queue::RedisQueue,
-    // pub channel_manager: std::sync::Arc<core_notify::ChannelManager>,
+    /// Queue selected by `[worker] driver`; dispatch with `job.dispatch(&*ctx.jobs)`.
+    pub jobs: Arc<dyn core_jobs::driver::QueueDriver>,
+    /// Job lifecycle events and metrics, shared by `jobs` and embedded workers.
+    pub job_events: core_jobs::events::JobEvents,
     pub mailer: std::sync::Arc<core_mailer::Mailer>,
+    /// Mail, database, realtime and SMS (log driver) channels; `register` replaces one.
+    /// Opt-outs are read from `notification_preferences`.
+    pub notifications: Arc<core_notify::NotificationManager>,
 }
 
 pub async fn init_app() -> Result<(BootContext, core_web::logging::WorkerGuard)> {
@@ -38,10 +44,13 @@ pub async fn init_app() -> Result<(BootContext, core_web::logging::WorkerGuard)>
     let db = core_db::infra::db::create_pool(&settings.db).await?;
     core_db::common::sql::init_sql_profiler(settings.db.sql_profiler_enabled);
 
-    // 6. Connect Redis
-    let redis = core_db::infra::cache::create_cache(&settings.redis).await?;
+    // 6. Connect Redis (the Postgres queue driver runs without it: connect on first use)
+    let redis = if settings.worker.driver == "postgres" {
+        core_db::infra::cache::lazy_cache(&settings.redis)?
+    } else {
+        core_db::infra::cache::create_cache(&settings.redis).await?
+    };
 
-    // 7. Init Storage
     // 7. Init Storage
     let storage = core_db::infra::storage::create_storage(&settings.s3).await?;
 
@@ -53,10 +62,33 @@ pub async fn init_app() -> Result<(BootContext, core_web::logging::WorkerGuard)>
         .as_ref()
         .map(|prefix| format!("{prefix}:queue"))
         .unwrap_or_else(|| "queue".to_string());
-    let queue = core_jobs::queue::RedisQueue::new_with_prefix(&settings.redis.url, &queue_prefix)?;
+    let job_events = core_jobs::events::JobEvents::new();
+    let queue = core_jobs::queue::RedisQueue::new_with_prefix(&settings.redis.url, &queue_prefix)?
+        .with_events(job_events.clone());
+    let jobs = core_jobs::driver::from_settings(&settings, db.clone(), job_events.clone())?;
 
     // 9. Init Mailer
-    let mailer = core_mailer::Mailer::new(&settings.mail, Some(queue.clone()))?;
+    let mut mailer =
+        core_mailer::Mailer::new(&settings.mail, Some(jobs.clone()))?.with_storage(storage.clone());
+    if settings.mail.log_enabled {
+        mailer = mailer.with_log(db.clone());
+    }
+    let mailer = Arc::new(mailer);
+
+    // 10. Init Notifications (queued through the worker queue)
+    let notifications = core_notify::NotificationManager::new()
+        .with_queue(jobs.clone())
+        .with_preferences(Arc::new(core_notify::DatabasePreferences::new(db.clone())))
+        .with_digest_store(core_notify::DigestStore::new(db.clone()));
+    notifications.register(core_notify::MailChannel::new(mailer.clone()));
+    notifications.register(core_notify::DatabaseChannel::new(db.clone()));
+    notifications.register(core_notify::RealtimeChannel::new(
+        core_realtime::RealtimePublisher::from_realtime_settings(
+            &settings.redis.url,
+            &settings.realtime,
+        )?,
+    ));
+    notifications.register(core_notify::SmsChannel::new(core_notify::LogSmsDriver));
 
     Ok((
         BootContext {
@@ -65,7 +97,10 @@ pub async fn init_app() -> Result<(BootContext, core_web::logging::WorkerGuard)>
             redis,
             storage,
             queue,
-            mailer: Arc::new(mailer), // Add mailer to context
+            jobs,
+            job_events,
+            mailer, // Add mailer to context
+            notifications: Arc::new(notifications),
         },
         log_guard,
     ))
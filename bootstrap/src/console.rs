@@ -61,6 +61,10 @@ pub enum FrameworkCommand<C: clap::Subcommand> {
     #[command(subcommand)]
     Assets(crate::assets::AssetCommands),
 
+    /// Cron scheduler commands
+    #[command(subcommand)]
+    Schedule(crate::schedule::ScheduleCommands),
+
     /// Project specific commands
     #[command(flatten)]
     Project(C),
@@ -74,6 +78,27 @@ pub async fn start_console<C, F>(register_seeders: Option<F>) -> Result<()>
 where
     C: ProjectCommand + clap::Subcommand,
     F: Fn(&mut Vec<Box<dyn core_db::seeder::Seeder>>) + Send + Sync,
+{
+    start_console_with_schedules::<C, F, fn(&mut core_jobs::cron::Scheduler)>(
+        register_seeders,
+        None,
+    )
+    .await
+}
+
+/// Starts the CLI console with the app's schedules, so `schedule list` can show them.
+///
+/// # Arguments
+/// * `register_seeders` - Function to register app-specific seeders.
+/// * `register_schedules` - Function to register app cron schedules (same as the worker's).
+pub async fn start_console_with_schedules<C, F, S>(
+    register_seeders: Option<F>,
+    register_schedules: Option<S>,
+) -> Result<()>
+where
+    C: ProjectCommand + clap::Subcommand,
+    F: Fn(&mut Vec<Box<dyn core_db::seeder::Seeder>>) + Send + Sync,
+    S: FnOnce(&mut core_jobs::cron::Scheduler),
 {
     // 1. Parse Args First (to avoid booting if just --help)
     let cli = FrameworkCli::<C>::parse();
@@ -88,6 +113,10 @@ where
             core_db::commands::cli::handle(core_db::commands::cli::CoreCommands::Make(cmd)).await?
         }
         FrameworkCommand::Assets(cmd) => crate::assets::handle(cmd)?,
+        FrameworkCommand::Schedule(cmd) => {
+            let (ctx, _guard) = init_app().await?;
+            crate::schedule::handle(cmd, &ctx, register_schedules)?
+        }
         FrameworkCommand::Db(cmd) => {
             let (ctx, _guard) = init_app().await?;
             // Intercept Seed
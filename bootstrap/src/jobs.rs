@@ -12,12 +12,36 @@ pub async fn start_with_context<F, S>(
 where
     F: FnOnce(&mut core_jobs::worker::Worker),
     S: FnOnce(&mut core_jobs::cron::Scheduler),
+{
+    start_with_shutdown(
+        ctx,
+        register_jobs,
+        register_schedule,
+        std::future::pending(),
+    )
+    .await
+}
+
+/// Starts the worker with an existing boot context and drains it once `shutdown` resolves:
+/// no new jobs are pulled, running jobs get `[worker] shutdown_grace` seconds to finish,
+/// then group locks and in-flight jobs are released.
+pub async fn start_with_shutdown<F, S, Sig>(
+    ctx: BootContext,
+    register_jobs: F,
+    register_schedule: Option<S>,
+    shutdown: Sig,
+) -> Result<()>
+where
+    F: FnOnce(&mut core_jobs::worker::Worker),
+    S: FnOnce(&mut core_jobs::cron::Scheduler),
+    Sig: std::future::Future<Output = ()> + Send + 'static,
 {
     info!("Starting Worker...");
 
     // 2. Initialize Worker with Context
     let mut extensions = axum::http::Extensions::new();
     extensions.insert(ctx.mailer.clone());
+    extensions.insert(ctx.notifications.clone());
     extensions.insert(ctx.storage.clone()); // Added explicit storage injection
 
     let job_ctx = core_jobs::JobContext {
@@ -25,20 +49,58 @@ where
         redis: ctx.redis.clone(),
         settings: ctx.settings.clone(),
         extensions,
+        cancellation: Default::default(),
     };
 
     // Use from_settings to ensure worker config (concurrency, sweeper) is loaded
     let mut worker = core_jobs::worker::Worker::from_settings(job_ctx).await?;
+    worker.set_events(ctx.job_events.clone());
 
     // 3. Register Framework Jobs
     // Auto-register HTTP log cleanup if needed (or always available)
     worker.register::<core_http_log::jobs::CleanupHttpLogs>();
+    worker.register::<core_http_log::DeliverWebhook>();
+    worker.register::<core_mailer::jobs::CleanupMailLogs>();
+    worker.register::<core_notify::SendNotificationJob>();
+    worker.register::<core_notify::SendNotificationDigests>();
 
     // 4. Register Jobs (Project Level)
     register_jobs(&mut worker);
 
-    // 4. Register & Spawn Scheduler (if provided)
     // 4. Register & Spawn Scheduler
+    let scheduler = build_scheduler(&ctx, register_schedule)?;
+
+    let token = worker.cancellation_token();
+    let shutdown_token = token.clone();
+    tokio::spawn(async move {
+        shutdown.await;
+        info!("Shutdown requested, draining worker...");
+        shutdown_token.cancel();
+    });
+
+    info!("Spawning Cron Scheduler...");
+    let scheduler_task = tokio::spawn(async move {
+        if let Err(e) = scheduler.run_until(token).await {
+            tracing::error!("Cron Scheduler crashed: {}", e);
+        }
+    });
+
+    // 5. Run Worker
+    worker.run().await?;
+    let _ = scheduler_task.await;
+
+    Ok(())
+}
+
+/// Builds the cron scheduler with framework and app schedules.
+/// Tasks run in `[app] timezone` unless they set their own.
+pub fn build_scheduler<S>(
+    ctx: &BootContext,
+    register_schedule: Option<S>,
+) -> Result<core_jobs::cron::Scheduler>
+where
+    S: FnOnce(&mut core_jobs::cron::Scheduler),
+{
     let queue_prefix = ctx
         .settings
         .redis
@@ -47,34 +109,36 @@ where
         .map(|prefix| format!("{prefix}:queue"))
         .unwrap_or_else(|| "queue".to_string());
     let queue =
-        core_jobs::queue::RedisQueue::new_with_prefix(&ctx.settings.redis.url, &queue_prefix)?;
+        core_jobs::queue::RedisQueue::new_with_prefix(&ctx.settings.redis.url, &queue_prefix)?
+            .with_events(ctx.job_events.clone());
     let mut scheduler = core_jobs::cron::Scheduler::new(queue);
+    scheduler.set_timezone(ctx.settings.i18n.default_timezone);
+    if ctx.settings.worker.driver == "postgres" {
+        scheduler
+            .dispatch_to(ctx.jobs.clone())
+            .use_postgres_locks(ctx.db.clone());
+    }
 
     // Framework Schedules
     if ctx.settings.http_log.retention_days > 0 {
         // Run daily at midnight
         scheduler.cron::<core_http_log::jobs::CleanupHttpLogs>("0 0 0 * * *");
     }
+    if ctx.settings.mail.log_enabled && ctx.settings.mail.log_retention_days > 0 {
+        scheduler.cron::<core_mailer::jobs::CleanupMailLogs>("0 0 0 * * *");
+    }
+    // Hourly: digests of ended hourly and daily windows
+    scheduler.cron::<core_notify::SendNotificationDigests>("0 0 * * * *");
 
     // App Schedules
     if let Some(reg) = register_schedule {
         reg(&mut scheduler);
     }
 
-    info!("Spawning Cron Scheduler...");
-    tokio::spawn(async move {
-        if let Err(e) = scheduler.run().await {
-            tracing::error!("Cron Scheduler crashed: {}", e);
-        }
-    });
-
-    // 5. Run Worker
-    worker.run().await?;
-
-    Ok(())
+    Ok(scheduler)
 }
 
-/// Starts the worker.
+/// Starts the worker and drains it gracefully on SIGTERM/SIGINT.
 ///
 /// # Arguments
 /// * `register_jobs` - A function that registers jobs on the worker.
@@ -86,5 +150,36 @@ where
 {
     // 1. Common Boot (includes DB/Redis connect)
     let (ctx, _guard) = init_app().await?;
-    start_with_context(ctx, register_jobs, register_schedule).await
+    start_with_shutdown(ctx, register_jobs, register_schedule, shutdown_signal()).await
+}
+
+/// Resolves on SIGINT (Ctrl+C) or, on Unix, SIGTERM (Supervisor, systemd, Docker).
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
 }
@@ -3,5 +3,6 @@ pub mod boot;
 pub mod console;
 pub mod jobs;
 pub mod realtime;
+pub mod schedule;
 pub mod state;
 pub mod web;
@@ -0,0 +1,96 @@
+use anyhow::Result;
+use clap::Subcommand;
+
+use crate::boot::BootContext;
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ScheduleCommands {
+    /// List scheduled tasks with their next run time
+    List,
+}
+
+pub fn handle<S>(
+    cmd: ScheduleCommands,
+    ctx: &BootContext,
+    register_schedule: Option<S>,
+) -> Result<()>
+where
+    S: FnOnce(&mut core_jobs::cron::Scheduler),
+{
+    match cmd {
+        ScheduleCommands::List => list(ctx, register_schedule),
+    }
+}
+
+fn list<S>(ctx: &BootContext, register_schedule: Option<S>) -> Result<()>
+where
+    S: FnOnce(&mut core_jobs::cron::Scheduler),
+{
+    let scheduler = crate::jobs::build_scheduler(ctx, register_schedule)?;
+    let tasks = scheduler.list();
+    if tasks.is_empty() {
+        println!("No scheduled tasks.");
+        return Ok(());
+    }
+
+    let rows: Vec<[String; 4]> = tasks
+        .iter()
+        .map(|task| {
+            let mut flags = Vec::new();
+            if let Some(ttl) = task.without_overlapping {
+                flags.push(format!("without_overlapping({ttl}s)"));
+            }
+            if task.catch_up {
+                flags.push("catch_up".to_string());
+            }
+            if task.conditional {
+                flags.push("when".to_string());
+            }
+            let next_run = match task.next_run {
+                Some(at) => format!("{} {}", format_datetime(at), task.timezone),
+                None => "never (invalid expression?)".to_string(),
+            };
+            [
+                task.name.clone(),
+                task.expression.clone(),
+                next_run,
+                flags.join(", "),
+            ]
+        })
+        .collect();
+
+    let headers = ["Task", "Expression", "Next run", "Options"];
+    let mut widths = headers.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let print_row = |cells: [&str; 4]| {
+        let line: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        println!("{}", line.join("  ").trim_end());
+    };
+    print_row(headers);
+    for row in &rows {
+        print_row([&row[0], &row[1], &row[2], &row[3]]);
+    }
+
+    Ok(())
+}
+
+fn format_datetime(at: time::OffsetDateTime) -> String {
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        at.year(),
+        u8::from(at.month()),
+        at.day(),
+        at.hour(),
+        at.minute(),
+        at.second()
+    )
+}
@@ -13,6 +13,8 @@ pub struct FrameworkState {
     pub redis: Cache,
     pub storage: Arc<dyn Storage>,
     pub queue: core_jobs::queue::RedisQueue,
+    pub jobs: Arc<dyn core_jobs::driver::QueueDriver>,
+    pub job_events: core_jobs::events::JobEvents,
 
     // Configs often needed in handlers
     // Configs often needed in handlers
@@ -26,6 +28,8 @@ impl From<BootContext> for FrameworkState {
             redis: ctx.redis,
             storage: ctx.storage,
             queue: ctx.queue,
+            jobs: ctx.jobs,
+            job_events: ctx.job_events,
             settings: ctx.settings,
         }
     }
@@ -50,6 +54,18 @@ impl FromRef<FrameworkState> for core_jobs::queue::RedisQueue {
     }
 }
 
+impl FromRef<FrameworkState> for Arc<dyn core_jobs::driver::QueueDriver> {
+    fn from_ref(state: &FrameworkState) -> Self {
+        state.jobs.clone()
+    }
+}
+
+impl FromRef<FrameworkState> for core_jobs::events::JobEvents {
+    fn from_ref(state: &FrameworkState) -> Self {
+        state.job_events.clone()
+    }
+}
+
 impl FromRef<FrameworkState> for Arc<core_config::Settings> {
     fn from_ref(state: &FrameworkState) -> Self {
         state.settings.clone()
@@ -541,16 +541,26 @@ pub struct CdnSettings {
 #[serde(default)]
 pub struct WorkerSettings {
     pub enabled: bool,
+    /// Queue backend: `redis` (default) or `postgres` (the `queue_jobs` table).
+    pub driver: String,
     pub concurrency: usize,
     pub sweep_interval: u64,
+    /// At-least-once delivery: popped jobs are tracked in a per-worker processing
+    /// list and re-queued if the worker dies before finishing them. Off by default.
+    pub reliable: bool,
+    /// Seconds running jobs get to finish after SIGTERM/SIGINT before they are aborted.
+    pub shutdown_grace: u64,
 }
 
 impl Default for WorkerSettings {
     fn default() -> Self {
         Self {
             enabled: false,
+            driver: "redis".into(),
             concurrency: 10,
             sweep_interval: 30,
+            reliable: false,
+            shutdown_grace: 30,
         }
     }
 }
@@ -643,12 +653,34 @@ pub struct GuardConfig {
 #[serde(default)]
 pub struct MailSettings {
     pub enable: bool,
+    /// `smtp`, `log` (default), `file`, `memory`, `sendmail`, `postmark`, `mailgun`, `http`
+    /// or `failover`.
     pub driver: String,
+    /// Comma-separated drivers tried in order when `driver = "failover"` (e.g. `smtp,postmark`).
+    pub failover: String,
+    /// Per-transport send limits, comma-separated `driver=count/seconds` (e.g. `smtp=10/1`).
+    /// Counted in each process, not across the deployment: N workers send up to N times the
+    /// limit. Sends over the limit wait for the next window.
+    pub rate_limits_per_process: String,
+    /// Record every send attempt in `mail_logs`.
+    pub log_enabled: bool,
+    /// Days to keep `mail_logs` rows; 0 disables the cleanup job.
+    pub log_retention_days: u64,
     pub host: String,
     pub port: u16,
     pub username: String,
     pub password: String,
     pub from_address: String,
+    /// Directory for `.eml` files (`file` driver).
+    pub file_dir: String,
+    /// Binary for the `sendmail` driver.
+    pub sendmail_path: String,
+    /// API key or token for `postmark`, `mailgun` and `http`.
+    pub api_key: String,
+    /// API base URL; empty uses the provider default (required for `http`).
+    pub api_url: String,
+    /// Mailgun sending domain.
+    pub api_domain: String,
 }
 
 impl Default for MailSettings {
@@ -656,11 +688,20 @@ impl Default for MailSettings {
         Self {
             enable: false,
             driver: "log".into(),
+            failover: String::new(),
+            rate_limits_per_process: String::new(),
+            log_enabled: true,
+            log_retention_days: 30,
             host: "smtp.mailtrap.io".into(),
             port: 2525,
             username: String::new(),
             password: String::new(),
             from_address: "hello@example.com".into(),
+            file_dir: "storage/mail".into(),
+            sendmail_path: "sendmail".into(),
+            api_key: String::new(),
+            api_url: String::new(),
+            api_domain: String::new(),
         }
     }
 }
@@ -681,6 +722,14 @@ impl MailSettings {
             Some(&self.password)
         }
     }
+
+    pub fn api_url_opt(&self) -> Option<&str> {
+        if self.api_url.is_empty() {
+            None
+        } else {
+            Some(&self.api_url)
+        }
+    }
 }
 
 #[derive(Debug, Clone, serde::Deserialize)]
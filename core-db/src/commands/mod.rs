@@ -254,6 +254,182 @@ CREATE INDEX IF NOT EXISTS idx_sql_profiler_queries_created_at ON sql_profiler_q
         fs::write(&profiler_path, profiler_sql).await?;
         println!("Created/Updated: {}", profiler_path.display());
 
+        // 10. Outbox Jobs: delayed dispatch
+        let outbox_delay_sql = r#"
+ALTER TABLE outbox_jobs ADD COLUMN IF NOT EXISTS available_at TIMESTAMPTZ;
+"#;
+        let outbox_delay_path = migrations_dir.join("0000000000010_outbox_jobs_available_at.sql");
+        fs::write(&outbox_delay_path, outbox_delay_sql).await?;
+        println!("Created/Updated: {}", outbox_delay_path.display());
+
+        // 11. Queue Jobs (Postgres queue driver)
+        let queue_jobs_sql = r#"
+CREATE TABLE IF NOT EXISTS queue_jobs (
+    id UUID PRIMARY KEY,
+    queue TEXT NOT NULL,
+    group_id TEXT,
+    unique_key TEXT,
+    payload JSONB NOT NULL,
+    attempts INT NOT NULL DEFAULT 0,
+    available_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    queued_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    reserved_at TIMESTAMPTZ,
+    reserved_by TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS idx_queue_jobs_available ON queue_jobs(queue, available_at);
+CREATE INDEX IF NOT EXISTS idx_queue_jobs_group ON queue_jobs(queue, group_id, queued_at) WHERE group_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_queue_jobs_reserved_by ON queue_jobs(reserved_by) WHERE reserved_by IS NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_queue_jobs_unique_key ON queue_jobs(unique_key);
+"#;
+        let queue_jobs_path = migrations_dir.join("0000000000011_queue_jobs.sql");
+        fs::write(&queue_jobs_path, queue_jobs_sql).await?;
+        println!("Created/Updated: {}", queue_jobs_path.display());
+
+        // 12. Paused Queues (skipped by workers)
+        let paused_queues_sql = r#"
+CREATE TABLE IF NOT EXISTS paused_queues (
+    queue TEXT PRIMARY KEY,
+    paused_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+"#;
+        let paused_queues_path = migrations_dir.join("0000000000012_paused_queues.sql");
+        fs::write(&paused_queues_path, paused_queues_sql).await?;
+        println!("Created/Updated: {}", paused_queues_path.display());
+
+        // 13. Mail Logs (written by core_mailer::Mailer)
+        let mail_logs_sql = r#"
+CREATE TABLE IF NOT EXISTS mail_logs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    recipient TEXT NOT NULL,
+    subject TEXT NOT NULL,
+    transport TEXT NOT NULL,
+    message_id TEXT,
+    status TEXT NOT NULL,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS idx_mail_logs_created_at ON mail_logs(created_at);
+CREATE INDEX IF NOT EXISTS idx_mail_logs_recipient ON mail_logs(recipient);
+CREATE INDEX IF NOT EXISTS idx_mail_logs_status ON mail_logs(status);
+"#;
+        let mail_logs_path = migrations_dir.join("0000000000013_mail_logs.sql");
+        fs::write(&mail_logs_path, mail_logs_sql).await?;
+        println!("Created/Updated: {}", mail_logs_path.display());
+
+        // 14. Notifications (core_notify database channel)
+        let notifications_sql = r#"
+CREATE TABLE IF NOT EXISTS notifications (
+    id UUID PRIMARY KEY,
+    notifiable_type TEXT NOT NULL,
+    notifiable_id TEXT NOT NULL,
+    notification_type TEXT NOT NULL,
+    data JSONB NOT NULL,
+    read_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS idx_notifications_notifiable ON notifications(notifiable_type, notifiable_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_notifications_unread ON notifications(notifiable_type, notifiable_id) WHERE read_at IS NULL;
+"#;
+        let notifications_path = migrations_dir.join("0000000000014_notifications.sql");
+        fs::write(&notifications_path, notifications_sql).await?;
+        println!("Created/Updated: {}", notifications_path.display());
+
+        // 15. Notification Preferences (per-channel opt-out)
+        let notification_preferences_sql = r#"
+CREATE TABLE IF NOT EXISTS notification_preferences (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    notifiable_type TEXT NOT NULL,
+    notifiable_id TEXT NOT NULL,
+    notification_type TEXT NOT NULL,
+    channel TEXT NOT NULL,
+    enabled BOOLEAN NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_notification_preferences_key ON notification_preferences(notifiable_type, notifiable_id, notification_type, channel);
+"#;
+        let notification_preferences_path =
+            migrations_dir.join("0000000000015_notification_preferences.sql");
+        fs::write(&notification_preferences_path, notification_preferences_sql).await?;
+        println!(
+            "Created/Updated: {}",
+            notification_preferences_path.display()
+        );
+
+        // 16. Notification Digest Items (bundled and throttled notifications)
+        let notification_digest_items_sql = r#"
+CREATE TABLE IF NOT EXISTS notification_digest_items (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    notifiable_type TEXT NOT NULL,
+    notifiable_id TEXT NOT NULL,
+    notification_type TEXT NOT NULL,
+    notifiable JSONB NOT NULL,
+    data JSONB NOT NULL,
+    status TEXT NOT NULL,
+    delivered_channels JSONB NOT NULL DEFAULT '[]',
+    deliver_after TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS idx_notification_digest_items_due ON notification_digest_items(status, deliver_after);
+CREATE INDEX IF NOT EXISTS idx_notification_digest_items_window ON notification_digest_items(notifiable_type, notifiable_id, notification_type, deliver_after);
+"#;
+        let notification_digest_items_path =
+            migrations_dir.join("0000000000016_notification_digest_items.sql");
+        fs::write(
+            &notification_digest_items_path,
+            notification_digest_items_sql,
+        )
+        .await?;
+        println!(
+            "Created/Updated: {}",
+            notification_digest_items_path.display()
+        );
+
+        // 17. Webhook Endpoints (outbound webhook subscriptions)
+        let webhook_endpoints_sql = r#"
+CREATE TABLE IF NOT EXISTS webhook_endpoints (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    url TEXT NOT NULL,
+    -- AES-GCM ciphertext under [app] key (core_db::common::crypt)
+    secret TEXT NOT NULL,
+    events JSONB NOT NULL DEFAULT '["*"]',
+    description TEXT,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    failure_count INT NOT NULL DEFAULT 0,
+    disabled_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS idx_webhook_endpoints_active ON webhook_endpoints(active);
+"#;
+        let webhook_endpoints_path = migrations_dir.join("0000000000017_webhook_endpoints.sql");
+        fs::write(&webhook_endpoints_path, webhook_endpoints_sql).await?;
+        println!("Created/Updated: {}", webhook_endpoints_path.display());
+
+        // 18. Webhook Logs: signature verification outcome
+        let webhook_verification_sql = r#"
+ALTER TABLE webhook_logs ADD COLUMN IF NOT EXISTS verification_status TEXT;
+ALTER TABLE webhook_logs ADD COLUMN IF NOT EXISTS verification_error TEXT;
+CREATE INDEX IF NOT EXISTS idx_webhook_logs_verification_status ON webhook_logs(verification_status);
+"#;
+        let webhook_verification_path =
+            migrations_dir.join("0000000000018_webhook_logs_verification.sql");
+        fs::write(&webhook_verification_path, webhook_verification_sql).await?;
+        println!("Created/Updated: {}", webhook_verification_path.display());
+
+        // 19. Cron State: scheduler run locks, cursor and last runs (Postgres queue driver)
+        let cron_state_sql = r#"
+CREATE TABLE IF NOT EXISTS cron_state (
+    key TEXT PRIMARY KEY,
+    value BIGINT NOT NULL,
+    expires_at TIMESTAMPTZ
+);
+CREATE INDEX IF NOT EXISTS idx_cron_state_expires_at ON cron_state(expires_at) WHERE expires_at IS NOT NULL;
+"#;
+        let cron_state_path = migrations_dir.join("0000000000019_cron_state.sql");
+        fs::write(&cron_state_path, cron_state_sql).await?;
+        println!("Created/Updated: {}", cron_state_path.display());
+
         Ok(())
     }
 }
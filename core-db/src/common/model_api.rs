@@ -1677,6 +1677,802 @@ where
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// Bulk create — create_many / insert_select
+// ---------------------------------------------------------------------------
+
+/// Bind-parameter ceiling for a single Postgres statement.
+const MAX_BIND_PARAMS: usize = 65_535;
+
+/// Chunks of at least this many rows are inserted with one array bind per column
+/// (`SELECT * FROM UNNEST(..)`) instead of a multi-row `VALUES` list.
+pub const CREATE_MANY_UNNEST_MIN_ROWS: usize = 500;
+
+pub const CREATE_MANY_DEFAULT_CHUNK_SIZE: usize = 1_000;
+
+/// How `create_many` reports to the task-local model observer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BulkObserverMode {
+    /// `on_creating` / `on_created` for every row, like `create()`.
+    #[default]
+    PerRow,
+    /// `on_creating_many` / `on_created_many` once per call.
+    Batched,
+    /// No observer hooks.
+    Skip,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateManyOptions {
+    pub conflict: Option<CreateConflictSpec>,
+    pub chunk_size: usize,
+    pub observers: BulkObserverMode,
+}
+
+impl Default for CreateManyOptions {
+    fn default() -> Self {
+        Self {
+            conflict: None,
+            chunk_size: CREATE_MANY_DEFAULT_CHUNK_SIZE,
+            observers: BulkObserverMode::PerRow,
+        }
+    }
+}
+
+/// One column of a chunk as a typed Postgres array, for `UNNEST`.
+enum BindArray {
+    I16(Vec<Option<i16>>),
+    I32(Vec<Option<i32>>),
+    I64(Vec<Option<i64>>),
+    F64(Vec<Option<f64>>),
+    Decimal(Vec<Option<rust_decimal::Decimal>>),
+    Bool(Vec<Option<bool>>),
+    String(Vec<Option<String>>),
+    Time(Vec<Option<time::OffsetDateTime>>),
+    Uuid(Vec<Option<Uuid>>),
+    Json(Vec<Option<sqlx::types::Json<serde_json::Value>>>),
+}
+
+macro_rules! push_bind_array {
+    ($out:ident, $variant:ident, $value:expr) => {
+        match $out.get_or_insert_with(|| BindArray::$variant(Vec::new())) {
+            BindArray::$variant(items) => items.push($value),
+            _ => return None,
+        }
+    };
+}
+
+impl BindArray {
+    /// `None` when the values mix types or hold `text[]`, which `UNNEST` would flatten.
+    fn collect<'a>(values: impl Iterator<Item = &'a BindValue>) -> Option<Self> {
+        let mut out: Option<Self> = None;
+        for value in values {
+            match value {
+                BindValue::I16(v) => push_bind_array!(out, I16, Some(*v)),
+                BindValue::I16Opt(v) => push_bind_array!(out, I16, *v),
+                BindValue::I32(v) => push_bind_array!(out, I32, Some(*v)),
+                BindValue::I32Opt(v) => push_bind_array!(out, I32, *v),
+                BindValue::I64(v) => push_bind_array!(out, I64, Some(*v)),
+                BindValue::I64Opt(v) => push_bind_array!(out, I64, *v),
+                BindValue::F64(v) => push_bind_array!(out, F64, Some(*v)),
+                BindValue::F64Opt(v) => push_bind_array!(out, F64, *v),
+                BindValue::Decimal(v) => push_bind_array!(out, Decimal, Some(*v)),
+                BindValue::DecimalOpt(v) => push_bind_array!(out, Decimal, *v),
+                BindValue::Bool(v) => push_bind_array!(out, Bool, Some(*v)),
+                BindValue::BoolOpt(v) => push_bind_array!(out, Bool, *v),
+                BindValue::String(v) => push_bind_array!(out, String, Some(v.clone())),
+                BindValue::StringOpt(v) => push_bind_array!(out, String, v.clone()),
+                BindValue::Time(v) => push_bind_array!(out, Time, Some(*v)),
+                BindValue::TimeOpt(v) => push_bind_array!(out, Time, *v),
+                BindValue::Uuid(v) => push_bind_array!(out, Uuid, Some(*v)),
+                BindValue::UuidOpt(v) => push_bind_array!(out, Uuid, *v),
+                BindValue::Json(v) => {
+                    push_bind_array!(out, Json, Some(sqlx::types::Json(v.clone())))
+                }
+                BindValue::JsonOpt(v) => {
+                    push_bind_array!(out, Json, v.clone().map(sqlx::types::Json))
+                }
+                BindValue::StringArray(_) | BindValue::StringArrayOpt(_) => return None,
+            }
+        }
+        out
+    }
+
+    fn bind<'q, T>(
+        self,
+        q: crate::common::sql::PgQueryAs<'q, T>,
+    ) -> crate::common::sql::PgQueryAs<'q, T> {
+        match self {
+            BindArray::I16(v) => q.bind(v),
+            BindArray::I32(v) => q.bind(v),
+            BindArray::I64(v) => q.bind(v),
+            BindArray::F64(v) => q.bind(v),
+            BindArray::Decimal(v) => q.bind(v),
+            BindArray::Bool(v) => q.bind(v),
+            BindArray::String(v) => q.bind(v),
+            BindArray::Time(v) => q.bind(v),
+            BindArray::Uuid(v) => q.bind(v),
+            BindArray::Json(v) => q.bind(v),
+        }
+    }
+}
+
+/// Columns set by any row, in first-seen order.
+fn create_many_columns(rows: &[CreateState<'_>]) -> Vec<&'static str> {
+    let mut cols: Vec<&'static str> = Vec::new();
+    for row in rows {
+        for assignment in &row.assignments {
+            if !cols.contains(&assignment.col_sql) {
+                cols.push(assignment.col_sql);
+            }
+        }
+    }
+    cols
+}
+
+/// `ON CONFLICT` clause for a multi-row insert; updates take the incoming values from
+/// `EXCLUDED`, except for `keep` (primary key, `created_at`) so existing rows keep their identity.
+fn create_many_conflict_sql(
+    conflict: Option<&CreateConflictSpec>,
+    cols: &[&'static str],
+    keep: &[&str],
+) -> String {
+    let Some(conflict) = conflict.filter(|conflict| !conflict.cols.is_empty()) else {
+        return String::new();
+    };
+    let target = conflict.cols.join(", ");
+    let set_clauses: Vec<String> = cols
+        .iter()
+        .filter(|col| !conflict.cols.contains(col) && !keep.contains(col))
+        .map(|col| format!("{col} = EXCLUDED.{col}"))
+        .collect();
+    match conflict.action {
+        CreateConflictAction::Update if !set_clauses.is_empty() => {
+            format!(
+                " ON CONFLICT ({}) DO UPDATE SET {}",
+                target,
+                set_clauses.join(", ")
+            )
+        }
+        _ => format!(" ON CONFLICT ({}) DO NOTHING", target),
+    }
+}
+
+/// Postgres rejects `DO UPDATE` touching the same row twice in one statement,
+/// so only the last row per conflict key is kept.
+fn dedupe_conflicting_rows<'db>(
+    rows: Vec<CreateState<'db>>,
+    conflict_cols: &[&'static str],
+) -> Vec<CreateState<'db>> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut kept: Vec<Option<CreateState<'db>>> = Vec::with_capacity(rows.len());
+    for row in rows {
+        let key: Option<Vec<String>> = conflict_cols
+            .iter()
+            .map(|col| row.col_value(col).map(|value| format!("{:?}", value)))
+            .collect();
+        if let Some(key) = key {
+            if let Some(previous) = seen.insert(key.join("\u{1f}"), kept.len()) {
+                kept[previous] = None;
+            }
+        }
+        kept.push(Some(row));
+    }
+    kept.into_iter().flatten().collect()
+}
+
+fn build_insert_values_sql(
+    table: &str,
+    cols: &[&'static str],
+    rows: &[CreateState<'_>],
+    conflict: Option<&CreateConflictSpec>,
+    keep: &[&str],
+) -> (String, Vec<BindValue>) {
+    let mut binds = Vec::with_capacity(rows.len() * cols.len());
+    let mut tuples = Vec::with_capacity(rows.len());
+    for row in rows {
+        let values: Vec<String> = cols
+            .iter()
+            .map(|col| match row.col_value(col) {
+                Some(value) => {
+                    binds.push(value.clone());
+                    format!("${}", binds.len())
+                }
+                None => "DEFAULT".to_string(),
+            })
+            .collect();
+        tuples.push(format!("({})", values.join(", ")));
+    }
+    let sql = format!(
+        "INSERT INTO {} ({}) VALUES {}{} RETURNING *",
+        table,
+        cols.join(", "),
+        tuples.join(", "),
+        create_many_conflict_sql(conflict, cols, keep)
+    );
+    (sql, binds)
+}
+
+/// `None` unless every row sets every column and each column fits one array type.
+fn build_insert_unnest_sql(
+    table: &str,
+    cols: &[&'static str],
+    rows: &[CreateState<'_>],
+    conflict: Option<&CreateConflictSpec>,
+    keep: &[&str],
+) -> Option<(String, Vec<BindArray>)> {
+    let mut arrays = Vec::with_capacity(cols.len());
+    for col in cols {
+        let values: Option<Vec<&BindValue>> = rows.iter().map(|row| row.col_value(col)).collect();
+        arrays.push(BindArray::collect(values?.into_iter())?);
+    }
+    let (placeholders, _) = make_placeholders(cols.len(), 1);
+    let sql = format!(
+        "INSERT INTO {table} ({cols}) SELECT * FROM UNNEST({placeholders}) AS t({cols}){conflict} RETURNING *",
+        cols = cols.join(", "),
+        placeholders = placeholders.join(", "),
+        conflict = create_many_conflict_sql(conflict, cols, keep),
+    );
+    Some((sql, arrays))
+}
+
+pub fn create_many_runtime<'db, M>(
+    db: DbConn<'db>,
+    mut rows: Vec<CreateState<'db>>,
+    options: CreateManyOptions,
+) -> BoxModelFuture<'db, Vec<M::Row>>
+where
+    M: CreateModel + RuntimeModel,
+    M::Create: Serialize,
+    M::Row: Serialize,
+{
+    Box::pin(async move {
+        use crate::common::model_observer::{
+            log_observer_error, try_get_observer, ModelEvent, ObserverAction,
+        };
+
+        if rows.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let observer = match options.observers {
+            BulkObserverMode::Skip => None,
+            BulkObserverMode::PerRow | BulkObserverMode::Batched => try_get_observer(),
+        };
+        let event = ModelEvent {
+            model: M::MODEL_KEY,
+            table: M::TABLE,
+            record_key: None,
+        };
+
+        if let Some(observer) = observer.as_ref() {
+            if options.observers == BulkObserverMode::Batched {
+                let inputs = rows
+                    .iter()
+                    .map(M::build_create_input)
+                    .collect::<Result<Vec<_>>>()?;
+                let data = serde_json::to_value(&inputs)?;
+                match observer.on_creating_many(&event, &data).await? {
+                    ObserverAction::Prevent(err) => return Err(err),
+                    ObserverAction::Modify(overrides) => {
+                        rows = rows
+                            .into_iter()
+                            .map(|row| M::apply_create_overrides(row, overrides.clone()))
+                            .collect::<Result<Vec<_>>>()?;
+                    }
+                    ObserverAction::Continue => {}
+                }
+            } else {
+                let mut observed = Vec::with_capacity(rows.len());
+                for row in rows {
+                    let data = serde_json::to_value(M::build_create_input(&row)?)?;
+                    match observer.on_creating(&event, &data).await? {
+                        ObserverAction::Prevent(err) => return Err(err),
+                        ObserverAction::Modify(overrides) => {
+                            observed.push(M::apply_create_overrides(row, overrides)?);
+                        }
+                        ObserverAction::Continue => observed.push(row),
+                    }
+                }
+                rows = observed;
+            }
+        }
+
+        let inserted = match db {
+            DbConn::Pool(pool) => {
+                let tx = pool.begin().await?;
+                let tx_lock = std::sync::Arc::new(tokio::sync::Mutex::new(tx));
+                let result = {
+                    let db = DbConn::tx(tx_lock.clone());
+                    create_many_with_db_runtime::<M>(db, rows, &options).await?
+                };
+                let tx = std::sync::Arc::try_unwrap(tx_lock)
+                    .map_err(|_| anyhow::anyhow!("transaction scope still has active handles"))?
+                    .into_inner();
+                tx.commit().await?;
+                result
+            }
+            DbConn::Tx(_) => create_many_with_db_runtime::<M>(db, rows, &options).await?,
+        };
+
+        if let Some(observer) = observer.as_ref() {
+            if options.observers == BulkObserverMode::Batched {
+                match serde_json::to_value(&inserted) {
+                    Ok(data) => {
+                        if let Err(err) = observer.on_created_many(&event, &data).await {
+                            log_observer_error("created", M::MODEL_KEY, &err);
+                        }
+                    }
+                    Err(err) => log_observer_error("created", M::MODEL_KEY, &err),
+                }
+            } else {
+                for row in &inserted {
+                    let event = ModelEvent {
+                        record_key: Some(M::created_row_key(row)),
+                        ..event.clone()
+                    };
+                    match serde_json::to_value(row) {
+                        Ok(data) => {
+                            if let Err(err) = observer.on_created(&event, &data).await {
+                                log_observer_error("created", M::MODEL_KEY, &err);
+                            }
+                        }
+                        Err(err) => log_observer_error("created", M::MODEL_KEY, &err),
+                    }
+                }
+            }
+        }
+
+        Ok(inserted)
+    })
+}
+
+pub async fn create_many_with_db_runtime<'db, M>(
+    db: DbConn<'db>,
+    rows: Vec<CreateState<'db>>,
+    options: &CreateManyOptions,
+) -> Result<Vec<M::Row>>
+where
+    M: CreateModel + RuntimeModel + FeaturePersistenceModel,
+    M::Row: Serialize,
+{
+    let now = time::OffsetDateTime::now_utc();
+    let mut prepared = Vec::with_capacity(rows.len());
+    for mut state in rows {
+        if M::USE_SNOWFLAKE_ID && !state.has_col(M::PK_COL) {
+            state = state.set_col(
+                M::PK_COL,
+                crate::common::sql::generate_snowflake_i64().into(),
+            );
+        }
+        if M::HAS_CREATED_AT && !state.has_col("created_at") {
+            state = state.set_col("created_at", now.into());
+        }
+        if M::HAS_UPDATED_AT && !state.has_col("updated_at") {
+            state = state.set_col("updated_at", now.into());
+        }
+        if state.assignments.is_empty() {
+            anyhow::bail!("insert: no columns set");
+        }
+        prepared.push(state);
+    }
+
+    let conflict = options
+        .conflict
+        .as_ref()
+        .filter(|conflict| !conflict.cols.is_empty());
+    let rows = match conflict {
+        Some(conflict) if conflict.action == CreateConflictAction::Update => {
+            dedupe_conflicting_rows(prepared, &conflict.cols)
+        }
+        _ => prepared,
+    };
+    let cols = create_many_columns(&rows);
+    let keep = [M::PK_COL, "created_at"];
+
+    // Skipped rows are missing from RETURNING, so side tables are matched by primary key
+    if create_many_conflict_sql(conflict, &cols, &keep).ends_with("DO NOTHING")
+        && rows
+            .iter()
+            .any(|row| row.has_feature_state() && create_many_pk_key(row, M::PK_COL).is_none())
+    {
+        anyhow::bail!(
+            "{}: create_many rows with translations, meta or attachments need a primary key when conflicts are skipped",
+            M::TABLE
+        );
+    }
+    let replace_attachments =
+        conflict.is_some_and(|conflict| conflict.action == CreateConflictAction::Update);
+
+    let values_rows = (MAX_BIND_PARAMS / cols.len().max(1)).max(1);
+    let mut inserted = Vec::with_capacity(rows.len());
+    for chunk in rows.chunks(options.chunk_size.max(1)) {
+        let unnest = if chunk.len() >= CREATE_MANY_UNNEST_MIN_ROWS {
+            build_insert_unnest_sql(M::TABLE, &cols, chunk, conflict, &keep)
+        } else {
+            None
+        };
+        if let Some((sql, arrays)) = unnest {
+            let profiler_binds =
+                if M::PROFILE_QUERIES && crate::common::sql::is_sql_profiler_enabled() {
+                    format!("[{} rows via UNNEST]", chunk.len())
+                } else {
+                    String::new()
+                };
+            let mut q = sqlx::query_as::<_, M::Row>(&sql);
+            for array in arrays {
+                q = array.bind(q);
+            }
+            let returned = create_many_fetch::<M>(&db, &sql, q, &profiler_binds).await?;
+            persist_create_many_state::<M>(
+                db.clone(),
+                chunk,
+                &returned,
+                conflict,
+                replace_attachments,
+            )
+            .await?;
+            inserted.extend(returned);
+            continue;
+        }
+
+        for part in chunk.chunks(values_rows) {
+            let (sql, binds) = build_insert_values_sql(M::TABLE, &cols, part, conflict, &keep);
+            let profiler_binds =
+                if M::PROFILE_QUERIES && crate::common::sql::is_sql_profiler_enabled() {
+                    profiler_binds_string(&binds)
+                } else {
+                    String::new()
+                };
+            let mut q = sqlx::query_as::<_, M::Row>(&sql);
+            for bind in binds {
+                q = crate::common::sql::bind(q, bind);
+            }
+            let returned = create_many_fetch::<M>(&db, &sql, q, &profiler_binds).await?;
+            persist_create_many_state::<M>(
+                db.clone(),
+                part,
+                &returned,
+                conflict,
+                replace_attachments,
+            )
+            .await?;
+            inserted.extend(returned);
+        }
+    }
+    Ok(inserted)
+}
+
+async fn create_many_fetch<'db, 'q, M>(
+    db: &DbConn<'db>,
+    sql: &str,
+    query: crate::common::sql::PgQueryAs<'q, M::Row>,
+    profiler_binds: &str,
+) -> Result<Vec<M::Row>>
+where
+    M: RuntimeModel,
+{
+    let profiler_start = if M::PROFILE_QUERIES {
+        Some(std::time::Instant::now())
+    } else {
+        None
+    };
+    let rows = db.fetch_all(query).await?;
+    if let Some(start) = profiler_start {
+        crate::common::sql::record_profiled_query(
+            M::TABLE,
+            "INSERT",
+            sql,
+            profiler_binds,
+            start.elapsed(),
+        );
+    }
+    Ok(rows)
+}
+
+/// `created_row_key` form of the value a row sets for `col`, if it has a key-like type.
+fn create_many_pk_key(state: &CreateState<'_>, col: &str) -> Option<String> {
+    match state.col_value(col)? {
+        BindValue::I16(v) | BindValue::I16Opt(Some(v)) => Some(v.to_string()),
+        BindValue::I32(v) | BindValue::I32Opt(Some(v)) => Some(v.to_string()),
+        BindValue::I64(v) | BindValue::I64Opt(Some(v)) => Some(v.to_string()),
+        BindValue::String(v) | BindValue::StringOpt(Some(v)) => Some(v.clone()),
+        BindValue::Uuid(v) | BindValue::UuidOpt(Some(v)) => Some(v.to_string()),
+        _ => None,
+    }
+}
+
+/// Key of the values a row sets for `cols`.
+fn create_many_state_key(state: &CreateState<'_>, cols: &[&str]) -> Option<String> {
+    cols.iter()
+        .map(|col| create_many_pk_key(state, col))
+        .collect::<Option<Vec<_>>>()
+        .map(|parts| parts.join("\u{1f}"))
+}
+
+/// Key of a returned row's `cols`, read from its JSON form.
+fn create_many_row_key(row: &serde_json::Value, cols: &[&str]) -> Option<String> {
+    cols.iter()
+        .map(|col| match row.get(*col)? {
+            serde_json::Value::String(v) => Some(v.clone()),
+            serde_json::Value::Number(v) => Some(v.to_string()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(|parts| parts.join("\u{1f}"))
+}
+
+/// Pair returned rows (by index) with the input rows carrying feature state, on `cols`.
+/// `None` when such an input row does not set every column.
+fn pair_create_many_rows<'s, 'db>(
+    states: &'s [CreateState<'db>],
+    cols: &[&str],
+    row_keys: &[Option<String>],
+) -> Option<Vec<(usize, &'s CreateState<'db>)>> {
+    let by_key: HashMap<String, &'s CreateState<'db>> = states
+        .iter()
+        .filter(|state| state.has_feature_state())
+        .map(|state| create_many_state_key(state, cols).map(|key| (key, state)))
+        .collect::<Option<_>>()?;
+    Some(
+        row_keys
+            .iter()
+            .enumerate()
+            .filter_map(|(index, key)| Some((index, *by_key.get(key.as_ref()?)?)))
+            .collect(),
+    )
+}
+
+/// Side tables and touch targets for one inserted statement. RETURNING order is not
+/// guaranteed, so rows are matched by primary key, or by the conflict target for upserts
+/// on another unique column (conflicting rows keep their existing key). Only rows whose
+/// key the database generates fall back to pairing by position.
+async fn persist_create_many_state<'db, M>(
+    db: DbConn<'db>,
+    states: &[CreateState<'db>],
+    rows: &[M::Row],
+    conflict: Option<&CreateConflictSpec>,
+    replace_attachments: bool,
+) -> Result<()>
+where
+    M: CreateModel + RuntimeModel,
+    M::Row: Serialize,
+{
+    if states.iter().any(CreateState::has_feature_state) {
+        let match_cols: &[&str] = match conflict {
+            Some(conflict)
+                if conflict.action == CreateConflictAction::Update
+                    && conflict.cols != [M::PK_COL] =>
+            {
+                &conflict.cols
+            }
+            _ => &[M::PK_COL],
+        };
+        let by_pk = match_cols == [M::PK_COL];
+        let row_keys = rows
+            .iter()
+            .map(|row| {
+                if by_pk {
+                    Ok(Some(M::created_row_key(row)))
+                } else {
+                    Ok(create_many_row_key(&serde_json::to_value(row)?, match_cols))
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut owners: Vec<(i64, &CreateState<'db>)> = Vec::new();
+        match pair_create_many_rows(states, match_cols, &row_keys) {
+            Some(pairs) => {
+                let featured = states
+                    .iter()
+                    .filter(|state| state.has_feature_state())
+                    .count();
+                if !by_pk && pairs.len() < featured {
+                    anyhow::bail!(
+                        "{}: create_many could not match {} upserted rows to their translations, meta or attachments on ({})",
+                        M::TABLE,
+                        featured - pairs.len(),
+                        match_cols.join(", ")
+                    );
+                }
+                for (index, state) in pairs {
+                    if let Some(owner_id) = M::create_owner_id(&rows[index]) {
+                        owners.push((owner_id, state));
+                    }
+                }
+            }
+            None if !by_pk => anyhow::bail!(
+                "{}: create_many upserts with translations, meta or attachments must set every conflict column",
+                M::TABLE
+            ),
+            None if rows.len() == states.len() => {
+                for (row, state) in rows.iter().zip(states) {
+                    if let Some(owner_id) =
+                        M::create_owner_id(row).filter(|_| state.has_feature_state())
+                    {
+                        owners.push((owner_id, state));
+                    }
+                }
+            }
+            None => {}
+        }
+        persist_feature_state_many::<M>(db.clone(), &owners, replace_attachments).await?;
+    }
+
+    for target in M::touch_targets() {
+        let mut parent_ids: Vec<i64> = rows
+            .iter()
+            .flat_map(|row| M::extract_touch_fk_values(row))
+            .filter(|(fk, _)| *fk == target.fk_col)
+            .filter_map(|(_, parent_id)| parent_id)
+            .collect();
+        parent_ids.sort_unstable();
+        parent_ids.dedup();
+        if parent_ids.is_empty() {
+            continue;
+        }
+        let sql = format!(
+            "UPDATE {} SET updated_at = NOW() WHERE {} = ANY($1)",
+            target.parent_table, target.parent_pk
+        );
+        db.execute(sqlx::query(&sql).bind(parent_ids)).await?;
+    }
+    Ok(())
+}
+
+/// Bulk version of `persist_feature_state_for_owner`: one statement per side table.
+async fn persist_feature_state_many<'db, M>(
+    db: DbConn<'db>,
+    owners: &[(i64, &CreateState<'db>)],
+    replace_attachments: bool,
+) -> Result<()>
+where
+    M: FeaturePersistenceModel,
+{
+    if owners.is_empty() {
+        return Ok(());
+    }
+
+    if let Some(owner_type) = M::localized_owner_type() {
+        let mut ids = Vec::new();
+        let mut owner_ids = Vec::new();
+        let mut fields = Vec::new();
+        let mut locales = Vec::new();
+        let mut values = Vec::new();
+        for (owner_id, state) in owners {
+            for (field, translations) in &state.translations {
+                for (locale, value) in filter_supported_localized_values::<M>(translations) {
+                    ids.push(crate::common::sql::generate_snowflake_i64());
+                    owner_ids.push(*owner_id);
+                    fields.push(field.to_string());
+                    locales.push(locale);
+                    values.push(value);
+                }
+            }
+        }
+        if !ids.is_empty() {
+            let q = sqlx::query(
+                "INSERT INTO localized (id, owner_type, owner_id, field, locale, value) \
+                 SELECT t.id, $1, t.owner_id, t.field, t.locale, t.value \
+                 FROM UNNEST($2::BIGINT[], $3::BIGINT[], $4::TEXT[], $5::TEXT[], $6::TEXT[]) \
+                 AS t(id, owner_id, field, locale, value) \
+                 ON CONFLICT (owner_type, owner_id, field, locale) DO UPDATE SET value = EXCLUDED.value",
+            )
+            .bind(owner_type)
+            .bind(ids)
+            .bind(owner_ids)
+            .bind(fields)
+            .bind(locales)
+            .bind(values);
+            db.execute(q).await?;
+        }
+    }
+
+    if let Some(owner_type) = M::meta_owner_type() {
+        let mut ids = Vec::new();
+        let mut owner_ids = Vec::new();
+        let mut fields = Vec::new();
+        let mut values = Vec::new();
+        for (owner_id, state) in owners {
+            for (field, value) in &state.meta {
+                ids.push(crate::common::sql::generate_snowflake_i64());
+                owner_ids.push(*owner_id);
+                fields.push(field.clone());
+                values.push(sqlx::types::Json(value.clone()));
+            }
+        }
+        if !ids.is_empty() {
+            let q = sqlx::query(
+                "INSERT INTO meta (id, owner_type, owner_id, field, value) \
+                 SELECT t.id, $1, t.owner_id, t.field, t.value \
+                 FROM UNNEST($2::BIGINT[], $3::BIGINT[], $4::TEXT[], $5::JSONB[]) \
+                 AS t(id, owner_id, field, value) \
+                 ON CONFLICT (owner_type, owner_id, field) DO UPDATE SET value = EXCLUDED.value",
+            )
+            .bind(owner_type)
+            .bind(ids)
+            .bind(owner_ids)
+            .bind(fields)
+            .bind(values);
+            db.execute(q).await?;
+        }
+    }
+
+    if let Some(owner_type) = M::attachment_owner_type() {
+        if replace_attachments {
+            // Upserted owners may already hold a file in single-attachment fields
+            let mut owner_ids = Vec::new();
+            let mut fields = Vec::new();
+            for (owner_id, state) in owners {
+                for field in state.attachments_single.keys() {
+                    owner_ids.push(*owner_id);
+                    fields.push(field.to_string());
+                }
+            }
+            if !owner_ids.is_empty() {
+                let q = sqlx::query(
+                    "UPDATE attachments SET deleted_at = NOW() \
+                     WHERE owner_type = $1 AND deleted_at IS NULL \
+                     AND (owner_id, field) IN (SELECT * FROM UNNEST($2::BIGINT[], $3::TEXT[]))",
+                )
+                .bind(owner_type)
+                .bind(owner_ids)
+                .bind(fields);
+                db.execute(q).await?;
+            }
+        }
+
+        let mut ids = Vec::new();
+        let mut owner_ids = Vec::new();
+        let mut fields = Vec::new();
+        let mut paths = Vec::new();
+        let mut content_types = Vec::new();
+        let mut sizes = Vec::new();
+        let mut widths = Vec::new();
+        let mut heights = Vec::new();
+        for (owner_id, state) in owners {
+            let single = state
+                .attachments_single
+                .iter()
+                .map(|(field, input)| (*field, input));
+            let multi = state
+                .attachments_multi
+                .iter()
+                .flat_map(|(field, inputs)| inputs.iter().map(move |input| (*field, input)));
+            for (field, input) in single.chain(multi) {
+                ids.push(input.id.unwrap_or_else(Uuid::new_v4));
+                owner_ids.push(*owner_id);
+                fields.push(field.to_string());
+                paths.push(input.path.clone());
+                content_types.push(input.content_type.clone());
+                sizes.push(input.size);
+                widths.push(input.width);
+                heights.push(input.height);
+            }
+        }
+        if !ids.is_empty() {
+            let q = sqlx::query(
+                "INSERT INTO attachments (id, owner_type, owner_id, field, path, content_type, size, width, height, created_at, updated_at) \
+                 SELECT t.id, $1, t.owner_id, t.field, t.path, t.content_type, t.size, t.width, t.height, NOW(), NOW() \
+                 FROM UNNEST($2::UUID[], $3::BIGINT[], $4::TEXT[], $5::TEXT[], $6::TEXT[], $7::BIGINT[], $8::INT[], $9::INT[]) \
+                 AS t(id, owner_id, field, path, content_type, size, width, height)",
+            )
+            .bind(owner_type)
+            .bind(ids)
+            .bind(owner_ids)
+            .bind(fields)
+            .bind(paths)
+            .bind(content_types)
+            .bind(sizes)
+            .bind(widths)
+            .bind(heights);
+            db.execute(q).await?;
+        }
+    }
+
+    Ok(())
+}
+
 async fn patch_select_target_ids<'db, M>(db: DbConn<'db>, state: &PatchState<'db>) -> Result<Vec<M::Pk>>
 where
     M: PatchModel,
@@ -4640,6 +5436,255 @@ impl<'db, M: CreateModel> Create<'db, M> {
     }
 }
 
+// ---------------------------------------------------------------------------
+// CreateMany wrapper
+// ---------------------------------------------------------------------------
+
+/// Multi-row insert/upsert built from `Create` rows. Conflict handling is set
+/// here, once for the whole batch; a row's own `on_conflict_*` is ignored.
+pub struct CreateMany<'db, M: CreateModel> {
+    base_url: Option<String>,
+    rows: Vec<CreateState<'db>>,
+    options: CreateManyOptions,
+    _marker: PhantomData<M>,
+}
+
+impl<'db, M: CreateModel> Default for CreateMany<'db, M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'db, M: CreateModel> CreateMany<'db, M> {
+    pub fn new() -> Self {
+        Self::new_with_base_url(None)
+    }
+
+    pub fn new_with_base_url(base_url: Option<String>) -> Self {
+        Self {
+            base_url: resolve_attachment_base_url(base_url),
+            rows: Vec::new(),
+            options: CreateManyOptions::default(),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn row(mut self, row: Create<'db, M>) -> Self {
+        self.rows.push(row.into_inner());
+        self
+    }
+
+    pub fn rows<I>(mut self, rows: I) -> Self
+    where
+        I: IntoIterator<Item = Create<'db, M>>,
+    {
+        self.rows.extend(rows.into_iter().map(Create::into_inner));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn on_conflict_do_nothing<F>(mut self, fields: &[F]) -> Self
+    where
+        F: CreateConflictField<M>,
+    {
+        self.options.conflict =
+            F::on_conflict_do_nothing(CreateState::new(None, M::TABLE), fields).conflict;
+        self
+    }
+
+    /// Upsert: conflicting rows take every other column from the incoming row.
+    /// Duplicate keys within the batch keep the last row.
+    pub fn on_conflict_update<F>(mut self, fields: &[F]) -> Self
+    where
+        F: CreateConflictField<M>,
+    {
+        self.options.conflict =
+            F::on_conflict_update(CreateState::new(None, M::TABLE), fields).conflict;
+        self
+    }
+
+    /// Rows per statement (default `CREATE_MANY_DEFAULT_CHUNK_SIZE`). Chunks still
+    /// split further to stay under the bind-parameter limit.
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.options.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    pub fn observers(mut self, mode: BulkObserverMode) -> Self {
+        self.options.observers = mode;
+        self
+    }
+
+    /// Insert every row and return the hydrated records. Rows skipped by
+    /// `on_conflict_do_nothing` are not returned.
+    pub async fn save(self, db: impl Into<DbConn<'db>>) -> Result<Vec<M::Record>>
+    where
+        M: RuntimeModel,
+        M::Create: Serialize,
+        M::Row: Serialize,
+    {
+        let db = db.into();
+        let rows = create_many_runtime::<M>(db.clone(), self.rows, self.options).await?;
+        M::hydrate_records(db, rows, self.base_url).await
+    }
+
+    /// Like `save`, without hydrating records. Returns the affected row count.
+    pub async fn execute(self, db: impl Into<DbConn<'db>>) -> Result<u64>
+    where
+        M: RuntimeModel,
+        M::Create: Serialize,
+        M::Row: Serialize,
+    {
+        let rows = create_many_runtime::<M>(db.into(), self.rows, self.options).await?;
+        Ok(rows.len() as u64)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// InsertSelect wrapper
+// ---------------------------------------------------------------------------
+
+/// `INSERT INTO .. (cols) SELECT .. FROM <source query>`, run in one statement.
+/// Observers, side tables and touch targets are not involved.
+pub struct InsertSelect<'db, M: CreateModel> {
+    cols: Vec<&'static str>,
+    source: QueryState<'db>,
+    source_table: &'static str,
+    source_has_soft_delete: bool,
+    source_soft_delete_col: &'static str,
+    conflict: Option<CreateConflictSpec>,
+    _marker: PhantomData<M>,
+}
+
+impl<'db, M: CreateModel> InsertSelect<'db, M> {
+    /// `select` replaces the source query's select list and must yield one
+    /// expression per entry of `cols`, in the same order.
+    pub fn new<F, S>(
+        cols: &[F],
+        select: crate::common::sql::RawSelectExpr,
+        source: Query<'db, S>,
+    ) -> Self
+    where
+        F: ColExpr,
+        S: QueryModel,
+    {
+        let mut state = source.into_inner();
+        state.selects = vec![SelectExpr::Raw(select.into_inner())];
+        Self {
+            cols: cols.iter().map(|col| col.col_sql()).collect(),
+            source: state,
+            source_table: S::TABLE,
+            source_has_soft_delete: S::HAS_SOFT_DELETE,
+            source_soft_delete_col: S::SOFT_DELETE_COL,
+            conflict: None,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn on_conflict_do_nothing<F>(mut self, fields: &[F]) -> Self
+    where
+        F: CreateConflictField<M>,
+    {
+        self.conflict =
+            F::on_conflict_do_nothing(CreateState::new(None, M::TABLE), fields).conflict;
+        self
+    }
+
+    pub fn on_conflict_update<F>(mut self, fields: &[F]) -> Self
+    where
+        F: CreateConflictField<M>,
+    {
+        self.conflict = F::on_conflict_update(CreateState::new(None, M::TABLE), fields).conflict;
+        self
+    }
+
+    pub fn to_sql(&self) -> Result<(String, Vec<BindValue>)> {
+        check_deferred(&self.source.deferred_error)?;
+        if self.cols.is_empty() {
+            anyhow::bail!("{}: insert_select needs at least one column", M::TABLE);
+        }
+        let (select_sql, binds) = self.source.to_select_sql(
+            self.source_table,
+            self.source_has_soft_delete,
+            self.source_soft_delete_col,
+        );
+        let sql = format!(
+            "INSERT INTO {} ({}) {}{}",
+            M::TABLE,
+            self.cols.join(", "),
+            select_sql,
+            create_many_conflict_sql(
+                self.conflict.as_ref(),
+                &self.cols,
+                &[M::PK_COL, "created_at"]
+            )
+        );
+        Ok((sql, binds))
+    }
+
+    /// Returns the number of inserted (or upserted) rows.
+    pub async fn execute(self, db: impl Into<DbConn<'db>>) -> Result<u64>
+    where
+        M: RuntimeModel,
+    {
+        let (sql, binds) = self.to_sql()?;
+        let profiler_binds = if M::PROFILE_QUERIES && crate::common::sql::is_sql_profiler_enabled()
+        {
+            profiler_binds_string(&binds)
+        } else {
+            String::new()
+        };
+        let profiler_start = if M::PROFILE_QUERIES {
+            Some(std::time::Instant::now())
+        } else {
+            None
+        };
+        let mut q = sqlx::query(&sql);
+        for bind in binds {
+            q = crate::common::sql::bind_query(q, bind);
+        }
+        let result = db.into().execute(q).await?;
+        if let Some(start) = profiler_start {
+            crate::common::sql::record_profiled_query(
+                M::TABLE,
+                "INSERT",
+                &sql,
+                &profiler_binds,
+                start.elapsed(),
+            );
+        }
+        Ok(result.rows_affected())
+    }
+
+    pub async fn save(self, db: impl Into<DbConn<'db>>) -> Result<Vec<M::Record>>
+    where
+        M: RuntimeModel,
+    {
+        let (mut sql, binds) = self.to_sql()?;
+        sql.push_str(" RETURNING *");
+        let db = db.into();
+        let profiler_binds = if M::PROFILE_QUERIES && crate::common::sql::is_sql_profiler_enabled()
+        {
+            profiler_binds_string(&binds)
+        } else {
+            String::new()
+        };
+        let mut q = sqlx::query_as::<_, M::Row>(&sql);
+        for bind in binds {
+            q = crate::common::sql::bind(q, bind);
+        }
+        let rows = create_many_fetch::<M>(&db, &sql, q, &profiler_binds).await?;
+        M::hydrate_records(db, rows, resolve_attachment_base_url(None)).await
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Patch wrapper
 // ---------------------------------------------------------------------------
@@ -5892,6 +6937,23 @@ impl<'db> CreateState<'db> {
             .any(|assignment| assignment.col_sql == col_sql)
     }
 
+    /// The value set for `col_sql`; a later `set` wins over an earlier one.
+    pub fn col_value(&self, col_sql: &str) -> Option<&BindValue> {
+        self.assignments
+            .iter()
+            .rev()
+            .find(|assignment| assignment.col_sql == col_sql)
+            .map(|assignment| &assignment.value)
+    }
+
+    /// True when the row carries translations, meta or attachments.
+    pub fn has_feature_state(&self) -> bool {
+        !self.translations.is_empty()
+            || !self.meta.is_empty()
+            || !self.attachments_single.is_empty()
+            || !self.attachments_multi.is_empty()
+    }
+
     pub fn set_translation(
         mut self,
         field: &'static str,
@@ -6471,6 +7533,7 @@ mod tests {
         relation_aggregates: HashMap<String, f64>,
     }
     const STATUS_COL: Column<FakeModel, String> = Column::new("status");
+    const ID_COL: Column<FakeModel, i64> = Column::new("id");
     const STATE_COL: Column<FakeModel, String> = Column::new("processing_status");
     const CREATED_AT_COL: Column<FakeModel, time::OffsetDateTime> = Column::new("created_at");
     const ATTEMPTS_COL: Column<FakeModel, i64> = Column::new("send_attempt_count");
@@ -7051,6 +8114,146 @@ mod tests {
         assert_eq!(binds.len(), 2);
     }
 
+    #[test]
+    fn create_many_values_sql_uses_default_for_missing_columns() {
+        let rows = vec![
+            CreateState::new(None, "fake_models")
+                .set_col("id", 1_i64.into())
+                .set_col("status", "queued".into()),
+            CreateState::new(None, "fake_models").set_col("id", 2_i64.into()),
+        ];
+        let conflict = CreateConflictSpec {
+            action: CreateConflictAction::Update,
+            cols: vec!["id"],
+        };
+        let cols = create_many_columns(&rows);
+        let (sql, binds) =
+            build_insert_values_sql("fake_models", &cols, &rows, Some(&conflict), &["id"]);
+        assert_eq!(
+            sql,
+            "INSERT INTO fake_models (id, status) VALUES ($1, $2), ($3, DEFAULT) \
+             ON CONFLICT (id) DO UPDATE SET status = EXCLUDED.status RETURNING *"
+        );
+        assert_eq!(binds.len(), 3);
+        assert!(build_insert_unnest_sql("fake_models", &cols, &rows, None, &[]).is_none());
+    }
+
+    #[test]
+    fn create_many_unnest_sql_binds_one_array_per_column() {
+        let rows: Vec<_> = (1..=3_i64)
+            .map(|id| {
+                CreateState::new(None, "fake_models")
+                    .set_col("id", id.into())
+                    .set_col("status", "queued".into())
+            })
+            .collect();
+        let cols = create_many_columns(&rows);
+        let (sql, arrays) =
+            build_insert_unnest_sql("fake_models", &cols, &rows, None, &[]).expect("uniform rows");
+        assert_eq!(
+            sql,
+            "INSERT INTO fake_models (id, status) SELECT * FROM UNNEST($1, $2) AS t(id, status) RETURNING *"
+        );
+        assert!(
+            matches!(arrays.as_slice(), [BindArray::I64(ids), BindArray::String(_)] if ids.len() == 3)
+        );
+    }
+
+    #[test]
+    fn create_many_upsert_keeps_last_row_per_conflict_key() {
+        let rows = vec![
+            CreateState::new(None, "fake_models")
+                .set_col("id", 1_i64.into())
+                .set_col("status", "first".into()),
+            CreateState::new(None, "fake_models")
+                .set_col("id", 2_i64.into())
+                .set_col("status", "other".into()),
+            CreateState::new(None, "fake_models")
+                .set_col("id", 1_i64.into())
+                .set_col("status", "last".into()),
+        ];
+        let rows = dedupe_conflicting_rows(rows, &["id"]);
+        let statuses: Vec<_> = rows
+            .iter()
+            .map(|row| format!("{:?}", row.col_value("status")))
+            .collect();
+        assert_eq!(rows.len(), 2);
+        assert!(statuses[0].contains("other"));
+        assert!(statuses[1].contains("last"));
+    }
+
+    #[test]
+    fn create_many_upsert_pairs_rows_on_the_conflict_target() {
+        // Fresh snowflake ids; "intro" already exists as id 7, so RETURNING reports 7 for it.
+        let states = vec![
+            CreateState::new(None, "fake_models")
+                .set_col("id", 101_i64.into())
+                .set_col("slug", "intro".into())
+                .set_translation("title", "en".to_string(), "Intro".to_string()),
+            CreateState::new(None, "fake_models")
+                .set_col("id", 102_i64.into())
+                .set_col("slug", "setup".into())
+                .set_translation("title", "en".to_string(), "Setup".to_string()),
+            CreateState::new(None, "fake_models")
+                .set_col("id", 103_i64.into())
+                .set_col("slug", "plain".into()),
+        ];
+        let returned = [
+            serde_json::json!({ "id": 102, "slug": "setup" }),
+            serde_json::json!({ "id": 7, "slug": "intro" }),
+            serde_json::json!({ "id": 103, "slug": "plain" }),
+        ];
+        let keys_on = |cols: &[&str]| -> Vec<Option<String>> {
+            returned
+                .iter()
+                .map(|row| create_many_row_key(row, cols))
+                .collect()
+        };
+
+        let pairs = pair_create_many_rows(&states, &["slug"], &keys_on(&["slug"]))
+            .expect("every row sets slug");
+        let matched: Vec<_> = pairs
+            .iter()
+            .map(|(index, state)| (*index, create_many_pk_key(state, "slug")))
+            .collect();
+        assert_eq!(
+            matched,
+            vec![
+                (0, Some("setup".to_string())),
+                (1, Some("intro".to_string()))
+            ]
+        );
+
+        // The conflicting row kept its old primary key, so pairing on it misses that row.
+        let by_pk = pair_create_many_rows(&states, &["id"], &keys_on(&["id"])).expect("ids set");
+        assert_eq!(by_pk.len(), 1);
+
+        // Rows with side-table data must set every column of the target.
+        assert!(
+            pair_create_many_rows(&states, &["slug", "tenant_id"], &keys_on(&["slug"])).is_none()
+        );
+    }
+
+    #[test]
+    fn insert_select_sql_wraps_source_query() {
+        let source = Query::<SoftDeleteFakeModel>::new().where_col(
+            SD_STATUS_COL,
+            Op::Eq,
+            "queued".to_string(),
+        );
+        let select = crate::common::sql::RawSelectExpr::new("status, 'archived'").expect("select");
+        let (sql, binds) = InsertSelect::<FakeModel>::new(&[STATUS_COL, STATE_COL], select, source)
+            .on_conflict_do_nothing(&[ID_COL])
+            .to_sql()
+            .expect("insert_select sql");
+        assert!(sql.starts_with(
+            "INSERT INTO fake_models (status, processing_status) SELECT status, 'archived' FROM "
+        ));
+        assert!(sql.contains("deleted_at IS NULL"));
+        assert!(sql.ends_with("ON CONFLICT (id) DO NOTHING"));
+        assert_eq!(binds.len(), 1);
+    }
+
     #[test]
     fn patch_query_excludes_deleted_by_default() {
         let state = Query::<SoftDeleteFakeModel>::new()
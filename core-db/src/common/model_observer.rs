@@ -52,6 +52,25 @@ pub trait ModelObserver: Send + Sync {
         Ok(())
     }
 
+    /// Called once before a `create_many` insert when its observers are batched.
+    /// `new_data` is an array of the rows; `Modify` overrides apply to every row.
+    async fn on_creating_many(
+        &self,
+        _event: &ModelEvent,
+        _new_data: &serde_json::Value,
+    ) -> anyhow::Result<ObserverAction> {
+        Ok(ObserverAction::Continue)
+    }
+
+    /// Called once after a batched `create_many` insert with the array of inserted rows.
+    async fn on_created_many(
+        &self,
+        _event: &ModelEvent,
+        _new_data: &serde_json::Value,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     /// Called before an UPDATE is executed. `old_data` contains current row state.
     async fn on_updating(
         &self,
@@ -0,0 +1,12 @@
+#[rf_model(table = "mail_logs")]
+pub struct MailLog {
+    #[rf(pk(strategy = manual))]
+    pub id: uuid::Uuid,
+    pub recipient: String,
+    pub subject: String,
+    pub transport: String,
+    pub message_id: Option<String>,
+    pub status: String,
+    pub error: Option<String>,
+    pub created_at: time::OffsetDateTime,
+}
@@ -0,0 +1,11 @@
+#[rf_model(table = "notifications")]
+pub struct Notification {
+    #[rf(pk(strategy = manual))]
+    pub id: uuid::Uuid,
+    pub notifiable_type: String,
+    pub notifiable_id: String,
+    pub notification_type: String,
+    pub data: serde_json::Value,
+    pub read_at: Option<time::OffsetDateTime>,
+    pub created_at: time::OffsetDateTime,
+}
@@ -0,0 +1,14 @@
+#[rf_model(table = "notification_digest_items")]
+pub struct NotificationDigestItem {
+    #[rf(pk(strategy = manual))]
+    pub id: uuid::Uuid,
+    pub notifiable_type: String,
+    pub notifiable_id: String,
+    pub notification_type: String,
+    pub notifiable: serde_json::Value,
+    pub data: serde_json::Value,
+    pub status: String,
+    pub delivered_channels: serde_json::Value,
+    pub deliver_after: time::OffsetDateTime,
+    pub created_at: time::OffsetDateTime,
+}
@@ -0,0 +1,11 @@
+#[rf_model(table = "notification_preferences")]
+pub struct NotificationPreference {
+    #[rf(pk(strategy = manual))]
+    pub id: uuid::Uuid,
+    pub notifiable_type: String,
+    pub notifiable_id: String,
+    pub notification_type: String,
+    pub channel: String,
+    pub enabled: bool,
+    pub updated_at: time::OffsetDateTime,
+}
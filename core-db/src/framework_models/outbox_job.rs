@@ -4,5 +4,6 @@ pub struct OutboxJob {
     pub id: uuid::Uuid,
     pub queue: String,
     pub payload: serde_json::Value,
+    pub available_at: Option<time::OffsetDateTime>,
     pub created_at: time::OffsetDateTime,
 }
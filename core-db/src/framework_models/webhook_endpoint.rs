@@ -0,0 +1,14 @@
+#[rf_model(table = "webhook_endpoints")]
+pub struct WebhookEndpoint {
+    #[rf(pk(strategy = manual))]
+    pub id: uuid::Uuid,
+    pub url: String,
+    pub secret: String,
+    pub events: serde_json::Value,
+    pub description: Option<String>,
+    pub active: bool,
+    pub failure_count: i32,
+    pub disabled_at: Option<time::OffsetDateTime>,
+    pub created_at: time::OffsetDateTime,
+    pub updated_at: time::OffsetDateTime,
+}
@@ -9,5 +9,8 @@ pub struct WebhookLog {
     pub response_status: Option<i32>,
     pub response_body: Option<String>,
     pub duration_ms: Option<i32>,
+    /// `verified` / `rejected`; `NULL` when no verifier ran.
+    pub verification_status: Option<String>,
+    pub verification_error: Option<String>,
     pub created_at: time::OffsetDateTime,
 }
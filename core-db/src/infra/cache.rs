@@ -4,27 +4,45 @@ use std::sync::Arc;
 use anyhow::Result;
 use redis::{AsyncCommands, Client};
 use serde::{de::DeserializeOwned, Serialize};
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, OnceCell};
 
 use core_config::RedisSettings;
 
 #[derive(Clone)]
 pub struct Cache {
-    conn: Arc<Mutex<redis::aio::MultiplexedConnection>>,
+    client: Client,
+    conn: Arc<OnceCell<Mutex<redis::aio::MultiplexedConnection>>>,
     prefix: Option<String>,
 }
 
 pub async fn create_cache(settings: &RedisSettings) -> Result<Cache> {
-    let client = Client::open(settings.url.as_str())?;
-    let conn = client.get_multiplexed_async_connection().await?;
+    let cache = lazy_cache(settings)?;
+    cache.conn().await?;
+    Ok(cache)
+}
 
+/// Like `create_cache`, but connects on first use instead of at boot, so processes
+/// that may run without Redis (`[worker] driver = "postgres"`) start without it.
+/// A failed connect is retried by the next call.
+pub fn lazy_cache(settings: &RedisSettings) -> Result<Cache> {
     Ok(Cache {
-        conn: Arc::new(Mutex::new(conn)),
+        client: Client::open(settings.url.as_str())?,
+        conn: Arc::default(),
         prefix: settings.prefix.clone(),
     })
 }
 
 impl Cache {
+    async fn conn(&self) -> Result<&Mutex<redis::aio::MultiplexedConnection>> {
+        Ok(self
+            .conn
+            .get_or_try_init(|| async {
+                let conn = self.client.get_multiplexed_async_connection().await?;
+                Ok::<_, redis::RedisError>(Mutex::new(conn))
+            })
+            .await?)
+    }
+
     fn key(&self, k: &str) -> String {
         match &self.prefix {
             Some(p) => format!("{}:{}", p, k),
@@ -33,18 +51,18 @@ impl Cache {
     }
 
     pub async fn get(&self, key: &str) -> Result<Option<String>> {
-        let mut conn = self.conn.lock().await;
+        let mut conn = self.conn().await?.lock().await;
         Ok(conn.get(self.key(key)).await?)
     }
 
     pub async fn set(&self, key: &str, value: &str) -> Result<()> {
-        let mut conn = self.conn.lock().await;
+        let mut conn = self.conn().await?.lock().await;
         conn.set::<_, _, ()>(self.key(key), value).await?;
         Ok(())
     }
 
     pub async fn del(&self, key: &str) -> Result<()> {
-        let mut conn = self.conn.lock().await;
+        let mut conn = self.conn().await?.lock().await;
         conn.del::<_, ()>(self.key(key)).await?;
         Ok(())
     }
@@ -52,14 +70,28 @@ impl Cache {
     // ── TTL + Convenience ──────────────────────────────────────────
 
     pub async fn set_ex(&self, key: &str, value: &str, ttl_secs: u64) -> Result<()> {
-        let mut conn = self.conn.lock().await;
+        let mut conn = self.conn().await?.lock().await;
         conn.set_ex::<_, _, ()>(self.key(key), value, ttl_secs)
             .await?;
         Ok(())
     }
 
+    /// Store `value` only if `key` does not exist yet. Returns `true` when stored.
+    pub async fn add(&self, key: &str, value: &str, ttl_secs: u64) -> Result<bool> {
+        let mut conn = self.conn().await?.lock().await;
+        let set: Option<String> = redis::cmd("SET")
+            .arg(self.key(key))
+            .arg(value)
+            .arg("EX")
+            .arg(ttl_secs.max(1))
+            .arg("NX")
+            .query_async(&mut *conn)
+            .await?;
+        Ok(set.is_some())
+    }
+
     pub async fn ttl(&self, key: &str) -> Result<Option<i64>> {
-        let mut conn = self.conn.lock().await;
+        let mut conn = self.conn().await?.lock().await;
         let val: i64 = conn.ttl(self.key(key)).await?;
         Ok(if val < 0 { None } else { Some(val) })
     }
@@ -69,7 +101,7 @@ impl Cache {
     }
 
     pub async fn has(&self, key: &str) -> Result<bool> {
-        let mut conn = self.conn.lock().await;
+        let mut conn = self.conn().await?.lock().await;
         Ok(conn.exists(self.key(key)).await?)
     }
 
@@ -130,19 +162,19 @@ impl Cache {
     // ── Atomic Counters ────────────────────────────────────────────
 
     pub async fn increment(&self, key: &str, by: i64) -> Result<i64> {
-        let mut conn = self.conn.lock().await;
+        let mut conn = self.conn().await?.lock().await;
         Ok(conn.incr(self.key(key), by).await?)
     }
 
     pub async fn decrement(&self, key: &str, by: i64) -> Result<i64> {
-        let mut conn = self.conn.lock().await;
+        let mut conn = self.conn().await?.lock().await;
         Ok(conn.decr(self.key(key), by).await?)
     }
 
     // ── Bulk Operations ────────────────────────────────────────────
 
     pub async fn many(&self, keys: &[&str]) -> Result<Vec<Option<String>>> {
-        let mut conn = self.conn.lock().await;
+        let mut conn = self.conn().await?.lock().await;
         let prefixed: Vec<String> = keys.iter().map(|k| self.key(k)).collect();
         let results: Vec<Option<String>> = redis::cmd("MGET")
             .arg(&prefixed)
@@ -152,7 +184,7 @@ impl Cache {
     }
 
     pub async fn put_many(&self, pairs: &[(&str, &str)]) -> Result<()> {
-        let mut conn = self.conn.lock().await;
+        let mut conn = self.conn().await?.lock().await;
         let mut pipe = redis::pipe();
         for (k, v) in pairs {
             pipe.set(self.key(k), *v);
@@ -162,7 +194,7 @@ impl Cache {
     }
 
     pub async fn flush_prefix(&self, prefix: &str) -> Result<()> {
-        let mut conn = self.conn.lock().await;
+        let mut conn = self.conn().await?.lock().await;
         let pattern = format!("{}*", self.key(prefix));
         let keys: Vec<String> = redis::cmd("KEYS")
             .arg(&pattern)
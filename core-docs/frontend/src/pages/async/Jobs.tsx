@@ -35,13 +35,267 @@ pub fn register_jobs(worker: &mut Worker) {
 pub fn register_schedules(scheduler: &mut core_jobs::cron::Scheduler) {}`}</code>
                 </pre>
 
+                <h2>Delayed dispatch</h2>
+                <p>
+                    Delayed jobs are stored in the <code>{'{prefix}'}:scheduler</code> sorted set (the same set used for retry backoff) and moved onto their queue once due. Ordered jobs keep their <code>group_id</code> and re-enter their group list. Every payload carries a per-dispatch <code>id</code>, so identical jobs scheduled for the same time are all kept; use <code>unique_key</code> to dedupe.
+                </p>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto text-xs">
+                    <code className="language-rust">{`// Redis queue
+ExpireWithdrawal { id }
+    .dispatch_after(&queue, std::time::Duration::from_secs(30 * 60))
+    .await?;
+ExpireWithdrawal { id }.dispatch_at(&queue, expires_at).await?;
+
+// Transactional outbox: committed with the DB write, scheduled on flush
+let mut buffer = JobBuffer::new(&mut tx);
+buffer.push_after(ExpireWithdrawal { id }, Duration::from_secs(1800)).await?;`}</code>
+                </pre>
+
+                <h2>Unique jobs</h2>
+                <p>
+                    Return a key from <code>Job::unique_key()</code> to make pushes idempotent: while a job with the same <code>NAME</code> and key is pending or running, further pushes from <code>RedisQueue::push</code>, the outbox flusher, or the cron scheduler are dropped. The worker clears the key when the job succeeds or permanently fails; <code>unique_ttl()</code> (default 1 hour) is a safety expiry.
+                </p>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto text-xs">
+                    <code className="language-rust">{`fn unique_key(&self) -> Option<String> {
+    Some(format!("user:{}", self.user_id))
+}`}</code>
+                </pre>
+
+                <h2>Batches and chains</h2>
+                <p>
+                    <code>core_jobs::batch::JobBatch</code> fans out jobs and tracks pending/failed counts in Redis. <code>catch</code> fires on the first permanent failure, <code>then</code> when all jobs succeeded, and <code>finally</code> when all jobs finished. <code>JobChain</code> runs jobs one after another and stops at the first permanent failure. Both dispatch through any <code>QueueDriver</code>; a <code>PgQueue</code> needs a Redis batch store (<code>with_batch_store</code>, set up by <code>driver::from_settings</code>) for batches.
+                </p>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto text-xs">
+                    <code className="language-rust">{`let batch_id = JobBatch::new("deposit-approval")
+    .push(&CreditWallet { deposit_id })?
+    .push(&IssueReceipt { deposit_id })?
+    .then(&NotifyDepositApproved { deposit_id })?
+    .catch(&FlagDepositForReview { deposit_id })?
+    .dispatch(&*ctx.jobs)
+    .await?;
+
+JobChain::new()
+    .push(&ReserveFunds { withdrawal_id })?
+    .push(&SubmitPayout { withdrawal_id })?
+    .dispatch(&*ctx.jobs)
+    .await?;`}</code>
+                </pre>
+                <p>
+                    Batch progress is available from <code>JobBatch::find(&amp;queue, &amp;id)</code> or <code>GET /batches/{'{id}'}</code> on the <code>core_jobs::api::routes</code> router.
+                </p>
+
+                <h2>Reliable delivery</h2>
+                <p>
+                    With <code>[worker] reliable = true</code> (opt-in, off by default), a popped job is atomically moved into the worker's <code>{'{prefix}'}:processing:{'{worker_id}'}</code> list and removed only after it succeeded, was rescheduled, or permanently failed. This covers standard queues, group signals, and the ordered group drain. Each worker process refreshes a <code>{'{prefix}'}:worker:{'{worker_id}'}</code> heartbeat every 10 seconds; a reaper running in every worker re-queues the processing list of any worker whose heartbeat is older than 30 seconds.
+                </p>
+                <ul>
+                    <li>Delivery is at-least-once: a job interrupted by a crash runs again, so handlers should be idempotent.</li>
+                    <li>Reliable workers poll instead of blocking on <code>BLPOP</code>; idle workers check for work every 500 ms.</li>
+                    <li>Without it (<code>reliable = false</code>, the default) workers keep the plain <code>BLPOP</code> behavior. When turning it on for an existing deployment, roll it out to every worker: mixed workers share queues fine, but only reliable ones re-queue jobs after a crash.</li>
+                </ul>
+
+                <h2>Job middleware</h2>
+                <p>
+                    Implement <code>core_jobs::middleware::JobMiddleware</code> for code that wraps every job: audit context, DB transactions, or custom spans. It provides <code>before</code>, <code>after</code>, and <code>on_error</code> hooks, plus <code>around</code> for task-local scopes. The first registered middleware is the outermost. Workers built with <code>Worker::from_settings</code> already register <code>TracingMiddleware</code> (a <code>job</code> span per execution) and <code>LocaleMiddleware</code> (restores the locale captured at dispatch). <code>TimingMiddleware</code> logs slow jobs and keeps per-job totals.
+                </p>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto text-xs">
+                    <code className="language-rust">{`struct AuditMiddleware;
+
+#[async_trait::async_trait]
+impl JobMiddleware for AuditMiddleware {
+    async fn on_error(&self, job: &JobPayload, _ctx: &JobContext, error: &anyhow::Error) {
+        tracing::warn!(job = %job.job, attempt = job.attempts, "job failed: {error}");
+    }
+}
+
+pub fn register_jobs(worker: &mut Worker) {
+    worker
+        .middleware(TimingMiddleware::new(Duration::from_secs(5)))
+        .middleware(AuditMiddleware);
+}`}</code>
+                </pre>
+
+                <h2>Rate limits and concurrency caps</h2>
+                <p>
+                    Jobs that call quota-limited APIs can declare throttles. They are enforced in Redis across every worker process, keyed by <code>throttle_key()</code> (default: the job <code>NAME</code>). A throttled job goes back to the delayed set (ordered groups pause) and runs later. This does not use up an attempt and never lands in <code>failed_jobs</code>.
+                </p>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto text-xs">
+                    <code className="language-rust">{`// At most 100 starts per minute, shared by every job using the "payment-gateway" key
+fn rate_limit(&self) -> Option<(u32, std::time::Duration)> {
+    Some((100, std::time::Duration::from_secs(60)))
+}
+
+// At most 5 running at once
+fn max_concurrency(&self) -> Option<u32> {
+    Some(5)
+}
+
+fn throttle_key(&self) -> String {
+    "payment-gateway".to_string()
+}`}</code>
+                </pre>
+
+                <h2>Timeouts and cancellation</h2>
+                <p>
+                    Jobs opt in to a run-time limit by overriding <code>Job::timeout()</code> (default <code>None</code>, no limit); each attempt of <code>handle</code> is then cut off after that duration. A timed-out attempt is retried with the normal backoff and, once retries run out, lands in <code>failed_jobs</code> with the error <code>Job timed out after Ns</code>. Ordered-group locks are refreshed while a job runs, so long jobs keep their group to themselves.
+                </p>
+                <p>
+                    <code>JobContext::cancellation</code> is cancelled when the worker shuts down. Long-running jobs should check it between steps and return early.
+                </p>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto text-xs">
+                    <code className="language-rust">{`fn timeout(&self) -> Option<std::time::Duration> {
+    Some(std::time::Duration::from_secs(30))
+}
+
+async fn handle(&self, ctx: &JobContext) -> anyhow::Result<()> {
+    for chunk in self.chunks() {
+        if ctx.is_cancelled() {
+            anyhow::bail!("worker shutting down");
+        }
+        process(chunk).await?;
+    }
+    Ok(())
+}`}</code>
+                </pre>
+
+                <h2>Graceful shutdown</h2>
+                <p>
+                    <code>bootstrap::jobs::start_worker</code> listens for SIGTERM and SIGINT. On a signal, the worker stops pulling new jobs, cancels <code>JobContext::cancellation</code>, and waits up to <code>[worker] shutdown_grace</code> seconds (default 30) for running jobs. Jobs still running after that are aborted. Then the worker releases its group locks, re-queues its unfinished jobs in reliable mode, and logs a summary. Set your process manager's stop timeout (for example Supervisor <code>stopwaitsecs</code>) above the grace period.
+                </p>
+                <p>
+                    Embedded workers started with <code>start_with_context</code> run until the process exits. Use <code>start_with_shutdown(ctx, register_jobs, register_schedules, signal)</code> to drain them on your own signal future.
+                </p>
+
+                <h2>Postgres queue driver</h2>
+                <p>
+                    Set <code>[worker] driver = "postgres"</code> (or <code>WORKER_DRIVER=postgres</code>) to keep jobs in the framework <code>queue_jobs</code> table instead of Redis lists. Run <code>./console migrate pump</code> and <code>./console migrate run</code> to create the table. Workers claim jobs with <code>FOR UPDATE SKIP LOCKED</code>. Delayed jobs and retries wait for <code>available_at</code>. <code>pg_notify('core_jobs', queue)</code> wakes idle workers, and they also poll every 2 seconds.
+                </p>
+                <ul>
+                    <li>Dispatch through <code>ctx.jobs</code> (the driver picked by the setting) or <code>core_jobs::postgres::PgQueue</code>. <code>Job::dispatch</code> accepts any <code>QueueDriver</code>.</li>
+                    <li>Ordered groups run one job at a time in order, and a retry pauses its group. Unique keys are enforced by a unique index until the row is deleted.</li>
+                    <li>Permanent failures land in <code>failed_jobs</code>. <code>PgQueue::retry_failed(id)</code> moves one back.</li>
+                    <li>The outbox sweeper and the cron scheduler enqueue into <code>queue_jobs</code>.</li>
+                    <li>A running job's reservation is refreshed every 15 seconds. If a worker dies, its jobs are picked up again after 60 seconds.</li>
+                    <li>The cron scheduler keeps its leader lease (a session advisory lock), run locks, cursor and last runs in Postgres (<code>cron_state</code> table, also created by <code>migrate pump</code>).</li>
+                    <li>
+                        Redis is optional: boot and the worker only connect when a feature needs it, and the worker backs off reconnects (up to 30 seconds) instead of retrying per job. These features still need Redis:
+                        <ul>
+                            <li>batches (<code>JobBatch</code> counters); member jobs and callbacks go into <code>queue_jobs</code>, so dispatching a batch fails while Redis is down;</li>
+                            <li>rate limits and concurrency caps, which fail open (jobs run unthrottled) while Redis is down;</li>
+                            <li><code>ctx.redis</code> (the cache), which connects on first use;</li>
+                            <li>realtime publishing, notification realtime channel included.</li>
+                        </ul>
+                    </li>
+                </ul>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto text-xs">
+                    <code className="language-rust">{`// Works with either driver
+SendInvoice { invoice_id }.dispatch(&*ctx.jobs).await?;
+ExpireWithdrawal { id }
+    .dispatch_after(&*ctx.jobs, std::time::Duration::from_secs(1800))
+    .await?;`}</code>
+                </pre>
+
+                <h2>Lifecycle events and metrics</h2>
+                <p>
+                    Workers emit <code>core_jobs::events::JobEvent</code>s: <code>started</code>, <code>succeeded</code>, <code>retried</code>, and <code>failed</code>. The queues emit <code>queued</code> for every accepted push, whatever sent it: <code>ctx.jobs</code>, <code>ctx.queue</code>, the mailer, cron, batches, the outbox sweeper and retries. Each event carries the job name, queue, attempt, wait time, run time, and error. <code>ctx.job_events</code> counts them per job type and keeps run-time and wait-time histograms. The wait time runs from when the job became available to when it started, so delays and retry backoff are not counted.
+                </p>
+                <ul>
+                    <li>Listeners run inline on the worker task. Keep them cheap and send slow work to a channel.</li>
+                    <li>Counters are per process. <code>start_with_context</code> shares <code>ctx.job_events</code> with the embedded worker. A standalone worker process keeps its own counters, so the web process only sees its own <code>queued</code> pushes. In that setup, serve <code>metrics_routes</code> from the worker process.</li>
+                    <li><code>core_jobs::api::metrics_routes</code> serves the snapshot as JSON at <code>/</code> and in Prometheus text format at <code>/prometheus</code>. It has no auth, so mount it on an internal router or behind your own middleware.</li>
+                </ul>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto text-xs">
+                    <code className="language-rust">{`use core_jobs::events::{JobEvent, JobEventKind};
+
+ctx.job_events.listen(|event: &JobEvent| {
+    if event.kind == JobEventKind::Failed {
+        tracing::warn!(job = %event.job, error = ?event.error, "job failed permanently");
+    }
+});
+
+let snapshot = ctx.job_events.metrics_snapshot();
+router = router.nest("/internal/jobs/metrics", core_jobs::api::metrics_routes(ctx.job_events.clone()));`}</code>
+                </pre>
+
+                <h2>Encrypted and versioned payloads</h2>
+                <p>
+                    Set <code>const ENCRYPTED: bool = true</code> on jobs that carry PII. <code>JobPayload.data</code> is then sealed with <code>[app] key</code> (AES-GCM, <code>core_db::common::crypt::Crypt</code>) before it reaches Redis, <code>queue_jobs</code>, or <code>failed_jobs</code>. The job name, queue, and attempts stay readable. The key is read from <code>global_config</code>, which bootstrap registers at boot, so every process that dispatches or runs the job needs the same key.
+                </p>
+                <p>
+                    Each payload stores <code>Job::VERSION</code> (default 1). When you change a job struct incompatibly, bump <code>VERSION</code> and convert older data in <code>upgrade</code>. Jobs that are already queued, delayed, or in <code>failed_jobs</code> then still decode.
+                </p>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto text-xs">
+                    <code className="language-rust">{`impl Job for SendOtpSms {
+    const NAME: &'static str = "SendOtpSms";
+    const ENCRYPTED: bool = true;
+    const VERSION: u32 = 2;
+
+    // v1 had a single "phone" string; v2 splits the country code.
+    fn upgrade(mut data: serde_json::Value, from: u32) -> anyhow::Result<serde_json::Value> {
+        if from < 2 {
+            let phone = data["phone"].as_str().unwrap_or_default().to_string();
+            data["country_code"] = "+60".into();
+            data["number"] = phone.trim_start_matches("+60").into();
+        }
+        Ok(data)
+    }
+
+    async fn handle(&self, ctx: &JobContext) -> anyhow::Result<()> { /* ... */ Ok(()) }
+}`}</code>
+                </pre>
+
+                <h2>Testing code that dispatches jobs</h2>
+                <p>
+                    <code>core_jobs::testing</code> has two queue drivers for <code>#[tokio::test]</code>, and neither needs Redis. Pass one wherever a <code>QueueDriver</code> is expected, or put it in <code>ctx.jobs</code>.
+                </p>
+                <ul>
+                    <li><code>FakeQueue</code> records pushes. Check them with <code>assert_pushed::&lt;J&gt;(|job| ...)</code>, <code>assert_pushed_on_queue</code>, <code>assert_pushed_times</code>, <code>assert_not_pushed</code>, or <code>assert_nothing_pushed</code>, or read them back with <code>pushed::&lt;J&gt;()</code>. A recorded payload with the name of <code>J</code> that does not decode as <code>J</code> fails the test.</li>
+                    <li><code>SyncQueue</code> runs registered jobs inline against the <code>JobContext</code> you give it. Delays are ignored, chains run in order, and a job error is returned from <code>dispatch</code>. Middlewares, retries, and throttles are skipped.</li>
+                </ul>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto text-xs">
+                    <code className="language-rust">{`use core_jobs::{testing::FakeQueue, Job};
+
+#[tokio::test]
+async fn signup_sends_welcome_mail() {
+    let queue = FakeQueue::new();
+    register_user(&queue, "ada@example.com").await.unwrap();
+
+    queue.assert_pushed::<SendWelcomeEmail>(|job| job.email == "ada@example.com");
+    queue.assert_not_pushed::<NotifyAdmins>();
+}`}</code>
+                </pre>
+
                 <h2>Outbox and failed-job behavior</h2>
                 <ul>
                     <li>Use durable enqueue when the DB write and the future job must commit together.</li>
+                    <li><code>OutboxFlusher::flush(db, queue)</code> pushes committed outbox rows through any <code>QueueDriver</code>, including <code>FakeQueue</code> in tests.</li>
                     <li>Workers record terminal failures into <code>failed_jobs</code>.</li>
                     <li>The queue API can list failed jobs and retry them back into Redis.</li>
                 </ul>
 
+                <h2>Job management API</h2>
+                <p>
+                    <code>core_web::jobs::routes</code> mounts admin endpoints on an <code>ApiRouter</code>, with OpenAPI docs under the <code>Jobs</code> tag. The logic lives in <code>core_jobs::manager::JobManager</code>, which you can also call from console commands.
+                </p>
+                <ul>
+                    <li><code>GET /failed</code> searches failed jobs by job name, queue, error text, and failed-at range. <code>GET /failed/{'{id}'}</code> includes the payload.</li>
+                    <li><code>POST /failed/retry</code> and <code>POST /failed/delete</code> take up to 500 ids. <code>POST /failed/retry_job</code> retries every failure of one job.</li>
+                    <li>Retries go through the configured driver. A job whose unique key is already pending is skipped and stays in <code>failed_jobs</code>.</li>
+                    <li><code>POST /queues/{'{name}'}/pause</code> and <code>/resume</code> are stored in <code>paused_queues</code>. Workers reload the list every 5 seconds and stop pulling from paused queues. Running jobs finish normally.</li>
+                    <li>Read endpoints accept the read or manage permissions. Everything else needs manage.</li>
+                </ul>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto text-xs">
+                    <code className="language-rust">{`use core_jobs::manager::JobManager;
+use core_web::jobs::JobAdminPermissions;
+
+let jobs = core_web::jobs::routes(
+    AdminGuard,
+    JobManager::new(ctx.db.clone(), ctx.jobs.clone()),
+    JobAdminPermissions::new(["job.read"], ["job.manage"]),
+);
+router = router.nest("/api/v1/admin/jobs", jobs);`}</code>
+                </pre>
+
                 <h2>When to use a queued job vs runtime fan-out</h2>
                 <table>
                     <thead>
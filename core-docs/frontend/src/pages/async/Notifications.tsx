@@ -29,8 +29,9 @@ export function Notifications() {
                 <h2>Where the SSOT lives</h2>
                 <ul>
                     <li>
-                        <code>core-notify</code>: <code>Notifiable</code>, <code>Mailable</code>,
-                        and <code>MailChannel</code>
+                        <code>core-notify</code>: <code>Notifiable</code>, <code>Notification</code>,
+                        <code> NotificationManager</code>, and the mail, database, realtime, and SMS
+                        channels
                     </li>
                     <li>
                         <code>core-mailer</code>: <code>Mailer</code>, <code>MailPayload</code>,
@@ -132,6 +133,7 @@ impl Mailable for CountryStatusChangedMail {
             to: vec![notifiable.email()?],
             subject: format!("Country {} updated", self.iso2),
             body: format!("<p>Status: {}</p>", self.status),
+            ..Default::default()
         })
     }
 }
@@ -142,6 +144,401 @@ MailChannel::dispatch(state.mailer.as_ref(), &admin, &CountryStatusChangedMail {
 }).await?;`}</code>
                 </pre>
 
+                <h2>Rich mail messages</h2>
+                <p>
+                    <code>MailPayload</code> carries more than one recipient list: <code>cc</code>,
+                    <code>bcc</code>, <code>reply_to</code>, raw <code>headers</code>, an optional
+                    plain-text alternative next to the HTML <code>body</code>, and attachments. The
+                    builder methods keep call sites short.
+                </p>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto text-xs">
+                    <code className="language-rust">{`use core_mailer::{MailAttachment, MailPayload};
+
+let mail = MailPayload::new(user.email.clone(), "Your invoice".into(), html)
+    .cc("billing@example.com")
+    .reply_to("support@example.com")
+    .header("X-Campaign", "invoices")
+    .text(plain_text)
+    .attach(MailAttachment::from_storage("invoice.pdf", "application/pdf", invoice_key))
+    .attach(MailAttachment::from_bytes("logo.png", "image/png", logo_bytes).inline("logo"));
+
+state.mailer.queue_raw(mail).await?;`}</code>
+                </pre>
+                <ul>
+                    <li>
+                        <code>from_bytes</code> embeds the file in the payload (base64 when queued),
+                        so keep those small.
+                    </li>
+                    <li>
+                        <code>from_storage</code> stores only the <code>Storage</code> key; the
+                        bytes are read when the mail is sent, by the mailer or by the worker
+                        running <code>SendMailJob</code>.
+                    </li>
+                    <li>
+                        <code>inline("logo")</code> attaches an image the HTML references as
+                        <code> cid:logo</code>.
+                    </li>
+                </ul>
+
+                <h2>Templated mail</h2>
+                <p>
+                    <code>MailTemplate</code> builds mail from blocks instead of hand-written HTML:
+                    <code> heading</code>, <code>line</code>, <code>button</code>,
+                    <code> panel</code>, <code>table</code>, and raw <code>html</code>. Block text
+                    and the subject are <code>core_i18n</code> keys rendered with
+                    <code> t_for_locale</code>, and <code>:name</code> placeholders are filled from
+                    <code> with(..)</code> (HTML-escaped). The result is wrapped in the shared
+                    layout, and the plain-text part is generated from the same blocks.
+                </p>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto text-xs">
+                    <code className="language-rust">{`use core_mailer::{MailTemplate, TemplatedMailable};
+
+pub struct OrderShippedMail {
+    pub email: String,
+    pub locale: String,
+    pub order_no: String,
+    pub track_url: String,
+}
+
+impl TemplatedMailable for OrderShippedMail {
+    fn to(&self) -> Vec<String> {
+        vec![self.email.clone()]
+    }
+
+    fn template(&self) -> MailTemplate {
+        MailTemplate::new("Order :order shipped")
+            .locale(self.locale.clone())
+            .with("order", &self.order_no)
+            .heading("Your order is on its way")
+            .line("Order :order left our warehouse today.")
+            .button("Track parcel", self.track_url.clone())
+            .panel("Reply to this mail if anything looks wrong.")
+    }
+}
+
+state.mailer.queue(&mail).await?; // every TemplatedMailable is a Mailable`}</code>
+                </pre>
+                <ul>
+                    <li>
+                        Replace the built-in layout once at startup with
+                        <code> template::set_default_layout(MailLayout::from_files(html, text)?)</code>,
+                        or per mail with <code>.layout(..)</code>. Layout placeholders are
+                        <code> {'{{ content }}'}</code>, <code>{'{{ subject }}'}</code>,
+                        <code> {'{{ app_name }}'}</code>, and <code>{'{{ locale }}'}</code>.
+                    </li>
+                    <li>
+                        Notifications render in the recipient's locale via
+                        <code> Notifiable::preferred_locale</code>:
+                        <code> .locale(notifiable.preferred_locale().unwrap_or_default())</code>;
+                        unsupported or empty locales fall back to the current one.
+                    </li>
+                    <li>
+                        Templates render when the payload is built, so queued mail carries the
+                        final HTML and text.
+                    </li>
+                </ul>
+
+                <h3>Previewing in the browser</h3>
+                <p>
+                    <code>core_mailer::preview::routes</code> renders registered sample mails:
+                    <code> GET /</code> lists them, <code>GET /{'{name}'}</code> shows the HTML part,
+                    <code> GET /{'{name}'}/text</code> the text part, and <code>?locale=ms</code>
+                    switches locale. The routes have no auth, so mount them only in development.
+                </p>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto text-xs">
+                    <code className="language-rust">{`use core_mailer::{preview::{self, MailPreviews}, Mailable};
+
+if !settings.app.is_production() {
+    let previews = MailPreviews::new().add("order-shipped", || {
+        OrderShippedMail::sample().to_payload()
+    });
+    router = router.nest("/dev/mail", preview::routes(previews));
+}`}</code>
+                </pre>
+
+                <h2>Mail transports</h2>
+                <p>
+                    <code>[mail] driver</code> picks the <code>MailTransport</code> the
+                    <code> Mailer</code> (and the worker running <code>SendMailJob</code>) delivers
+                    through. Unknown drivers fail at boot.
+                </p>
+                <table>
+                    <thead>
+                        <tr>
+                            <th>Driver</th>
+                            <th>Delivery</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        <tr>
+                            <td><code>log</code></td>
+                            <td>Logs recipients, subject, and body. The default.</td>
+                        </tr>
+                        <tr>
+                            <td><code>smtp</code></td>
+                            <td>SMTP relay using <code>host</code>, <code>port</code>, and credentials.</td>
+                        </tr>
+                        <tr>
+                            <td><code>file</code></td>
+                            <td>One <code>.eml</code> file per message in <code>file_dir</code>.</td>
+                        </tr>
+                        <tr>
+                            <td><code>memory</code></td>
+                            <td>Process-wide in-memory mailbox (last 500 messages).</td>
+                        </tr>
+                        <tr>
+                            <td><code>sendmail</code></td>
+                            <td>Pipes the message to <code>sendmail_path</code>.</td>
+                        </tr>
+                        <tr>
+                            <td><code>postmark</code>, <code>mailgun</code></td>
+                            <td>
+                                Provider HTTP API with <code>api_key</code> (and
+                                <code> api_domain</code> for Mailgun); <code>api_url</code> overrides the
+                                base URL.
+                            </td>
+                        </tr>
+                        <tr>
+                            <td><code>http</code></td>
+                            <td>
+                                POSTs a JSON body (<code>from</code>, <code>to</code>,
+                                <code> subject</code>, <code>html</code>, <code>text</code>,
+                                <code> attachments</code> as base64, ...) to <code>api_url</code> with
+                                a bearer <code>api_key</code>. Use it for relays or provider adapters
+                                such as an SES forwarding function.
+                            </td>
+                        </tr>
+                    </tbody>
+                </table>
+                <p>
+                    In tests, swap the transport for a <code>MemoryTransport</code> and assert on
+                    what was sent. HTTP providers can point at a local stub server through
+                    <code> endpoint(..)</code>.
+                </p>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto text-xs">
+                    <code className="language-rust">{`use core_mailer::{transport::HttpApiTransport, Mailer, MemoryTransport};
+
+let mailbox = MemoryTransport::new();
+let mailer = Mailer::new(&settings.mail, None)?.with_transport(Arc::new(mailbox.clone()));
+
+mailer.send(&OrderShippedMail::sample()).await?;
+mailbox.assert_sent_to("buyer@example.com");
+mailbox.assert_sent(|mail| mail.subject.contains("shipped"));
+
+let stubbed = HttpApiTransport::postmark("test-token").endpoint(&stub_server.url());`}</code>
+                </pre>
+                <p>
+                    With the <code>memory</code> driver in local dev, mount
+                    <code> preview::mailbox_routes(MemoryTransport::shared())</code> next to the
+                    preview routes to browse sent mail: <code>GET /</code> lists messages,
+                    <code> GET /{'{index}'}</code> shows the HTML, <code>GET /{'{index}'}/raw</code>
+                    the MIME source, and <code>DELETE /</code> empties the mailbox.
+                </p>
+
+                <h3>Failover, rate limits, and the mail log</h3>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto text-xs">
+                    <code className="language-toml">{`[mail]
+driver = "failover"
+failover = "smtp,postmark"          # tried in order until one accepts the mail
+rate_limits_per_process = "smtp=10/1"  # at most 10 sends per second per process
+log_enabled = true
+log_retention_days = 30`}</code>
+                </pre>
+                <ul>
+                    <li>
+                        <code>FailoverTransport</code> logs each failing transport and moves on;
+                        the send fails only when every transport failed, and the
+                        <code> SendMailJob</code> retry then starts from the first one again.
+                    </li>
+                    <li>
+                        HTTP API transports give up after 10 seconds to connect and 30 seconds per
+                        request, so a hung provider counts as a failure and the next transport is tried.
+                    </li>
+                    <li>
+                        Rate limits count per process with a fixed window and are not shared
+                        through Redis: with N workers the provider sees up to N times the limit, so
+                        divide the provider quota by the worker count. Sends over the limit wait
+                        for the next window instead of failing.
+                    </li>
+                    <li>
+                        Every attempt writes a <code>mail_logs</code> row: recipients, subject, the
+                        transport that handled it, the <code>Message-ID</code> (or the provider's
+                        id), <code>status</code> (<code>sent</code>/<code>failed</code>), and the
+                        error. Failing to write the log never fails the send.
+                    </li>
+                    <li>
+                        <code>core:cleanup_mail_logs</code> runs daily at midnight and deletes rows
+                        older than <code>log_retention_days</code>, like the HTTP log cleanup.
+                    </li>
+                </ul>
+
+                <h2>Multi-channel notifications</h2>
+                <p>
+                    A <code>Notification</code> names its channels in <code>via</code> and renders
+                    each one with <code>to_mail</code>, <code>to_database</code>,
+                    <code> to_realtime</code>, or <code>to_sms</code> (<code>to_channel</code> for
+                    app channels). A renderer returning <code>None</code> skips that channel.
+                    <code> BootContext::notifications</code> is a <code>NotificationManager</code>
+                    with all four built-in channels registered.
+                </p>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto text-xs">
+                    <code className="language-rust">{`use core_notify::{Notifiable, Notification, RealtimeMessage, SmsMessage};
+use serde_json::json;
+
+pub struct WithdrawalApproved {
+    pub id: i64,
+    pub amount: String,
+}
+
+impl Notification for WithdrawalApproved {
+    fn notification_type(&self) -> &'static str {
+        "withdrawal_approved"
+    }
+
+    fn via(&self, _notifiable: &dyn Notifiable) -> Vec<&'static str> {
+        vec!["mail", "database", "realtime", "sms"]
+    }
+
+    fn to_mail(&self, _notifiable: &dyn Notifiable) -> Option<MailPayload> {
+        // "to" defaults to notifiable.email()
+        Some(MailTemplate::new("mail.withdrawal_approved.subject")
+            .with("amount", &self.amount)
+            .line("mail.withdrawal_approved.body")
+            .to_payload(Vec::new()))
+    }
+
+    fn to_database(&self, _notifiable: &dyn Notifiable) -> Option<serde_json::Value> {
+        Some(json!({ "withdrawal_id": self.id, "amount": self.amount }))
+    }
+
+    fn to_realtime(&self, notifiable: &dyn Notifiable) -> Option<RealtimeMessage> {
+        Some(RealtimeMessage::new("user", "withdrawal_approved", json!({ "id": self.id }))
+            .room(format!("user:{}", notifiable.id())))
+    }
+
+    fn to_sms(&self, _notifiable: &dyn Notifiable) -> Option<SmsMessage> {
+        // number defaults to notifiable.phone()
+        Some(SmsMessage::new(format!("Withdrawal of {} approved", self.amount)))
+    }
+}
+
+ctx.notifications.send(&user, &WithdrawalApproved { id, amount }).await?;   // in-request
+ctx.notifications.queue(&user, &WithdrawalApproved { id, amount }).await?;  // one job per channel`}</code>
+                </pre>
+                <ul>
+                    <li>
+                        Rendering runs in the notifiable's <code>preferred_locale()</code>, so
+                        <code> t()</code> and <code>MailTemplate</code> use the recipient's language
+                        even when delivery is queued.
+                    </li>
+                    <li>
+                        <code>send</code> tries every channel and reports all failures together.
+                        <code> queue</code> dispatches one <code>SendNotificationJob</code> per
+                        channel, so a failing SMS provider retries without re-sending the mail.
+                    </li>
+                    <li>
+                        The database channel writes the <code>notifications</code> table.
+                        <code> DatabaseNotifications</code> reads it back: <code>list</code>,
+                        <code> unread</code>, <code>unread_count</code>, <code>mark_as_read</code>,
+                        <code> mark_as_unread</code>, <code>mark_all_as_read</code>, and
+                        <code> delete</code>, all scoped to one notifiable.
+                    </li>
+                    <li>
+                        The SMS channel logs messages through <code>LogSmsDriver</code> by default.
+                        Implement <code>SmsDriver</code> for a provider and register it with
+                        <code> ctx.notifications.register(SmsChannel::new(MyDriver))</code>; custom
+                        channels implement <code>Channel</code> the same way.
+                    </li>
+                </ul>
+
+                <h3>Preferences and opt-out</h3>
+                <p>
+                    Before rendering, the manager asks its <code>NotificationPreferences</code>
+                    whether the notifiable accepts the notification type on each channel. The
+                    default <code>DatabasePreferences</code> reads
+                    <code> notification_preferences</code>: everything is on until a row turns it
+                    off, a row with type <code>"*"</code> covers every type on its channel, and a
+                    row for the type itself wins over it. Notifications whose
+                    <code> mandatory()</code> returns <code>true</code> skip the check.
+                </p>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto text-xs">
+                    <code className="language-rust">{`// at boot: what the settings page lists
+ctx.notifications.describe("withdrawal_approved", &["mail", "sms", "database"], false);
+ctx.notifications.describe("password_changed", &["mail"], true);
+
+// GET /notification-preferences
+let prefs = DatabasePreferences::new(state.db.clone());
+let output = prefs.settings(&user, &state.notifications.types()).await?;
+
+// PUT /notification-preferences (body: NotificationPreferenceUpdateInput)
+prefs.update(&user, &state.notifications.types(), &input).await?;`}</code>
+                </pre>
+                <p>
+                    <code>update</code> rejects pairs that were not described and attempts to turn
+                    off a mandatory type with a 422, and writes nothing in that case. The contracts
+                    live in <code>core_notify::contracts</code>; <code>make gen-types</code> writes
+                    their TypeScript to <code>shared/types/notifications.ts</code>.
+                </p>
+
+                <h3>Digests</h3>
+                <p>
+                    Register a <code>Digest</code> for noisy types. Instead of delivering each one,
+                    the manager stores the notification's <code>to_digest</code> value (its
+                    <code> to_database</code> by default) in <code>notification_digest_items</code>,
+                    and <code>core:send_notification_digests</code>, scheduled hourly, sends one
+                    summary per notifiable once the window is over.
+                </p>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto text-xs">
+                    <code className="language-rust">{`use core_notify::{Digest, DigestWindow, Notifiable, Notification};
+
+pub struct CreditTransactionDigest;
+
+impl Digest for CreditTransactionDigest {
+    fn notification_type(&self) -> &'static str {
+        "credit_transaction"
+    }
+
+    fn window(&self) -> DigestWindow {
+        DigestWindow::Hourly
+    }
+
+    // the first 3 per hour go out one by one, the rest wait for the summary
+    fn max_per_window(&self) -> u32 {
+        3
+    }
+
+    fn summarize(&self, _notifiable: &dyn Notifiable, items: &[serde_json::Value]) -> Box<dyn Notification> {
+        Box::new(CreditTransactionSummary { count: items.len(), items: items.to_vec() })
+    }
+}
+
+ctx.notifications.register_digest(CreditTransactionDigest);`}</code>
+                </pre>
+                <ul>
+                    <li>
+                        Windows are aligned to the UTC hour or day. A daily digest goes out in the
+                        first hourly run after UTC midnight.
+                    </li>
+                    <li>
+                        The summary is a normal <code>Notification</code>. It is queued when the
+                        manager has a queue, and opt-outs still apply. It is sent to the routes
+                        captured when the last item was collected, so keep its <code>via</code>
+                        within the bundled type's channels.
+                    </li>
+                    <li>
+                        Mandatory notifications are never bundled. If a summary fails on a channel,
+                        its items stay for the next run. That run sends the summary again only on
+                        the channels that failed.
+                    </li>
+                    <li>
+                        One bad group does not hold up the others. If its stored notifiable cannot be
+                        read, its items get <code>status = 'failed'</code> and are kept for
+                        inspection. If the summary cannot be rendered (e.g. its <code>via</code> names
+                        an unregistered channel), the error is logged and the group is tried again
+                        next run.
+                    </li>
+                </ul>
+
                 <h2>Extension points</h2>
                 <ul>
                     <li>
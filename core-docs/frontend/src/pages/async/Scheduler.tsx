@@ -33,6 +33,58 @@ pub fn register_schedules(scheduler: &mut core_jobs::cron::Scheduler) {
                     Scheduled items enqueue into the queue declared by each job&apos;s{' '}
                     <code>QUEUE</code> constant and are executed by worker processes.
                 </p>
+
+                <h3>Fluent helpers and job instances</h3>
+                <p>
+                    <code>scheduler.job(instance)</code> schedules a concrete job with its payload, so the job does not need to implement <code>Default</code>. Chain a frequency helper after it:{' '}
+                    <code>every_seconds</code>, <code>every_minute</code>, <code>every_minutes</code>, <code>hourly</code>, <code>hourly_at</code>, <code>daily</code>, <code>daily_at</code>, <code>weekly_on</code>, <code>monthly_on</code>, or <code>cron_expression</code>.
+                </p>
+                <p>
+                    Tasks run in <code>[app] timezone</code> by default. Use <code>.timezone("+00:00")</code> to override it for one task. Use <code>.name(..)</code> when the same job is scheduled more than once on the same expression, since the name identifies the run lock.
+                </p>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto">
+                    <code className="language-rust">{`scheduler
+    .job(SendReport { kind: ReportKind::Daily })
+    .daily_at("03:00")
+    .name("daily-report");
+
+scheduler
+    .job(SendReport { kind: ReportKind::Weekly })
+    .weekly_on(time::Weekday::Monday, "08:30")
+    .timezone("+00:00");
+
+scheduler.job(PollGateway::default()).every_seconds(15);`}</code>
+                </pre>
+                <p>
+                    <code>./console schedule list</code> prints each task with its expression, next run time, and options. The starter console passes <code>register_schedules</code> through <code>start_console_with_schedules</code>.
+                </p>
+
+                <h3>Timing, leadership and catch-up</h3>
+                <ul>
+                    <li>
+                        The scheduler sleeps until the next due task, so six-field expressions with seconds (e.g.{' '}
+                        <code>*/15 * * * * *</code>) fire on time.
+                    </li>
+                    <li>
+                        Every worker process may run a scheduler. Only the holder of the{' '}
+                        <code>{'{prefix}'}:cron:leader</code> lease fires tasks. The lease lasts 15 seconds and is renewed every 5 seconds, and a standby takes over when the leader stops. With <code>[worker] driver = "postgres"</code>, bootstrap calls <code>use_postgres_locks</code>: the lease is a Postgres session advisory lock held by the leader&apos;s connection, and locks, cursor and last runs live in the <code>cron_state</code> table, so the scheduler does not need Redis.
+                    </li>
+                    <li>
+                        A new leader resumes from the previous leader&apos;s cursor if it is at most two minutes old, so short restarts do not drop runs. Per-run locks prevent double fires.
+                    </li>
+                    <li>
+                        If a run cannot be enqueued (e.g. the queue is briefly down), the error is logged, its locks are released and it is retried every 5 seconds until the task&apos;s next run is due. Other tasks keep firing.
+                    </li>
+                    <li>
+                        For longer outages, mark a task with <code>.catch_up()</code>. The last fire time is recorded in{' '}
+                        <code>{'{prefix}'}:cron:last_run</code>, and missed runs are fired once, coalesced, when a leader starts.
+                    </li>
+                </ul>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto">
+                    <code className="language-rust">{`scheduler
+    .cron::<SettleDailyInterest>("0 0 1 * * *")
+    .catch_up();`}</code>
+                </pre>
             </div>
         </div>
     )
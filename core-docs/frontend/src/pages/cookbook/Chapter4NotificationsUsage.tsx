@@ -76,6 +76,7 @@ impl Mailable for CountryStatusChangedMail {
             to: vec![notifiable.email()?],
             subject: format!("Country {} updated", self.iso2),
             body: format!("<p>Status: {}</p>", self.status),
+            ..Default::default()
         })
     }
 }`}</code>
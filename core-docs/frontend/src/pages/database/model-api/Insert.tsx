@@ -66,6 +66,71 @@ export function ModelApiInsert() {
     .await?;`}</code>
                 </pre>
 
+                <h2>Bulk insert and upsert</h2>
+                <p>
+                    <code>XxxModel::create_many()</code> (or <code>create_many_with_base_url(base_url)</code>, like <code>create_with_base_url</code>) collects ordinary <code>create()</code> builders and writes them in as few statements as possible. Small chunks use one multi-row <code>VALUES</code> list (missing columns fall back to <code>DEFAULT</code>); chunks of 500+ rows where every row sets the same columns bind one array per column through <code>UNNEST</code>. Statements are split to stay under the Postgres bind-parameter limit, and the whole call runs in one transaction (or the caller's).
+                </p>
+                <MethodTable
+                    rows={[
+                        {
+                            method: 'row(create) / rows(iter)',
+                            returns: 'Self',
+                            notes: 'Add rows built with XxxModel::create(). Per-row on_conflict_* is ignored.',
+                        },
+                        {
+                            method: 'on_conflict_do_nothing(&[col]) / on_conflict_update(&[col])',
+                            returns: 'Self',
+                            notes: 'Batch conflict handling. Upserts set other columns from EXCLUDED but keep the existing primary key and created_at; duplicate keys within the batch keep the last row.',
+                        },
+                        {
+                            method: 'chunk_size(n)',
+                            returns: 'Self',
+                            notes: 'Rows per statement, default 1000.',
+                        },
+                        {
+                            method: 'observers(BulkObserverMode)',
+                            returns: 'Self',
+                            notes: 'PerRow (default) runs on_creating/on_created per row, Batched runs on_creating_many/on_created_many once, Skip runs none.',
+                        },
+                        {
+                            method: 'save(db) / execute(db)',
+                            returns: 'Result<Vec<XxxRecord>> / Result<u64>',
+                            notes: 'save hydrates the returned rows; execute only counts them. Rows skipped by DO NOTHING are not returned.',
+                        },
+                    ]}
+                />
+                <p>
+                    Translations, meta and attachments set on the rows are written with one statement per side table, and touched parents are updated once per batch. Side-table data is matched to the returned rows by primary key when the rows set one (snowflake ids always do). <code>on_conflict_update</code> on another unique column matches on that column instead, since updated rows keep their existing key; the call fails rather than dropping side-table data it cannot match. With <code>on_conflict_do_nothing</code>, rows carrying side-table data must set their primary key so the surviving rows can be matched.
+                </p>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto">
+                    <code className="language-rust">{`let rows = seeds
+    .into_iter()
+    .map(|seed| CountryModel::create().set(CountryCol::ISO2, seed.iso2)?.set(CountryCol::NAME, seed.name))
+    .collect::<Result<Vec<_>>>()?;
+
+CountryModel::create_many()
+    .rows(rows)
+    .on_conflict_update(&[CountryCol::ISO2])
+    .observers(BulkObserverMode::Batched)
+    .execute(db)
+    .await?;`}</code>
+                </pre>
+
+                <h2>INSERT ... SELECT</h2>
+                <p>
+                    <code>XxxModel::insert_select(cols, select, source)</code> copies rows straight from another model query in one statement. <code>select</code> replaces the source query&apos;s select list and must yield one expression per target column; filters, joins, soft-delete scoping, ordering and limits of the source query are kept. Observers and side tables are not involved.
+                </p>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto">
+                    <code className="language-rust">{`let copied = OrderArchiveModel::insert_select(
+    &[OrderArchiveDbCol::OrderId, OrderArchiveDbCol::Total],
+    RawSelectExpr::new("id, total")?,
+    OrderModel::query().where_col(OrderCol::STATUS, Op::Eq, OrderStatus::Closed),
+)
+.on_conflict_do_nothing(&[OrderArchiveDbCol::OrderId])
+.execute(db)
+.await?;`}</code>
+                </pre>
+
                 <h2>Customization boundary</h2>
                 <p>
                     Keep create-time invariants in workflows and contracts, then call <code>XxxInsert</code> for the actual write. If app code needs custom computed output after save, add it on <a href="#/model-api-view"><code>XxxView</code> extensions</a> rather than forking the generated insert path.
@@ -117,12 +117,21 @@ MW_RATE_LIMIT_BURST=60          # Burst allowance
 # Mailer
 # ----------------------------
 MAIL_ENABLE=false
-MAIL_DRIVER=log                 # smtp | log
+MAIL_DRIVER=log                 # smtp | log | file | memory | sendmail | postmark | mailgun | http | failover
+MAIL_FAILOVER=                  # failover driver: ordered list, e.g. smtp,postmark
+MAIL_RATE_LIMITS=               # Per transport and process, e.g. smtp=10/1,postmark=50/1
+MAIL_LOG_ENABLED=true           # Record sends in mail_logs
+MAIL_LOG_RETENTION_DAYS=30      # 0 = keep forever
 MAIL_HOST=smtp.mailtrap.io
 MAIL_PORT=2525
 MAIL_USERNAME=
 MAIL_PASSWORD=
 MAIL_FROM_ADDRESS=hello@example.com
+MAIL_FILE_DIR=storage/mail      # file driver: one .eml per message
+MAIL_SENDMAIL_PATH=sendmail     # sendmail driver
+MAIL_API_KEY=                   # postmark | mailgun | http
+MAIL_API_URL=                   # Empty = provider default; required for http
+MAIL_API_DOMAIN=                # Mailgun sending domain
 
 # ----------------------------
 # HTTP Traffic Logging
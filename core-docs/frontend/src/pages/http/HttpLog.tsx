@@ -55,6 +55,62 @@ let app = Router::new()
     .layer(WebhookLogLayer::new(settings.http_log.clone()));`}</code>
                 </pre>
 
+                <h2>Inbound webhook verification</h2>
+                <p>
+                    <code>WebhookVerifyLayer</code> checks the provider signature before the handler runs. Add it
+                    inside <code>WebhookLogLayer</code> so the log row records <code>verification_status</code>
+                    (<code>verified</code> / <code>rejected</code>) and <code>verification_error</code>.
+                </p>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto text-xs">
+                    <code className="language-rust">{`use core_http_log::webhook_verify::{SignatureEncoding, WebhookVerifier};
+use core_http_log::{VerifiedWebhook, WebhookLogLayer, WebhookVerifyLayer};
+
+// Stripe-Signature: t=...,v1=...
+let stripe = WebhookVerifier::stripe("stripe", settings.stripe_webhook_secret.clone())
+    .with_replay_protection(cache.clone());
+
+// Generic HMAC: X-Shopify-Hmac-Sha256 = base64(hmac(body))
+let shopify = WebhookVerifier::hmac("shopify", secret, "X-Shopify-Hmac-Sha256")
+    .with_encoding(SignatureEncoding::Base64);
+
+// Another service sending through core_http_log::outbound_webhooks
+let partner = WebhookVerifier::outbound("partner", secret).with_replay_protection(cache.clone());
+
+let app = Router::new()
+    .route("/wh/stripe", post(handle_stripe).route_layer(WebhookVerifyLayer::new(stripe)))
+    .route("/wh/shopify", post(handle_shopify).route_layer(WebhookVerifyLayer::new(shopify)))
+    .layer(WebhookLogLayer::new(settings.http_log.clone()));
+
+async fn handle_stripe(webhook: VerifiedWebhook, body: String) -> StatusCode { .. }`}</code>
+                </pre>
+                <ul>
+                    <li>
+                        The signature is compared in constant time. Timestamps more than <code>with_tolerance</code>{' '}
+                        seconds away from now (default 300) are rejected.
+                    </li>
+                    <li>
+                        <code>with_replay_protection</code> claims a nonce in Redis with SET NX. The nonce is the
+                        HMAC over the signed payload, so unsigned headers cannot change it. Without a timestamp,
+                        nonces are kept for 24 hours.
+                    </li>
+                    <li>
+                        <code>with_id_header</code> only fills <code>VerifiedWebhook::delivery_id</code>.
+                        <code> outbound</code> also sets <code>with_body_id("id")</code>: the id is read from the signed
+                        body, and a request whose <code>X-Webhook-Id</code> differs is rejected.
+                    </li>
+                    <li>
+                        If the handler does not answer 2xx, the nonce is released so the provider can retry.
+                    </li>
+                    <li>
+                        A bad signature, stale timestamp or replay returns 401. If Redis is down, the layer returns
+                        500 so the provider retries later.
+                    </li>
+                    <li>
+                        Adding <code>VerifiedWebhook</code> as a handler argument makes the handler refuse
+                        requests that did not pass the layer.
+                    </li>
+                </ul>
+
                 <h2>Outbound HTTP logging</h2>
                 <p>
                     Use the framework <code>HttpClient</code> wrapper instead of raw <code>reqwest::Client</code> when
@@ -72,6 +128,47 @@ let response = client
     .await?;`}</code>
                 </pre>
 
+                <h2>Outbound webhooks</h2>
+                <p>
+                    Partner systems subscribe through <code>webhook_endpoints</code> rows (url, secret, event
+                    filter, active). <code>outbound_webhooks::dispatch</code> queues one
+                    <code> core:deliver_webhook</code> job per subscribed endpoint.
+                </p>
+                <pre className="bg-gray-900 text-gray-100 p-4 rounded-lg overflow-x-auto text-xs">
+                    <code className="language-rust">{`use core_http_log::{outbound_webhooks, WebhookEndpoints};
+
+// events: exact names, "deposit.*" or "*"
+let endpoint = WebhookEndpoints::create(&db, "https://partner.example/hooks", &["deposit.*"], None, None).await?;
+
+outbound_webhooks::dispatch(&db, &*ctx.jobs, "deposit.approved", json!({ "id": deposit.id })).await?;`}</code>
+                </pre>
+                <ul>
+                    <li>
+                        The body is <code>{'{"id", "event", "created_at", "data"}'}</code>. It is sent with
+                        <code> X-Webhook-Id</code> (the same on every retry), <code>X-Webhook-Event</code>,
+                        <code> X-Webhook-Timestamp</code> (unix seconds), and <code>X-Webhook-Signature: v1=&lt;hex&gt;</code>.
+                        The signature is HMAC-SHA256 of <code>{'"{timestamp}.{body}"'}</code> with the endpoint secret.
+                    </li>
+                    <li>
+                        The url must be <code>http</code> or <code>https</code>. Secrets are stored encrypted with the
+                        <code> [app] key</code>; <code>WebhookEndpoints::secret(&amp;endpoint)</code> returns the
+                        plain value and <code>rotate_secret</code> returns the new one.
+                    </li>
+                    <li>
+                        Any non-2xx response or network error is retried up to 10 times. The wait starts at 30s and
+                        doubles each time, up to 6 hours.
+                    </li>
+                    <li>
+                        After 20 failed attempts in a row the endpoint is disabled (<code>active = false</code>,
+                        <code> disabled_at</code> set). A success resets the count.
+                        <code> WebhookEndpoints::set_active(&amp;db, id, true)</code> turns it back on.
+                    </li>
+                    <li>
+                        Every attempt is written to <code>http_client_logs</code>, even when
+                        <code> HTTP_LOG_CLIENT_ENABLED</code> is off. Network errors are logged with no status.
+                    </li>
+                </ul>
+
                 <h2>Use cases</h2>
                 <ul>
                     <li>Audit external integration traffic</li>
@@ -4,7 +4,9 @@
 //!
 //! # Features
 //! - `WebhookLogLayer` - Middleware for logging incoming webhook requests
+//! - `WebhookVerifyLayer` - Signature, timestamp and replay checks for incoming webhooks
 //! - `HttpClient` - Wrapped reqwest client with request/response logging
+//! - `outbound_webhooks` - Signed webhook deliveries to subscribed endpoints
 //!
 //! # Configuration
 //! Controlled via environment variables:
@@ -15,11 +17,15 @@
 pub mod http_client;
 pub mod jobs;
 pub mod models;
+pub mod outbound_webhooks;
 pub mod webhook_middleware;
+pub mod webhook_verify;
 
 pub use http_client::HttpClient;
 pub use models::{HttpClientLog, WebhookLog};
+pub use outbound_webhooks::{DeliverWebhook, WebhookEndpoints};
 pub use webhook_middleware::WebhookLogLayer;
+pub use webhook_verify::{VerifiedWebhook, WebhookVerifier, WebhookVerifyLayer};
 
 use anyhow::Result;
 use core_db::{
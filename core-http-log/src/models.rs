@@ -9,6 +9,8 @@ use core_db::{
 use sqlx::PgPool;
 use uuid::Uuid;
 
+use crate::webhook_verify::WebhookVerification;
+
 /// Webhook (inbound) request log API.
 pub struct WebhookLog;
 
@@ -23,6 +25,7 @@ impl WebhookLog {
         response_status: Option<i32>,
         response_body: Option<&str>,
         duration_ms: Option<i32>,
+        verification: Option<&WebhookVerification>,
     ) -> anyhow::Result<Uuid> {
         let row = WebhookLogModel::create()
             .set(WebhookLogCol::REQUEST_URL, request_url.to_string())?
@@ -32,6 +35,14 @@ impl WebhookLog {
             .set(WebhookLogCol::RESPONSE_STATUS, response_status)?
             .set(WebhookLogCol::RESPONSE_BODY, response_body.map(str::to_string))?
             .set(WebhookLogCol::DURATION_MS, duration_ms)?
+            .set(
+                WebhookLogCol::VERIFICATION_STATUS,
+                verification.map(|v| v.status().to_string()),
+            )?
+            .set(
+                WebhookLogCol::VERIFICATION_ERROR,
+                verification.and_then(|v| v.error()).map(str::to_string),
+            )?
             .save(DbConn::pool(pool))
             .await?;
 
@@ -0,0 +1,381 @@
+//! Outbound webhooks: endpoint subscriptions, signed deliveries and retries.
+//!
+//! Every delivery attempt is recorded in `http_client_logs`.
+
+use crate::http_client::{HttpClient, HttpClientConfig};
+use crate::models::HttpClientLog;
+use anyhow::Result;
+use core_db::{
+    common::{
+        auth::token::generate_token,
+        crypt::Crypt,
+        sql::{DbConn, Op, OrderDir},
+    },
+    generated::models::{WebhookEndpointCol, WebhookEndpointModel, WebhookEndpointRecord},
+};
+use core_jobs::{driver::QueueDriver, Job, JobContext};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Consecutive failed attempts after which an endpoint is disabled.
+pub const AUTO_DISABLE_AFTER: i32 = 20;
+
+pub const ID_HEADER: &str = "X-Webhook-Id";
+pub const EVENT_HEADER: &str = "X-Webhook-Event";
+pub const TIMESTAMP_HEADER: &str = "X-Webhook-Timestamp";
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+
+/// `hex(HMAC-SHA256(secret, "{timestamp}.{body}"))`. Sent as `v1=<hex>` in
+/// `X-Webhook-Signature`, with the unix timestamp in `X-Webhook-Timestamp`.
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// `events` entries are exact names, `prefix.*` or `*`.
+pub fn matches_event(events: &Value, event: &str) -> bool {
+    let Some(events) = events.as_array() else {
+        return false;
+    };
+    events.iter().filter_map(Value::as_str).any(|pattern| {
+        pattern == "*"
+            || pattern == event
+            || pattern
+                .strip_suffix('*')
+                .is_some_and(|prefix| prefix.ends_with('.') && event.starts_with(prefix))
+    })
+}
+
+/// Cipher for stored endpoint secrets, keyed by `[app] key` from `global_config`.
+fn secret_crypt() -> Result<Crypt> {
+    let settings = core_config::global_config::get::<std::sync::Arc<core_config::Settings>>()
+        .ok_or_else(|| {
+            anyhow::anyhow!("Webhook secrets need the app key; register Settings in global_config")
+        })?;
+    Crypt::new(&settings.app.key)
+}
+
+/// Endpoints must be absolute `http` or `https` URLs.
+fn validate_url(url: &str) -> Result<()> {
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|e| anyhow::anyhow!("Invalid webhook url {url:?}: {e}"))?;
+    if !matches!(parsed.scheme(), "http" | "https") || !parsed.has_host() {
+        anyhow::bail!("Webhook url must be http(s), got {url:?}");
+    }
+    Ok(())
+}
+
+/// Subscription API over `webhook_endpoints`.
+pub struct WebhookEndpoints;
+
+impl WebhookEndpoints {
+    /// A `whsec_` secret is generated when `secret` is `None`. The secret is
+    /// stored encrypted with the app key; read it back with [`Self::secret`].
+    pub async fn create(
+        db: &PgPool,
+        url: &str,
+        events: &[&str],
+        secret: Option<String>,
+        description: Option<&str>,
+    ) -> Result<WebhookEndpointRecord> {
+        validate_url(url)?;
+        let secret = secret.unwrap_or_else(|| format!("whsec_{}", generate_token()));
+        WebhookEndpointModel::create()
+            .set(WebhookEndpointCol::URL, url.to_string())?
+            .set(
+                WebhookEndpointCol::SECRET,
+                secret_crypt()?.encrypt(&secret)?,
+            )?
+            .set(WebhookEndpointCol::EVENTS, json!(events))?
+            .set(
+                WebhookEndpointCol::DESCRIPTION,
+                description.map(str::to_string),
+            )?
+            .save(DbConn::pool(db))
+            .await
+    }
+
+    /// Decrypted signing secret of `endpoint`.
+    pub fn secret(endpoint: &WebhookEndpointRecord) -> Result<String> {
+        secret_crypt()?.decrypt(&endpoint.secret)
+    }
+
+    pub async fn list(db: &PgPool) -> Result<Vec<WebhookEndpointRecord>> {
+        WebhookEndpointModel::query()
+            .order_by(WebhookEndpointCol::CREATED_AT, OrderDir::Asc)
+            .all(DbConn::pool(db))
+            .await
+    }
+
+    /// Active endpoints subscribed to `event`.
+    pub async fn subscribed(db: &PgPool, event: &str) -> Result<Vec<WebhookEndpointRecord>> {
+        let endpoints = WebhookEndpointModel::query()
+            .where_col(WebhookEndpointCol::ACTIVE, Op::Eq, true)
+            .all(DbConn::pool(db))
+            .await?;
+        Ok(endpoints
+            .into_iter()
+            .filter(|endpoint| matches_event(&endpoint.events, event))
+            .collect())
+    }
+
+    /// Enabling clears the failure count and `disabled_at`.
+    pub async fn set_active(db: &PgPool, id: Uuid, active: bool) -> Result<bool> {
+        let mut patch = WebhookEndpointModel::query()
+            .where_col(WebhookEndpointCol::ID, Op::Eq, id)
+            .patch()
+            .assign(WebhookEndpointCol::ACTIVE, active)?
+            .assign(WebhookEndpointCol::UPDATED_AT, OffsetDateTime::now_utc())?;
+        if active {
+            patch = patch
+                .assign(WebhookEndpointCol::FAILURE_COUNT, 0)?
+                .assign(WebhookEndpointCol::DISABLED_AT, None::<OffsetDateTime>)?;
+        }
+        Ok(patch.save(DbConn::pool(db)).await? > 0)
+    }
+
+    /// Replace the secret; returns the new one.
+    pub async fn rotate_secret(db: &PgPool, id: Uuid) -> Result<Option<String>> {
+        let secret = format!("whsec_{}", generate_token());
+        let updated = WebhookEndpointModel::query()
+            .where_col(WebhookEndpointCol::ID, Op::Eq, id)
+            .patch()
+            .assign(
+                WebhookEndpointCol::SECRET,
+                secret_crypt()?.encrypt(&secret)?,
+            )?
+            .assign(WebhookEndpointCol::UPDATED_AT, OffsetDateTime::now_utc())?
+            .save(DbConn::pool(db))
+            .await?;
+        Ok((updated > 0).then_some(secret))
+    }
+
+    pub async fn delete(db: &PgPool, id: Uuid) -> Result<bool> {
+        let deleted = WebhookEndpointModel::query()
+            .where_col(WebhookEndpointCol::ID, Op::Eq, id)
+            .delete(DbConn::pool(db))
+            .await?;
+        Ok(deleted > 0)
+    }
+
+    /// Count a failed attempt. Returns `true` when this disabled the endpoint.
+    async fn record_failure(db: &PgPool, id: Uuid) -> Result<bool> {
+        WebhookEndpointModel::query()
+            .where_col(WebhookEndpointCol::ID, Op::Eq, id)
+            .patch()
+            .increment(WebhookEndpointCol::FAILURE_COUNT, 1)?
+            .save(DbConn::pool(db))
+            .await?;
+        let disabled = WebhookEndpointModel::query()
+            .where_col(WebhookEndpointCol::ID, Op::Eq, id)
+            .where_col(WebhookEndpointCol::ACTIVE, Op::Eq, true)
+            .where_col(
+                WebhookEndpointCol::FAILURE_COUNT,
+                Op::Ge,
+                AUTO_DISABLE_AFTER,
+            )
+            .patch()
+            .assign(WebhookEndpointCol::ACTIVE, false)?
+            .assign(
+                WebhookEndpointCol::DISABLED_AT,
+                Some(OffsetDateTime::now_utc()),
+            )?
+            .save(DbConn::pool(db))
+            .await?;
+        Ok(disabled > 0)
+    }
+
+    async fn reset_failures(db: &PgPool, id: Uuid) -> Result<()> {
+        WebhookEndpointModel::query()
+            .where_col(WebhookEndpointCol::ID, Op::Eq, id)
+            .patch()
+            .assign(WebhookEndpointCol::FAILURE_COUNT, 0)?
+            .save(DbConn::pool(db))
+            .await?;
+        Ok(())
+    }
+}
+
+/// Queue a `DeliverWebhook` for every active endpoint subscribed to `event`.
+/// Returns the number of deliveries.
+pub async fn dispatch<Q: QueueDriver + ?Sized>(
+    db: &PgPool,
+    queue: &Q,
+    event: &str,
+    data: Value,
+) -> Result<usize> {
+    let endpoints = WebhookEndpoints::subscribed(db, event).await?;
+    let occurred_at = OffsetDateTime::now_utc().unix_timestamp();
+    for endpoint in &endpoints {
+        DeliverWebhook {
+            endpoint_id: endpoint.id,
+            delivery_id: Uuid::new_v4(),
+            event: event.to_string(),
+            data: data.clone(),
+            occurred_at,
+        }
+        .dispatch(queue)
+        .await?;
+    }
+    Ok(endpoints.len())
+}
+
+/// One event for one endpoint. Retries with exponential backoff; the endpoint
+/// is disabled after `AUTO_DISABLE_AFTER` consecutive failed attempts.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DeliverWebhook {
+    pub endpoint_id: Uuid,
+    /// Stable across retries (`X-Webhook-Id`), so receivers can dedupe.
+    pub delivery_id: Uuid,
+    pub event: String,
+    pub data: Value,
+    pub occurred_at: i64,
+}
+
+#[async_trait::async_trait]
+impl Job for DeliverWebhook {
+    const NAME: &'static str = "core:deliver_webhook";
+
+    fn max_retries(&self) -> u32 {
+        10
+    }
+
+    /// 30s doubling per attempt, capped at 6 hours.
+    fn backoff(&self, attempt: u32) -> u64 {
+        30u64.saturating_mul(1 << attempt.min(16)).min(21_600)
+    }
+
+    async fn handle(&self, ctx: &JobContext) -> anyhow::Result<()> {
+        let endpoint = WebhookEndpointModel::query()
+            .find(DbConn::pool(&ctx.db), self.endpoint_id)
+            .await?;
+        let Some(endpoint) = endpoint.filter(|endpoint| endpoint.active) else {
+            return Ok(());
+        };
+
+        let body = json!({
+            "id": self.delivery_id,
+            "event": self.event,
+            "created_at": self.occurred_at,
+            "data": self.data,
+        })
+        .to_string();
+        let timestamp = OffsetDateTime::now_utc().unix_timestamp();
+        let signature = sign(&WebhookEndpoints::secret(&endpoint)?, timestamp, &body);
+
+        let inner = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(15))
+            .build()?;
+        let client = HttpClient::with_client(inner, &ctx.db, HttpClientConfig { enabled: true });
+        let result = client
+            .post(&endpoint.url)
+            .header("Content-Type", "application/json")
+            .header(ID_HEADER, &self.delivery_id.to_string())
+            .header(EVENT_HEADER, &self.event)
+            .header(TIMESTAMP_HEADER, &timestamp.to_string())
+            .header(SIGNATURE_HEADER, &format!("v1={}", signature))
+            .body(body.clone())
+            .send_and_read_text()
+            .await;
+
+        let error = match result {
+            Ok((status, _)) if (200..300).contains(&status) => {
+                if endpoint.failure_count > 0 {
+                    WebhookEndpoints::reset_failures(&ctx.db, endpoint.id).await?;
+                }
+                return Ok(());
+            }
+            Ok((status, response)) => {
+                format!(
+                    "HTTP {}: {}",
+                    status,
+                    response.chars().take(500).collect::<String>()
+                )
+            }
+            Err(e) => {
+                // No response, so HttpClient logged nothing
+                if let Err(log_error) = HttpClientLog::insert(
+                    &ctx.db,
+                    &endpoint.url,
+                    "POST",
+                    None,
+                    Some(&body),
+                    None,
+                    None,
+                    Some(&e.to_string()),
+                    None,
+                )
+                .await
+                {
+                    tracing::error!("Failed to log webhook delivery: {}", log_error);
+                }
+                e.to_string()
+            }
+        };
+
+        if WebhookEndpoints::record_failure(&ctx.db, endpoint.id).await? {
+            tracing::warn!(
+                "Webhook endpoint {} disabled after {} failed attempts",
+                endpoint.url,
+                AUTO_DISABLE_AFTER
+            );
+            return Ok(());
+        }
+        anyhow::bail!(
+            "Webhook {} to {} failed: {}",
+            self.event,
+            endpoint.url,
+            error
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_is_hmac_of_timestamp_and_body() {
+        let expected = {
+            let mut mac = Hmac::<Sha256>::new_from_slice(b"whsec_test").unwrap();
+            mac.update(b"1700000000.{\"id\":1}");
+            hex::encode(mac.finalize().into_bytes())
+        };
+        assert_eq!(sign("whsec_test", 1_700_000_000, r#"{"id":1}"#), expected);
+        assert_ne!(sign("whsec_test", 1_700_000_001, r#"{"id":1}"#), expected);
+        assert_ne!(sign("whsec_other", 1_700_000_000, r#"{"id":1}"#), expected);
+    }
+
+    #[test]
+    fn matches_event_patterns() {
+        assert!(matches_event(&json!(["*"]), "deposit.approved"));
+        assert!(matches_event(
+            &json!(["deposit.approved"]),
+            "deposit.approved"
+        ));
+        assert!(matches_event(&json!(["deposit.*"]), "deposit.approved"));
+        assert!(!matches_event(&json!(["deposit.*"]), "deposits.approved"));
+        assert!(!matches_event(&json!(["deposit*"]), "deposit.approved"));
+        assert!(!matches_event(&json!(["withdrawal.*"]), "deposit.approved"));
+        assert!(!matches_event(&json!([]), "deposit.approved"));
+        assert!(!matches_event(&json!("*"), "deposit.approved"));
+    }
+
+    #[test]
+    fn validate_url_requires_http_scheme() {
+        assert!(validate_url("https://partner.example/hooks").is_ok());
+        assert!(validate_url("http://localhost:8080/hooks").is_ok());
+        assert!(validate_url("ftp://partner.example/hooks").is_err());
+        assert!(validate_url("file:///etc/passwd").is_err());
+        assert!(validate_url("partner.example/hooks").is_err());
+    }
+}
@@ -15,6 +15,7 @@ use std::{sync::Arc, time::Instant};
 use tower::{Layer, Service};
 
 use crate::models::WebhookLog;
+use crate::webhook_verify::WebhookVerification;
 
 /// Configuration for webhook logging
 #[derive(Clone)]
@@ -131,6 +132,7 @@ where
 
             // Extract response info
             let status = response.status().as_u16() as i32;
+            let verification = response.extensions().get::<WebhookVerification>().cloned();
 
             // Buffer the response body
             let (parts, body) = response.into_parts();
@@ -154,6 +156,7 @@ where
                     Some(status),
                     Some(&response_body),
                     Some(duration_ms),
+                    verification.as_ref(),
                 )
                 .await
                 {
@@ -215,6 +218,7 @@ pub async fn webhook_log_middleware(
 
     // Extract response info
     let status = response.status().as_u16() as i32;
+    let verification = response.extensions().get::<WebhookVerification>().cloned();
 
     // Buffer response body
     let (parts, body) = response.into_parts();
@@ -238,6 +242,7 @@ pub async fn webhook_log_middleware(
             Some(status),
             Some(&response_body),
             Some(duration_ms),
+            verification.as_ref(),
         )
         .await
         {
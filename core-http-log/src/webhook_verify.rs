@@ -0,0 +1,683 @@
+//! Inbound webhook signature verification for Axum
+//!
+//! `WebhookVerifyLayer` checks the provider signature before the handler runs,
+//! rejects stale timestamps and replays, and tags the response so
+//! `WebhookLogLayer` records the outcome on the `webhook_logs` row.
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{FromRequestParts, Request},
+    http::{header::HeaderMap, request::Parts},
+    response::{IntoResponse, Response},
+};
+use base64::Engine;
+use core_db::infra::cache::Cache;
+use core_web::error::AppError;
+use hmac::{Hmac, Mac};
+use http_body_util::BodyExt;
+use sha2::Sha256;
+use time::OffsetDateTime;
+use tower::{Layer, Service};
+
+use crate::outbound_webhooks::{ID_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+
+/// Replay keys live this long when the scheme has no timestamp to bound them.
+pub const REPLAY_TTL_WITHOUT_TIMESTAMP: u64 = 60 * 60 * 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureEncoding {
+    Hex,
+    Base64,
+}
+
+#[derive(Debug, Clone)]
+pub enum SignatureScheme {
+    /// `header: <prefix><encoded HMAC-SHA256>`. The signed payload is
+    /// `"{timestamp}.{body}"` when `timestamp_header` is set, otherwise the raw body.
+    Hmac {
+        header: String,
+        encoding: SignatureEncoding,
+        prefix: Option<String>,
+        timestamp_header: Option<String>,
+    },
+    /// `header: t=<unix>,v1=<hex>[,v1=<hex>...]` over `"{t}.{body}"`.
+    Stripe { header: String },
+}
+
+/// Outcome stored on `webhook_logs.verification_status`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebhookVerification {
+    Verified,
+    Rejected(String),
+}
+
+impl WebhookVerification {
+    pub const VERIFIED: &'static str = "verified";
+    pub const REJECTED: &'static str = "rejected";
+
+    pub fn status(&self) -> &'static str {
+        match self {
+            Self::Verified => Self::VERIFIED,
+            Self::Rejected(_) => Self::REJECTED,
+        }
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        match self {
+            Self::Verified => None,
+            Self::Rejected(reason) => Some(reason),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum WebhookRejection {
+    MissingSignature,
+    MalformedSignature,
+    InvalidSignature,
+    MissingTimestamp,
+    TimestampOutOfTolerance,
+    DeliveryIdMismatch,
+    Replayed,
+    ReplayCacheUnavailable(anyhow::Error),
+}
+
+impl std::fmt::Display for WebhookRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingSignature => write!(f, "Missing webhook signature"),
+            Self::MalformedSignature => write!(f, "Malformed webhook signature"),
+            Self::InvalidSignature => write!(f, "Invalid webhook signature"),
+            Self::MissingTimestamp => write!(f, "Missing webhook timestamp"),
+            Self::TimestampOutOfTolerance => write!(f, "Webhook timestamp outside tolerance"),
+            Self::DeliveryIdMismatch => write!(f, "Webhook delivery id does not match the body"),
+            Self::Replayed => write!(f, "Webhook already received"),
+            Self::ReplayCacheUnavailable(e) => write!(f, "Replay cache unavailable: {}", e),
+        }
+    }
+}
+
+impl IntoResponse for WebhookRejection {
+    fn into_response(self) -> Response {
+        match self {
+            // 500 so the provider retries once Redis is back
+            Self::ReplayCacheUnavailable(e) => AppError::Internal(e).into_response(),
+            rejection => AppError::Unauthorized(rejection.to_string()).into_response(),
+        }
+    }
+}
+
+/// Request extension set by `WebhookVerifyLayer` on accepted requests.
+#[derive(Debug, Clone)]
+pub struct VerifiedWebhook {
+    pub provider: String,
+    pub timestamp: Option<i64>,
+    /// Delivery id, when the verifier has an id header or signed body id configured.
+    pub delivery_id: Option<String>,
+}
+
+impl<S> FromRequestParts<S> for VerifiedWebhook
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<VerifiedWebhook>()
+            .cloned()
+            .ok_or_else(|| AppError::Unauthorized("Webhook signature not verified".to_string()))
+    }
+}
+
+/// Signature rules for one provider.
+#[derive(Clone)]
+pub struct WebhookVerifier {
+    provider: String,
+    secret: String,
+    scheme: SignatureScheme,
+    tolerance_secs: u64,
+    id_header: Option<String>,
+    body_id_field: Option<String>,
+    replay_cache: Option<Cache>,
+}
+
+impl WebhookVerifier {
+    /// Hex HMAC-SHA256 of the raw body in `header`.
+    pub fn hmac(provider: &str, secret: impl Into<String>, header: &str) -> Self {
+        Self::new(
+            provider,
+            secret,
+            SignatureScheme::Hmac {
+                header: header.to_string(),
+                encoding: SignatureEncoding::Hex,
+                prefix: None,
+                timestamp_header: None,
+            },
+        )
+    }
+
+    /// Stripe's `Stripe-Signature: t=...,v1=...` format.
+    pub fn stripe(provider: &str, secret: impl Into<String>) -> Self {
+        Self::new(
+            provider,
+            secret,
+            SignatureScheme::Stripe {
+                header: "Stripe-Signature".to_string(),
+            },
+        )
+    }
+
+    /// Deliveries signed by `outbound_webhooks` (`X-Webhook-Signature: v1=...`).
+    pub fn outbound(provider: &str, secret: impl Into<String>) -> Self {
+        Self::hmac(provider, secret, SIGNATURE_HEADER)
+            .with_prefix("v1=")
+            .with_timestamp_header(TIMESTAMP_HEADER)
+            .with_id_header(ID_HEADER)
+            .with_body_id("id")
+    }
+
+    pub fn new(provider: &str, secret: impl Into<String>, scheme: SignatureScheme) -> Self {
+        Self {
+            provider: provider.to_string(),
+            secret: secret.into(),
+            scheme,
+            tolerance_secs: 300,
+            id_header: None,
+            body_id_field: None,
+            replay_cache: None,
+        }
+    }
+
+    /// Only applies to `SignatureScheme::Hmac`.
+    pub fn with_encoding(mut self, value: SignatureEncoding) -> Self {
+        if let SignatureScheme::Hmac { encoding, .. } = &mut self.scheme {
+            *encoding = value;
+        }
+        self
+    }
+
+    /// Strip this prefix (e.g. `sha256=`) before decoding. Only applies to `SignatureScheme::Hmac`.
+    pub fn with_prefix(mut self, value: &str) -> Self {
+        if let SignatureScheme::Hmac { prefix, .. } = &mut self.scheme {
+            *prefix = Some(value.to_string());
+        }
+        self
+    }
+
+    /// Sign `"{timestamp}.{body}"` and enforce the tolerance. Only applies to `SignatureScheme::Hmac`.
+    pub fn with_timestamp_header(mut self, value: &str) -> Self {
+        if let SignatureScheme::Hmac {
+            timestamp_header, ..
+        } = &mut self.scheme
+        {
+            *timestamp_header = Some(value.to_string());
+        }
+        self
+    }
+
+    /// Maximum clock skew, in seconds, between the signed timestamp and now (default 300).
+    pub fn with_tolerance(mut self, secs: u64) -> Self {
+        self.tolerance_secs = secs;
+        self
+    }
+
+    /// Report this header as `VerifiedWebhook::delivery_id`. It is not signed,
+    /// so it is never used as the replay nonce.
+    pub fn with_id_header(mut self, header: &str) -> Self {
+        self.id_header = Some(header.to_string());
+        self
+    }
+
+    /// Take the delivery id from this top-level field of the signed JSON body,
+    /// and reject requests whose id header says otherwise.
+    pub fn with_body_id(mut self, field: &str) -> Self {
+        self.body_id_field = Some(field.to_string());
+        self
+    }
+
+    /// Reject a nonce seen before. Nonces are released again when the handler
+    /// does not answer 2xx, so provider retries still go through.
+    pub fn with_replay_protection(mut self, cache: Cache) -> Self {
+        self.replay_cache = Some(cache);
+        self
+    }
+
+    pub fn provider(&self) -> &str {
+        &self.provider
+    }
+
+    /// Check the signature and timestamp. Does not touch the replay cache.
+    pub fn verify(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> Result<VerifiedWebhook, WebhookRejection> {
+        self.verify_signed(headers, body)
+            .map(|(verified, _)| verified)
+    }
+
+    /// `verify` plus the replay nonce: the HMAC over the signed payload, so
+    /// it cannot be changed without re-signing.
+    fn verify_signed(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> Result<(VerifiedWebhook, String), WebhookRejection> {
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let (timestamp, mac) = match &self.scheme {
+            SignatureScheme::Hmac {
+                header,
+                encoding,
+                prefix,
+                timestamp_header,
+            } => {
+                let value =
+                    header_value(headers, header).ok_or(WebhookRejection::MissingSignature)?;
+                let value = match prefix {
+                    Some(prefix) => value
+                        .strip_prefix(prefix.as_str())
+                        .ok_or(WebhookRejection::MalformedSignature)?,
+                    None => value,
+                };
+                let signature = decode(value.trim(), *encoding)?;
+                let timestamp = match timestamp_header {
+                    Some(name) => Some(parse_timestamp(
+                        header_value(headers, name).ok_or(WebhookRejection::MissingTimestamp)?,
+                    )?),
+                    None => None,
+                };
+                let mac = self.check_signature(timestamp, body, &[signature])?;
+                (timestamp, mac)
+            }
+            SignatureScheme::Stripe { header } => {
+                let value =
+                    header_value(headers, header).ok_or(WebhookRejection::MissingSignature)?;
+                let mut timestamp = None;
+                let mut signatures = Vec::new();
+                for part in value.split(',') {
+                    match part.trim().split_once('=') {
+                        Some(("t", t)) => timestamp = Some(parse_timestamp(t)?),
+                        Some(("v1", sig)) => signatures.push(decode(sig, SignatureEncoding::Hex)?),
+                        _ => {}
+                    }
+                }
+                let timestamp = timestamp.ok_or(WebhookRejection::MissingTimestamp)?;
+                if signatures.is_empty() {
+                    return Err(WebhookRejection::MissingSignature);
+                }
+                let mac = self.check_signature(Some(timestamp), body, &signatures)?;
+                (Some(timestamp), mac)
+            }
+        };
+
+        if let Some(timestamp) = timestamp {
+            if now.abs_diff(timestamp) > self.tolerance_secs {
+                return Err(WebhookRejection::TimestampOutOfTolerance);
+            }
+        }
+
+        let header_id = self
+            .id_header
+            .as_deref()
+            .and_then(|name| header_value(headers, name));
+        let delivery_id = match self.body_id_field.as_deref() {
+            Some(field) => {
+                let body_id = body_id(body, field);
+                if header_id.is_some() && header_id != body_id.as_deref() {
+                    return Err(WebhookRejection::DeliveryIdMismatch);
+                }
+                body_id
+            }
+            None => header_id.map(str::to_string),
+        };
+
+        let verified = VerifiedWebhook {
+            provider: self.provider.clone(),
+            timestamp,
+            delivery_id,
+        };
+        Ok((verified, hex::encode(mac)))
+    }
+
+    fn check_signature(
+        &self,
+        timestamp: Option<i64>,
+        body: &[u8],
+        signatures: &[Vec<u8>],
+    ) -> Result<Vec<u8>, WebhookRejection> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.secret.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        if let Some(timestamp) = timestamp {
+            mac.update(format!("{}.", timestamp).as_bytes());
+        }
+        mac.update(body);
+        // verify_slice compares in constant time
+        if signatures
+            .iter()
+            .any(|signature| mac.clone().verify_slice(signature).is_ok())
+        {
+            Ok(mac.finalize().into_bytes().to_vec())
+        } else {
+            Err(WebhookRejection::InvalidSignature)
+        }
+    }
+
+    fn replay_key(&self, nonce: &str) -> String {
+        let digest = hex::encode(<Sha256 as sha2::Digest>::digest(nonce.as_bytes()));
+        format!("webhook:nonce:{}:{}", self.provider, digest)
+    }
+
+    /// Keep nonces slightly longer than any timestamp the verifier would still accept.
+    fn replay_ttl(&self, timestamp: Option<i64>) -> u64 {
+        match timestamp {
+            Some(_) => self.tolerance_secs.saturating_mul(2).max(60),
+            None => REPLAY_TTL_WITHOUT_TIMESTAMP,
+        }
+    }
+
+    /// Claim the request's nonce. Returns the key to release if the handler fails.
+    async fn claim(
+        &self,
+        nonce: &str,
+        verified: &VerifiedWebhook,
+    ) -> Result<Option<String>, WebhookRejection> {
+        let Some(cache) = &self.replay_cache else {
+            return Ok(None);
+        };
+        let key = self.replay_key(nonce);
+        match cache
+            .add(&key, "1", self.replay_ttl(verified.timestamp))
+            .await
+        {
+            Ok(true) => Ok(Some(key)),
+            Ok(false) => Err(WebhookRejection::Replayed),
+            Err(e) => Err(WebhookRejection::ReplayCacheUnavailable(e)),
+        }
+    }
+}
+
+fn header_value<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+/// String or number `field` of a JSON object body.
+fn body_id(body: &[u8], field: &str) -> Option<String> {
+    let body: serde_json::Value = serde_json::from_slice(body).ok()?;
+    match body.get(field)? {
+        serde_json::Value::String(id) => Some(id.clone()),
+        serde_json::Value::Number(id) => Some(id.to_string()),
+        _ => None,
+    }
+}
+
+fn parse_timestamp(value: &str) -> Result<i64, WebhookRejection> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| WebhookRejection::MalformedSignature)
+}
+
+fn decode(value: &str, encoding: SignatureEncoding) -> Result<Vec<u8>, WebhookRejection> {
+    match encoding {
+        SignatureEncoding::Hex => hex::decode(value).ok(),
+        SignatureEncoding::Base64 => base64::engine::general_purpose::STANDARD.decode(value).ok(),
+    }
+    .ok_or(WebhookRejection::MalformedSignature)
+}
+
+/// Layer that verifies webhook signatures before the handler runs.
+///
+/// Add it inside `WebhookLogLayer` (i.e. `.layer(WebhookVerifyLayer::new(..))`
+/// before `.layer(WebhookLogLayer::new(..))`) so the log row gets the outcome.
+#[derive(Clone)]
+pub struct WebhookVerifyLayer {
+    verifier: WebhookVerifier,
+}
+
+impl WebhookVerifyLayer {
+    pub fn new(verifier: WebhookVerifier) -> Self {
+        Self { verifier }
+    }
+}
+
+impl<S> Layer<S> for WebhookVerifyLayer {
+    type Service = WebhookVerifyMiddleware<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        WebhookVerifyMiddleware {
+            inner,
+            verifier: self.verifier.clone(),
+        }
+    }
+}
+
+/// Middleware service for webhook verification
+#[derive(Clone)]
+pub struct WebhookVerifyMiddleware<S> {
+    inner: S,
+    verifier: WebhookVerifier,
+}
+
+impl<S> Service<Request> for WebhookVerifyMiddleware<S>
+where
+    S: Service<Request, Response = Response> + Send + Clone + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+    >;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let verifier = self.verifier.clone();
+        let mut inner = self.inner.clone();
+
+        Box::pin(async move {
+            // Buffer the request body
+            let (mut parts, body) = request.into_parts();
+            let body_bytes = match body.collect().await {
+                Ok(collected) => collected.to_bytes(),
+                Err(_) => Bytes::new(),
+            };
+
+            let result = match verifier.verify_signed(&parts.headers, &body_bytes) {
+                Ok((verified, nonce)) => verifier
+                    .claim(&nonce, &verified)
+                    .await
+                    .map(|key| (verified, key)),
+                Err(rejection) => Err(rejection),
+            };
+            let (verified, replay_key) = match result {
+                Ok(accepted) => accepted,
+                Err(rejection) => {
+                    tracing::warn!(
+                        "Rejected {} webhook {}: {}",
+                        verifier.provider,
+                        parts.uri,
+                        rejection
+                    );
+                    let outcome = WebhookVerification::Rejected(rejection.to_string());
+                    let mut response = rejection.into_response();
+                    response.extensions_mut().insert(outcome);
+                    return Ok(response);
+                }
+            };
+
+            parts.extensions.insert(verified);
+            let request = Request::from_parts(parts, Body::from(body_bytes));
+            let mut response = inner.call(request).await?;
+
+            if let Some(key) = replay_key {
+                if !response.status().is_success() {
+                    // Not processed, let the provider's retry through
+                    if let Some(cache) = &verifier.replay_cache {
+                        if let Err(e) = cache.forget(&key).await {
+                            tracing::warn!("Failed to release webhook nonce: {}", e);
+                        }
+                    }
+                }
+            }
+
+            response
+                .extensions_mut()
+                .insert(WebhookVerification::Verified);
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    const SECRET: &str = "whsec_test";
+    const BODY: &str = r#"{"id":"d-1","event":"deposit.approved"}"#;
+
+    fn now() -> i64 {
+        OffsetDateTime::now_utc().unix_timestamp()
+    }
+
+    fn mac(timestamp: Option<i64>, body: &str) -> Vec<u8> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+        if let Some(timestamp) = timestamp {
+            mac.update(format!("{}.", timestamp).as_bytes());
+        }
+        mac.update(body.as_bytes());
+        mac.finalize().into_bytes().to_vec()
+    }
+
+    fn headers(pairs: &[(&'static str, String)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(*name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    fn outbound_headers(timestamp: i64, id: &str) -> HeaderMap {
+        headers(&[
+            (
+                SIGNATURE_HEADER,
+                format!("v1={}", hex::encode(mac(Some(timestamp), BODY))),
+            ),
+            (TIMESTAMP_HEADER, timestamp.to_string()),
+            (ID_HEADER, id.to_string()),
+        ])
+    }
+
+    #[test]
+    fn hmac_hex_and_base64() {
+        let verifier = WebhookVerifier::hmac("shop", SECRET, "X-Sig");
+        let ok = headers(&[("X-Sig", hex::encode(mac(None, BODY)))]);
+        let verified = verifier.verify(&ok, BODY.as_bytes()).unwrap();
+        assert_eq!(verified.timestamp, None);
+        assert!(matches!(
+            verifier.verify(&ok, b"{}"),
+            Err(WebhookRejection::InvalidSignature)
+        ));
+        assert!(matches!(
+            verifier.verify(&HeaderMap::new(), BODY.as_bytes()),
+            Err(WebhookRejection::MissingSignature)
+        ));
+
+        let verifier = verifier.with_encoding(SignatureEncoding::Base64);
+        let encoded = base64::engine::general_purpose::STANDARD.encode(mac(None, BODY));
+        assert!(verifier
+            .verify(&headers(&[("X-Sig", encoded)]), BODY.as_bytes())
+            .is_ok());
+        assert!(matches!(
+            verifier.verify(&headers(&[("X-Sig", "%%".into())]), BODY.as_bytes()),
+            Err(WebhookRejection::MalformedSignature)
+        ));
+    }
+
+    #[test]
+    fn wrong_secret_is_rejected() {
+        let verifier = WebhookVerifier::outbound("partner", "whsec_other");
+        assert!(matches!(
+            verifier.verify(&outbound_headers(now(), "d-1"), BODY.as_bytes()),
+            Err(WebhookRejection::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn stripe_accepts_any_matching_v1() {
+        let verifier = WebhookVerifier::stripe("stripe", SECRET);
+        let t = now();
+        let good = hex::encode(mac(Some(t), BODY));
+        let bad = hex::encode(mac(Some(t), "{}"));
+        let header = |value: String| headers(&[("Stripe-Signature", value)]);
+
+        let verified = verifier
+            .verify(
+                &header(format!("t={t},v1={bad},v1={good}")),
+                BODY.as_bytes(),
+            )
+            .unwrap();
+        assert_eq!(verified.timestamp, Some(t));
+        assert!(matches!(
+            verifier.verify(&header(format!("t={t},v1={bad}")), BODY.as_bytes()),
+            Err(WebhookRejection::InvalidSignature)
+        ));
+        assert!(matches!(
+            verifier.verify(&header(format!("v1={good}")), BODY.as_bytes()),
+            Err(WebhookRejection::MissingTimestamp)
+        ));
+        assert!(matches!(
+            verifier.verify(&header(format!("t={t}")), BODY.as_bytes()),
+            Err(WebhookRejection::MissingSignature)
+        ));
+    }
+
+    #[test]
+    fn stale_timestamp_is_rejected() {
+        let verifier = WebhookVerifier::outbound("partner", SECRET);
+        let stale = now() - 301;
+        assert!(matches!(
+            verifier.verify(&outbound_headers(stale, "d-1"), BODY.as_bytes()),
+            Err(WebhookRejection::TimestampOutOfTolerance)
+        ));
+        assert!(verifier
+            .with_tolerance(600)
+            .verify(&outbound_headers(stale, "d-1"), BODY.as_bytes())
+            .is_ok());
+    }
+
+    #[test]
+    fn outbound_delivery_id_comes_from_signed_body() {
+        let verifier = WebhookVerifier::outbound("partner", SECRET);
+        let t = now();
+        let verified = verifier
+            .verify(&outbound_headers(t, "d-1"), BODY.as_bytes())
+            .unwrap();
+        assert_eq!(verified.delivery_id.as_deref(), Some("d-1"));
+        assert!(matches!(
+            verifier.verify(&outbound_headers(t, "d-2"), BODY.as_bytes()),
+            Err(WebhookRejection::DeliveryIdMismatch)
+        ));
+    }
+
+    #[test]
+    fn nonce_ignores_unsigned_headers() {
+        let verifier = WebhookVerifier::hmac("shop", SECRET, "X-Sig").with_id_header("X-Id");
+        let signature = hex::encode(mac(None, BODY));
+        let nonce = |id: &str, signature: String| {
+            let headers = headers(&[("X-Sig", signature), ("X-Id", id.to_string())]);
+            verifier.verify_signed(&headers, BODY.as_bytes()).unwrap().1
+        };
+        let first = nonce("a", signature.clone());
+        assert_eq!(first, nonce("b", signature.clone()));
+        assert_eq!(first, nonce("a", signature.to_uppercase()));
+    }
+}
@@ -13,7 +13,7 @@ use redis::AsyncCommands;
 use serde::Serialize;
 use sqlx::FromRow;
 
-use crate::queue::RedisQueue;
+use crate::{batch::JobBatch, events::JobEvents, queue::RedisQueue};
 
 #[derive(Serialize)]
 pub struct QueueInfo {
@@ -41,15 +41,46 @@ pub fn routes(queue: RedisQueue, db: Option<sqlx::PgPool>) -> Router {
     let state = ApiState { queue, db };
     Router::new()
         .route("/", get(list_queues))
-        .route("/:name", get(get_queue).delete(clear_queue))
-        .route("/:name/jobs", get(peek_jobs))
+        .route("/{name}", get(get_queue).delete(clear_queue))
+        .route("/{name}/jobs", get(peek_jobs))
         .route("/failed", get(list_failed_jobs))
-        .route("/failed/:id/retry", post(retry_failed_job))
+        .route("/failed/{id}/retry", post(retry_failed_job))
+        .route("/batches/{id}", get(get_batch))
         .with_state(state)
 }
 
+/// Job metrics of this process: `GET /` (JSON snapshot) and `GET /prometheus` (text format).
+pub fn metrics_routes(events: JobEvents) -> Router {
+    Router::new()
+        .route("/", get(metrics_snapshot))
+        .route("/prometheus", get(metrics_prometheus))
+        .with_state(events)
+}
+
 // ... handlers
 
+async fn metrics_snapshot(State(events): State<JobEvents>) -> impl IntoResponse {
+    Json(events.metrics_snapshot())
+}
+
+async fn metrics_prometheus(State(events): State<JobEvents>) -> impl IntoResponse {
+    (
+        [(
+            axum::http::header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        events.metrics_snapshot().to_prometheus(),
+    )
+}
+
+async fn get_batch(State(state): State<ApiState>, Path(id): Path<String>) -> impl IntoResponse {
+    match JobBatch::find(&state.queue, &id).await {
+        Ok(Some(status)) => Json(status).into_response(),
+        Ok(None) => (StatusCode::NOT_FOUND, "Batch not found").into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
 async fn list_failed_jobs(State(state): State<ApiState>) -> impl IntoResponse {
     let db = match state.db {
         Some(db) => db,
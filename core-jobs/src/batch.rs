@@ -0,0 +1,285 @@
+use crate::{
+    driver::{BatchStore, QueueDriver},
+    queue::RedisQueue,
+    Job, JobPayload,
+};
+use redis::AsyncCommands;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// How long batch bookkeeping stays in Redis after dispatch (7 days).
+const BATCH_TTL_SECS: i64 = 7 * 24 * 3600;
+
+/// A group of jobs tracked together, with optional completion callbacks.
+///
+/// Pending/failed counters live in the Redis hash `{prefix}:batch:{id}`.
+/// The worker updates them when a member job succeeds or permanently fails:
+/// - `catch` is pushed on the first permanent failure,
+/// - `then` is pushed once every job finished and none failed,
+/// - `finally` is pushed once every job finished, regardless of failures.
+pub struct JobBatch {
+    name: String,
+    jobs: Vec<JobPayload>,
+    then: Option<JobPayload>,
+    catch: Option<JobPayload>,
+    finally: Option<JobPayload>,
+}
+
+impl JobBatch {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            jobs: Vec::new(),
+            then: None,
+            catch: None,
+            finally: None,
+        }
+    }
+
+    /// Add a member job to the batch.
+    pub fn push<J: Job>(mut self, job: &J) -> anyhow::Result<Self> {
+        self.jobs.push(JobPayload::from_job(job)?);
+        Ok(self)
+    }
+
+    /// Job pushed when every member succeeded.
+    pub fn then<J: Job>(mut self, job: &J) -> anyhow::Result<Self> {
+        self.then = Some(JobPayload::from_job(job)?);
+        Ok(self)
+    }
+
+    /// Job pushed on the first permanent failure of a member.
+    pub fn catch<J: Job>(mut self, job: &J) -> anyhow::Result<Self> {
+        self.catch = Some(JobPayload::from_job(job)?);
+        Ok(self)
+    }
+
+    /// Job pushed when every member finished, successful or not.
+    pub fn finally<J: Job>(mut self, job: &J) -> anyhow::Result<Self> {
+        self.finally = Some(JobPayload::from_job(job)?);
+        Ok(self)
+    }
+
+    /// Register the batch and push its jobs into `queue`. Returns the batch id.
+    ///
+    /// Counters live in the driver's `BatchStore`; drivers without one reject the batch.
+    pub async fn dispatch<Q: QueueDriver + ?Sized>(self, queue: &Q) -> anyhow::Result<String> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let store = match queue.batch_store() {
+            BatchStore::Redis(store) => store,
+            BatchStore::Untracked => {
+                for mut payload in self.jobs {
+                    payload.batch_id = Some(id.clone());
+                    queue.push_payload(&payload).await?;
+                }
+                return Ok(id);
+            }
+            BatchStore::Unsupported => {
+                anyhow::bail!("Batch {}: the queue driver has no batch store", self.name)
+            }
+        };
+        let key = store.batch_key(&id);
+        let mut conn = store.client.get_multiplexed_async_connection().await?;
+
+        let mut fields: Vec<(&str, String)> = vec![
+            ("id", id.clone()),
+            ("name", self.name.clone()),
+            ("total", self.jobs.len().to_string()),
+            ("pending", self.jobs.len().to_string()),
+            ("failed", "0".to_string()),
+            ("created_at", now_unix().to_string()),
+        ];
+        for (field, callback) in [
+            ("then", &self.then),
+            ("catch", &self.catch),
+            ("finally", &self.finally),
+        ] {
+            if let Some(payload) = callback {
+                fields.push((field, serde_json::to_string(payload)?));
+            }
+        }
+
+        let _: () = redis::pipe()
+            .hset_multiple(&key, &fields)
+            .ignore()
+            .expire(&key, BATCH_TTL_SECS)
+            .ignore()
+            .query_async(&mut conn)
+            .await?;
+
+        if self.jobs.is_empty() {
+            finish_batch(queue, &mut conn, &key).await?;
+            return Ok(id);
+        }
+
+        for mut payload in self.jobs {
+            payload.batch_id = Some(id.clone());
+            if !queue.push_payload(&payload).await? {
+                // Deduplicated by its unique key: it will never report back, count it as done.
+                record_outcome(store, &mut conn, queue, &id, true).await?;
+            }
+        }
+
+        Ok(id)
+    }
+
+    /// Read the current state of a batch. `None` when unknown or expired.
+    pub async fn find(queue: &RedisQueue, id: &str) -> anyhow::Result<Option<BatchStatus>> {
+        let mut conn = queue.client.get_multiplexed_async_connection().await?;
+        let raw: HashMap<String, String> = conn.hgetall(queue.batch_key(id)).await?;
+        if raw.is_empty() {
+            return Ok(None);
+        }
+
+        let number = |field: &str| {
+            raw.get(field)
+                .and_then(|v| v.parse::<i64>().ok())
+                .unwrap_or(0)
+        };
+        let total = number("total");
+        let pending = number("pending");
+        let failed = number("failed");
+
+        Ok(Some(BatchStatus {
+            id: id.to_string(),
+            name: raw.get("name").cloned().unwrap_or_default(),
+            total_jobs: total,
+            pending_jobs: pending,
+            failed_jobs: failed,
+            processed_jobs: total - pending,
+            finished: pending <= 0,
+            created_at: number("created_at"),
+            finished_at: raw.get("finished_at").and_then(|v| v.parse().ok()),
+        }))
+    }
+}
+
+/// Snapshot of a batch as stored in Redis. Timestamps are unix seconds.
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchStatus {
+    pub id: String,
+    pub name: String,
+    pub total_jobs: i64,
+    pub pending_jobs: i64,
+    pub failed_jobs: i64,
+    pub processed_jobs: i64,
+    pub finished: bool,
+    pub created_at: i64,
+    pub finished_at: Option<i64>,
+}
+
+/// Jobs run one after another; the next job is pushed only when the previous one succeeded.
+/// A permanent failure stops the chain.
+#[derive(Default)]
+pub struct JobChain {
+    jobs: Vec<JobPayload>,
+}
+
+impl JobChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append the next job of the chain.
+    pub fn push<J: Job>(mut self, job: &J) -> anyhow::Result<Self> {
+        self.jobs.push(JobPayload::from_job(job)?);
+        Ok(self)
+    }
+
+    /// Push the first job; the rest travel inside its payload.
+    pub async fn dispatch<Q: QueueDriver + ?Sized>(self, queue: &Q) -> anyhow::Result<()> {
+        let mut jobs = self.jobs.into_iter();
+        let Some(mut head) = jobs.next() else {
+            return Ok(());
+        };
+        head.chain = jobs.collect();
+        queue.push_payload(&head).await?;
+        Ok(())
+    }
+}
+
+/// Push the next link of a chain after `payload` succeeded.
+pub(crate) async fn continue_chain(
+    queue: &dyn crate::driver::QueueDriver,
+    payload: &JobPayload,
+) -> anyhow::Result<()> {
+    let mut rest = payload.chain.clone().into_iter();
+    if let Some(mut next) = rest.next() {
+        next.chain = rest.collect();
+        queue.push_payload(&next).await?;
+    }
+    Ok(())
+}
+
+/// Update batch counters in `store` after a member job succeeded or permanently failed,
+/// and push the callbacks that became due into `queue`.
+pub(crate) async fn record_outcome<Q: QueueDriver + ?Sized>(
+    store: &RedisQueue,
+    conn: &mut redis::aio::MultiplexedConnection,
+    queue: &Q,
+    batch_id: &str,
+    succeeded: bool,
+) -> anyhow::Result<()> {
+    let key = store.batch_key(batch_id);
+    let exists: bool = conn.exists(&key).await?;
+    if !exists {
+        tracing::warn!(
+            "Batch {} not found (expired?), skipping bookkeeping",
+            batch_id
+        );
+        return Ok(());
+    }
+
+    if !succeeded {
+        let failed: i64 = conn.hincr(&key, "failed", 1).await?;
+        if failed == 1 {
+            push_callback(queue, conn, &key, "catch").await?;
+        }
+    }
+
+    let pending: i64 = conn.hincr(&key, "pending", -1).await?;
+    if pending == 0 {
+        finish_batch(queue, conn, &key).await?;
+    }
+    Ok(())
+}
+
+async fn finish_batch<Q: QueueDriver + ?Sized>(
+    queue: &Q,
+    conn: &mut redis::aio::MultiplexedConnection,
+    key: &str,
+) -> anyhow::Result<()> {
+    let _: () = conn.hset(key, "finished_at", now_unix()).await?;
+    let failed: i64 = conn
+        .hget::<_, _, Option<i64>>(key, "failed")
+        .await?
+        .unwrap_or(0);
+    if failed == 0 {
+        push_callback(queue, conn, key, "then").await?;
+    }
+    push_callback(queue, conn, key, "finally").await
+}
+
+async fn push_callback<Q: QueueDriver + ?Sized>(
+    queue: &Q,
+    conn: &mut redis::aio::MultiplexedConnection,
+    key: &str,
+    field: &str,
+) -> anyhow::Result<()> {
+    let raw: Option<String> = conn.hget(key, field).await?;
+    if let Some(raw) = raw {
+        let payload: JobPayload = serde_json::from_str(&raw)?;
+        tracing::info!(
+            "Batch {}: dispatching {} callback {}",
+            key,
+            field,
+            payload.job
+        );
+        queue.push_payload(&payload).await?;
+    }
+    Ok(())
+}
+
+fn now_unix() -> i64 {
+    time::OffsetDateTime::now_utc().unix_timestamp()
+}
@@ -1,8 +1,9 @@
-use crate::{queue::RedisQueue, Job, JobPayload};
+use crate::{driver::QueueDriver, Job, JobPayload};
 use core_db::{
     common::sql::{DbConn, Op, OrderDir},
     generated::models::{OutboxJobCol, OutboxJobModel},
 };
+use time::OffsetDateTime;
 
 /// Transactional Job Buffer (Outbox Pattern).
 /// Jobs are inserted into `outbox_jobs` table within the provided DbConn scope.
@@ -17,17 +18,37 @@ impl<'a> JobBuffer<'a> {
 
     /// Push a job to the outbox (Postgres).
     pub async fn push<J: Job>(&mut self, job: J) -> anyhow::Result<()> {
-        let payload = JobPayload {
-            job: J::NAME.to_string(),
-            data: serde_json::to_value(job)?,
-            queue: J::QUEUE.to_string(),
-            attempts: 0,
-        };
+        self.insert(&job, None).await
+    }
+
+    /// Push a job to the outbox that becomes available at `at`.
+    /// The flusher schedules it on the queue driver once the transaction commits.
+    pub async fn push_at<J: Job>(&mut self, job: J, at: OffsetDateTime) -> anyhow::Result<()> {
+        self.insert(&job, Some(at)).await
+    }
+
+    /// Push a job to the outbox that becomes available after `delay`.
+    pub async fn push_after<J: Job>(
+        &mut self,
+        job: J,
+        delay: std::time::Duration,
+    ) -> anyhow::Result<()> {
+        self.insert(&job, Some(OffsetDateTime::now_utc() + delay))
+            .await
+    }
+
+    async fn insert<J: Job>(
+        &mut self,
+        job: &J,
+        available_at: Option<OffsetDateTime>,
+    ) -> anyhow::Result<()> {
+        let payload = JobPayload::from_job(job)?;
         let payload_json = serde_json::to_value(payload)?;
 
         OutboxJobModel::create()
             .set(OutboxJobCol::QUEUE, J::QUEUE.to_string())?
             .set(OutboxJobCol::PAYLOAD, payload_json)?
+            .set(OutboxJobCol::AVAILABLE_AT, available_at)?
             .save(self.db.clone())
             .await?;
 
@@ -35,16 +56,21 @@ impl<'a> JobBuffer<'a> {
     }
 }
 
-/// Helper to flush the outbox to Redis.
+/// Helper to flush the outbox into a queue driver.
 /// Should be called after transaction commit, or by a background cron.
 pub struct OutboxFlusher;
 
 impl OutboxFlusher {
-    pub async fn flush(db: &sqlx::PgPool, queue: &RedisQueue) -> anyhow::Result<usize> {
+    /// Push up to 100 committed outbox rows through `queue` and delete them.
+    /// Rows pushed before a failed push are still deleted; the rest stay for the next flush.
+    pub async fn flush<Q: QueueDriver + ?Sized>(
+        db: &sqlx::PgPool,
+        queue: &Q,
+    ) -> anyhow::Result<usize> {
         let pool_conn = DbConn::pool(db);
         let scope = pool_conn.begin_scope().await?;
 
-        let count = {
+        let (count, failure) = {
             let conn = scope.conn();
             let rows = OutboxJobModel::query()
                 .order_by(OutboxJobCol::CREATED_AT, OrderDir::Asc)
@@ -53,35 +79,37 @@ impl OutboxFlusher {
                 .all(conn.clone())
                 .await?;
 
-            if rows.is_empty() {
-                0usize
-            } else {
-                let mut count = 0usize;
-                let mut pipe = redis::pipe();
-                let mut redis_conn = queue.client.get_multiplexed_async_connection().await?;
-                let mut ids = Vec::with_capacity(rows.len());
-
-                for row in rows {
-                    let payload_str = serde_json::to_string(&row.payload)?;
-                    let target_queue = format!("{}:{}", queue.prefix, row.queue);
-                    pipe.rpush(target_queue, payload_str);
-                    ids.push(row.id);
-                    count += 1;
-                }
-
-                let _: () = pipe.query_async(&mut redis_conn).await?;
-
-                for id in ids {
-                    OutboxJobModel::query()
-                        .where_col(OutboxJobCol::ID, Op::Eq, id)
-                        .delete(conn.clone())
-                        .await?;
+            let mut count = 0usize;
+            let mut failure = None;
+            for row in rows {
+                let pushed = match serde_json::from_value::<JobPayload>(row.payload) {
+                    Ok(payload) => match row.available_at {
+                        Some(at) => queue.schedule_payload(&payload, at).await,
+                        None => queue.push_payload(&payload).await,
+                    },
+                    Err(e) => Err(e.into()),
+                };
+                match pushed {
+                    Ok(true) => count += 1,
+                    // Duplicate of a pending/running unique job: drop the row without pushing.
+                    Ok(false) => {}
+                    Err(e) => {
+                        failure = Some(e);
+                        break;
+                    }
                 }
-                count
+                OutboxJobModel::query()
+                    .where_col(OutboxJobCol::ID, Op::Eq, row.id)
+                    .delete(conn.clone())
+                    .await?;
             }
+            (count, failure)
         };
 
         scope.commit().await?;
-        Ok(count)
+        match failure {
+            Some(e) => Err(e),
+            None => Ok(count),
+        }
     }
 }
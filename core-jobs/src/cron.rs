@@ -1,16 +1,60 @@
 #![allow(dead_code)] // For unused code during development
 use crate::{queue::RedisQueue, Job};
-use chrono::Timelike; // Needed for with_nanosecond
+use chrono::{DateTime, FixedOffset, Utc};
 use cron::Schedule as CronSchedule;
 use redis::AsyncCommands;
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
 use tokio::time::Duration;
 
+/// Lifetime of the scheduler leader lease; renewed every `LEASE_RENEW`.
+const LEASE_TTL: Duration = Duration::from_secs(15);
+const LEASE_RENEW: Duration = Duration::from_secs(5);
+/// A new leader resumes from the previous leader's cursor if it is at most this old,
+/// so a handover does not skip runs (per-run locks prevent double fires).
+const HANDOVER_WINDOW_SECS: i64 = 120;
+/// Upper bound when counting missed runs for catch-up.
+const MAX_MISSED_SCAN: usize = 10_000;
+/// Wait before retrying a run whose enqueue failed.
+const FIRE_RETRY: Duration = Duration::from_secs(5);
+
+/// Take the lease if free, or extend it if we already hold it. Returns 1 when held.
+static LEASE_SCRIPT: LazyLock<redis::Script> = LazyLock::new(|| {
+    redis::Script::new(
+        r#"
+local holder = redis.call('GET', KEYS[1])
+if holder == ARGV[1] then
+    redis.call('PEXPIRE', KEYS[1], ARGV[2])
+    return 1
+end
+if not holder then
+    redis.call('SET', KEYS[1], ARGV[1], 'PX', ARGV[2])
+    return 1
+end
+return 0
+"#,
+    )
+});
+
+/// Drop the lease only if we still hold it.
+static RELEASE_SCRIPT: LazyLock<redis::Script> = LazyLock::new(|| {
+    redis::Script::new(
+        r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+"#,
+    )
+});
+
 #[derive(Clone)]
 pub struct Scheduler {
     redis: RedisQueue,
+    driver: Option<Arc<dyn crate::driver::QueueDriver>>, // None: push into `redis`
+    locks: Option<sqlx::PgPool>,                         // None: lease and locks in `redis`
     tasks: Vec<Task>,
+    timezone: time::UtcOffset,
 }
 
 #[derive(Clone)]
@@ -20,12 +64,79 @@ struct Task {
     job_factory: Arc<dyn Fn() -> Box<dyn JobWrapper> + Send + Sync>,
     condition: Option<Arc<dyn Fn() -> bool + Send + Sync>>,
     without_overlapping_ttl: Option<usize>, // TTL in seconds
+    catch_up: bool,
+    timezone: Option<time::UtcOffset>, // None: scheduler default
+}
+
+impl Task {
+    /// Stable identity used for run locks and the last-run record.
+    fn id(&self) -> String {
+        format!("{}:{}", self.name, self.expression)
+    }
+}
+
+/// A parsed task with its next fire time (UTC).
+struct Entry<'a> {
+    task: &'a Task,
+    schedule: CronSchedule,
+    timezone: FixedOffset,
+    next: Option<DateTime<Utc>>,
+    /// Set after a failed enqueue of `next`: try that run again at this time.
+    retry_at: Option<DateTime<Utc>>,
+}
+
+impl Entry<'_> {
+    /// Next run after firing `due` at `now`, skipping occurrences that passed while we were late.
+    fn advance(&self, due: DateTime<Utc>, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self.after(due) {
+            Some(n) if n <= now => self.after(now),
+            other => other,
+        }
+    }
+
+    /// Runs missed between `last_run` and `start`: how many, and the latest one.
+    fn missed(
+        &self,
+        last_run: DateTime<Utc>,
+        start: DateTime<Utc>,
+    ) -> (usize, Option<DateTime<Utc>>) {
+        self.occurrences(last_run)
+            .take_while(|t| *t < start)
+            .take(MAX_MISSED_SCAN)
+            .fold((0, None), |(count, _), t| (count + 1, Some(t)))
+    }
+
+    /// First occurrence strictly after `t`, evaluated in the task's timezone.
+    fn after(&self, t: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.occurrences(t).next()
+    }
+
+    fn occurrences(&self, t: DateTime<Utc>) -> impl Iterator<Item = DateTime<Utc>> + '_ {
+        self.schedule
+            .after(&t.with_timezone(&self.timezone))
+            .map(|at| at.with_timezone(&Utc))
+    }
+}
+
+/// Registered task as shown by `schedule list`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ScheduledTask {
+    pub name: String,
+    pub expression: String,
+    /// UTC offset the expression is evaluated in (e.g. `+08:00`).
+    pub timezone: String,
+    /// Next run in the task's timezone; `None` if the expression is invalid or never fires again.
+    pub next_run: Option<time::OffsetDateTime>,
+    pub without_overlapping: Option<usize>,
+    pub catch_up: bool,
+    pub conditional: bool,
 }
 
 trait JobWrapper: Send + Sync {
     fn name(&self) -> &'static str;
     fn queue_name(&self) -> &'static str;
     fn to_json(&self) -> String;
+    fn payload(&self) -> anyhow::Result<crate::JobPayload>;
 }
 
 impl<J: Job> JobWrapper for J {
@@ -38,32 +149,203 @@ impl<J: Job> JobWrapper for J {
     fn to_json(&self) -> String {
         serde_json::to_string(self).unwrap()
     }
+    fn payload(&self) -> anyhow::Result<crate::JobPayload> {
+        crate::JobPayload::from_job(self)
+    }
 }
 
 impl Scheduler {
     pub fn new(queue: RedisQueue) -> Self {
         Self {
             redis: queue,
+            driver: None,
+            locks: None,
             tasks: Vec::new(),
+            timezone: time::UtcOffset::UTC,
         }
     }
 
+    /// Enqueue fired tasks into `driver` (e.g. `PgQueue`) instead of Redis.
+    /// The leader lease and run locks stay in Redis unless `use_postgres_locks` is set.
+    pub fn dispatch_to(&mut self, driver: Arc<dyn crate::driver::QueueDriver>) -> &mut Self {
+        self.driver = Some(driver);
+        self
+    }
+
+    /// Keep the leader lease (a session advisory lock), run locks, cursor and last runs in
+    /// Postgres (`cron_state` table) instead of Redis. The leader holds one `db` connection.
+    pub fn use_postgres_locks(&mut self, db: sqlx::PgPool) -> &mut Self {
+        self.locks = Some(db);
+        self
+    }
+
+    /// Default timezone of all tasks (bootstrap uses `[app] timezone`). Default UTC.
+    pub fn set_timezone(&mut self, offset: time::UtcOffset) -> &mut Self {
+        self.timezone = offset;
+        self
+    }
+
     /// Schedule a job with a cron expression.
     /// Example: "0 * * * * *" (Every minute)
     pub fn cron<J: Job + Default + Clone>(&mut self, expression: &str) -> &mut Self {
-        let expression = expression.to_string();
-        let job_factory = Arc::new(|| Box::new(J::default()) as Box<dyn JobWrapper>);
+        self.push_task(J::NAME, expression, || J::default())
+    }
+
+    /// Schedule a concrete job instance (with its payload). Runs every minute until a
+    /// frequency helper (`daily_at`, `every_minutes`, `cron_expression`, ...) is chained.
+    pub fn job<J: Job + Clone>(&mut self, job: J) -> &mut Self {
+        self.push_task(J::NAME, "0 * * * * *", move || job.clone())
+    }
 
+    fn push_task<J, F>(&mut self, name: &str, expression: &str, factory: F) -> &mut Self
+    where
+        J: Job,
+        F: Fn() -> J + Send + Sync + 'static,
+    {
         self.tasks.push(Task {
-            name: J::NAME.to_string(),
-            expression,
-            job_factory,
+            name: name.to_string(),
+            expression: expression.to_string(),
+            job_factory: Arc::new(move || Box::new(factory()) as Box<dyn JobWrapper>),
             condition: None,
             without_overlapping_ttl: None,
+            catch_up: false,
+            timezone: None,
         });
         self
     }
 
+    /// Replace the cron expression of the last scheduled job.
+    pub fn cron_expression(&mut self, expression: &str) -> &mut Self {
+        if let Some(task) = self.tasks.last_mut() {
+            task.expression = expression.to_string();
+        }
+        self
+    }
+
+    /// Every `n` seconds, aligned to the minute (`n` should divide 60).
+    pub fn every_seconds(&mut self, n: u32) -> &mut Self {
+        self.cron_expression(&format!("*/{} * * * * *", n.max(1)))
+    }
+
+    pub fn every_minute(&mut self) -> &mut Self {
+        self.cron_expression("0 * * * * *")
+    }
+
+    /// Every `n` minutes, aligned to the hour (`n` should divide 60).
+    pub fn every_minutes(&mut self, n: u32) -> &mut Self {
+        self.cron_expression(&format!("0 */{} * * * *", n.max(1)))
+    }
+
+    pub fn hourly(&mut self) -> &mut Self {
+        self.cron_expression("0 0 * * * *")
+    }
+
+    /// Every hour at `minute` past.
+    pub fn hourly_at(&mut self, minute: u32) -> &mut Self {
+        self.cron_expression(&format!("0 {} * * * *", minute))
+    }
+
+    pub fn daily(&mut self) -> &mut Self {
+        self.cron_expression("0 0 0 * * *")
+    }
+
+    /// Every day at `time` (`"HH:MM"` or `"HH:MM:SS"`).
+    ///
+    /// # Panics
+    /// If `time` is not a valid time of day.
+    pub fn daily_at(&mut self, time: &str) -> &mut Self {
+        let (h, m, s) = parse_time_of_day(time);
+        self.cron_expression(&format!("{} {} {} * * *", s, m, h))
+    }
+
+    /// Every week on `day` at `time` (`"HH:MM"` or `"HH:MM:SS"`).
+    ///
+    /// # Panics
+    /// If `time` is not a valid time of day.
+    pub fn weekly_on(&mut self, day: time::Weekday, time: &str) -> &mut Self {
+        let (h, m, s) = parse_time_of_day(time);
+        let day = &format!("{}", day)[..3];
+        self.cron_expression(&format!("{} {} {} * * {}", s, m, h, day))
+    }
+
+    /// Every month on `day` (1-31) at `time` (`"HH:MM"` or `"HH:MM:SS"`).
+    ///
+    /// # Panics
+    /// If `time` is not a valid time of day.
+    pub fn monthly_on(&mut self, day: u32, time: &str) -> &mut Self {
+        let (h, m, s) = parse_time_of_day(time);
+        self.cron_expression(&format!("{} {} {} {} * *", s, m, h, day))
+    }
+
+    /// Evaluate the last scheduled job in this UTC offset (`"+08:00"` format, like `[app] timezone`).
+    ///
+    /// # Panics
+    /// If `offset` is not in `±HH:MM` format.
+    pub fn timezone(&mut self, offset: &str) -> &mut Self {
+        let offset = core_i18n::config::I18nSettings::parse_utc_offset(offset)
+            .unwrap_or_else(|e| panic!("Invalid schedule timezone {:?}: {}", offset, e));
+        if let Some(task) = self.tasks.last_mut() {
+            task.timezone = Some(offset);
+        }
+        self
+    }
+
+    /// Name of the last scheduled job in logs, locks and `schedule list`.
+    /// Required to tell apart several instances of the same job on the same schedule.
+    pub fn name(&mut self, name: &str) -> &mut Self {
+        if let Some(task) = self.tasks.last_mut() {
+            task.name = name.to_string();
+        }
+        self
+    }
+
+    /// Registered tasks with their next run time.
+    pub fn list(&self) -> Vec<ScheduledTask> {
+        let now = Utc::now();
+        self.tasks
+            .iter()
+            .map(|task| {
+                let offset = task.timezone.unwrap_or(self.timezone);
+                let next_run = self
+                    .entry(task)
+                    .and_then(|entry| entry.after(now))
+                    .and_then(|at| {
+                        time::OffsetDateTime::from_unix_timestamp(at.timestamp())
+                            .ok()
+                            .map(|at| at.to_offset(offset))
+                    });
+                ScheduledTask {
+                    name: task.name.clone(),
+                    expression: task.expression.clone(),
+                    timezone: format_offset(offset),
+                    next_run,
+                    without_overlapping: task.without_overlapping_ttl,
+                    catch_up: task.catch_up,
+                    conditional: task.condition.is_some(),
+                }
+            })
+            .collect()
+    }
+
+    fn entry<'a>(&self, task: &'a Task) -> Option<Entry<'a>> {
+        let schedule = match CronSchedule::from_str(&task.expression) {
+            Ok(schedule) => schedule,
+            Err(e) => {
+                tracing::error!("Invalid cron expression for {}: {}", task.name, e);
+                return None;
+            }
+        };
+        let offset = task.timezone.unwrap_or(self.timezone);
+        let timezone = FixedOffset::east_opt(offset.whole_seconds())?;
+        Some(Entry {
+            task,
+            schedule,
+            timezone,
+            next: None,
+            retry_at: None,
+        })
+    }
+
     /// Add a condition to the last scheduled job.
     pub fn when<F>(&mut self, f: F) -> &mut Self
     where
@@ -85,152 +367,559 @@ impl Scheduler {
         self
     }
 
-    /// Schedule a job to run every N seconds (Approximation via cron?).
-    /// Cron doesn't support "Every 45 seconds" effectively if not aligned.
-    /// For "Every N minutes", cron works.
-    /// For simple interval, we might need custom logic.
-    /// For now, let's stick to CRON syntax as primary.
+    /// Fire the last scheduled job once on startup if runs were missed while no
+    /// scheduler was running (e.g. during a deploy). Missed runs are coalesced into one.
+    pub fn catch_up(&mut self) -> &mut Self {
+        if let Some(task) = self.tasks.last_mut() {
+            task.catch_up = true;
+        }
+        self
+    }
 
     pub async fn run(self) -> anyhow::Result<()> {
-        let mut interval = tokio::time::interval(Duration::from_secs(60));
-        let client = self.redis.client.clone();
-        let mut conn = client.get_multiplexed_async_connection().await?;
+        self.run_until(tokio_util::sync::CancellationToken::new())
+            .await
+    }
+
+    /// Run until `shutdown` is cancelled.
+    ///
+    /// Sleeps until the next due task (second resolution). When several processes run a
+    /// scheduler, only the holder of the `{prefix}:cron:leader` lease fires tasks; the
+    /// others stand by and take over if the leader disappears.
+    pub async fn run_until(
+        self,
+        shutdown: tokio_util::sync::CancellationToken,
+    ) -> anyhow::Result<()> {
+        let mut locks = match &self.locks {
+            Some(db) => Locks::Postgres {
+                db: db.clone(),
+                lease: None,
+            },
+            None => Locks::Redis {
+                conn: self.redis.client.get_multiplexed_async_connection().await?,
+                instance: uuid::Uuid::new_v4().to_string(),
+            },
+        };
         let prefix = self.redis.prefix.clone();
+        let leader_key = format!("{}:cron:leader", prefix);
 
+        let mut entries: Vec<Entry> = self
+            .tasks
+            .iter()
+            .filter_map(|task| self.entry(task))
+            .collect();
+
+        let mut leader = false;
         loop {
-            interval.tick().await;
-            let now = chrono::Utc::now();
-            tracing::info!("Scheduler Tick: {}", now);
+            let held = locks.hold_lease(&leader_key).await;
 
-            for task in &self.tasks {
-                let schedule = match CronSchedule::from_str(&task.expression) {
-                    Ok(s) => s,
-                    Err(e) => {
-                        tracing::error!("Invalid cron expression for {}: {}", task.name, e);
-                        continue; // Skip
-                    }
-                };
+            if held && !leader {
+                tracing::info!("Cron scheduler is leader");
+                self.resume(&mut locks, &prefix, &mut entries).await;
+            } else if !held && leader {
+                tracing::warn!("Cron scheduler lost leadership");
+            }
+            leader = held;
 
-                // Check if we should have run recently
-                // Logic: Find the *previous* scheduled time. If it matches current minute (ignoring seconds), run it.
-                // Or better: Is there an upcoming run in this minute?
-                // `upcoming(Utc).next()` gives next run.
-                // If `next_run` is close to `now`?
-
-                // Typical Cron Loop Logic:
-                // Wake up every minute (at :00).
-                // Check if task matches this minute.
-                // Cron crate `parse` gives explicit dates.
-                // Does `schedule.includes(now)` exist? No.
-                // `schedule.upcoming(Utc)`
-
-                // Alternative:
-                // Get the last run time?
-                // Just check if `upcoming` is effectively `now` (we just ticked).
-                // Or check if `schedule.after(now - 1 min).next() == now`?
-
-                // Simpler check:
-                // Is `now` inside the set of matching times?
-                // `cron` crate doesn't easily return "is match".
-                // But we can iterate `upcoming` from `now - 1 minute`.
-                // If yield `dt` such that `dt.minute() == now.minute()`, then yes.
-
-                // Let's rely on lock to deduplicate anyway.
-                // Lock Key: `scheduler:run:{job_name}:{YYYY-MM-DD-HH-MM}`
-
-                // Check if current minute is a match.
-                // We construct a date iterator starting from slightly in past?
-                // `schedule.after(&last_tick)`?
-
-                // Let's assume we run exactly on minute boundary.
-                // Find nearest upcoming event.
-                // Check if current minute is a match.
-                // We rely on cron iteration to see if the "current minute" (00 seconds) matches the schedule.
-                let last_minute = now.with_nanosecond(0).unwrap().with_second(0).unwrap();
-                // Check if `last_minute` is in the schedule.
-                // Hack: `schedule.after(last_minute - 1ns).next()` == `last_minute`?
-
-                if let Some(upcoming) = schedule
-                    .after(&(last_minute - chrono::Duration::seconds(1)))
-                    .next()
-                {
-                    if upcoming == last_minute {
-                        // 1. Check Condition
-                        if let Some(cond) = &task.condition {
-                            if !cond() {
-                                tracing::info!("Skipping {}: Condition false", task.name);
-                                continue;
+            let mut wake = Utc::now() + LEASE_RENEW;
+            if leader {
+                let now = Utc::now();
+                for entry in entries.iter_mut() {
+                    let Some(due) = entry.next else { continue };
+                    if due <= now && entry.retry_at.is_none_or(|at| at <= now) {
+                        entry.retry_at = None;
+                        match self.fire(&mut locks, &prefix, entry.task, due).await {
+                            Ok(()) => entry.next = entry.advance(due, now),
+                            Err(e) => {
+                                tracing::error!(
+                                    "Scheduled task {} ({}) failed to enqueue: {:#}",
+                                    entry.task.name,
+                                    due,
+                                    e
+                                );
+                                // Retry until the following run is due, then move on.
+                                match entry.after(due) {
+                                    Some(n) if n <= now => entry.next = entry.advance(due, now),
+                                    _ => entry.retry_at = Some(now + FIRE_RETRY),
+                                }
                             }
                         }
+                    }
+                    if let Some(n) = entry.retry_at.or(entry.next) {
+                        wake = wake.min(n);
+                    }
+                }
+                locks
+                    .set(&format!("{}:cron:cursor", prefix), now.timestamp())
+                    .await;
+            }
 
-                        // 2. Check Overlapping Lock (if configured)
-                        if let Some(ttl) = task.without_overlapping_ttl {
-                            let overlap_key = format!("{}:cron:overlap:{}", prefix, task.name);
-                            // Check if exists
-                            let exists: bool = redis::cmd("EXISTS")
-                                .arg(&overlap_key)
-                                .query_async(&mut conn)
-                                .await
-                                .unwrap_or(false);
-                            if exists {
-                                tracing::info!("Skipping {}: Overlap lock exists", task.name);
-                                continue;
-                            }
+            let sleep = (wake - Utc::now()).to_std().unwrap_or(Duration::ZERO);
+            tokio::select! {
+                _ = tokio::time::sleep(sleep) => {}
+                _ = shutdown.cancelled() => {
+                    if leader {
+                        locks.release_lease(&leader_key).await;
+                    }
+                    tracing::info!("Cron scheduler stopped");
+                    return Ok(());
+                }
+            }
+        }
+    }
 
-                            // Set Overlap Lock
-                            let _: () = redis::cmd("SET")
-                                .arg(&overlap_key)
-                                .arg("1")
-                                .arg("EX")
-                                .arg(ttl)
-                                .query_async(&mut conn)
-                                .await
-                                .unwrap_or(());
-                        }
+    /// Compute each task's next run after becoming leader: continue from the previous
+    /// leader's cursor, and fire catch-up tasks that missed runs since their last fire.
+    async fn resume(&self, locks: &mut Locks, prefix: &str, entries: &mut [Entry<'_>]) {
+        let now = Utc::now();
+        let cursor = locks.get(&format!("{}:cron:cursor", prefix)).await;
+        let start = resume_start(cursor, now);
 
-                        // IT IS TIME.
-                        let lock_key =
-                            format!("{}:cron:{}:{}", prefix, task.name, last_minute.timestamp());
-
-                        // Try Lock (Deduplication)
-                        let is_locked: bool = redis::cmd("SET")
-                            .arg(&lock_key)
-                            .arg("1")
-                            .arg("NX")
-                            .arg("EX")
-                            .arg(120) // 2 mins expire
-                            .query_async(&mut conn)
-                            .await
-                            .unwrap_or(false);
-
-                        if is_locked {
-                            tracing::info!("Enqueueing Scheduled Task: {}", task.name);
-                            // Enqueue Job
-                            let job_instance = (task.job_factory)();
-                            // Access queue methods directly?
-                            // Need `push_raw`? Or reconstruct job wrapper?
-                            // `RedisQueue::push` expects `&J`.
-                            // I only have `Box<dyn JobWrapper>`.
-                            // `JobWrapper` has `to_json()`.
-                            // I can manually push the payload using `to_json`.
-                            // `JobPayload` struct? I need to use `JobPayload` to be compatible with Worker.
-
-                            // Let's expose `push_raw` in `RedisQueue` or `push_wrapper`.
-                            // Or use `to_json` to get inner data, then wrap in `JobPayload`.
-
-                            let payload = crate::JobPayload {
-                                job: task.name.clone(),
-                                data: serde_json::from_str(&job_instance.to_json())?, // Reparse? Inefficient but safe.
-                                queue: job_instance.queue_name().to_string(),
-                                attempts: 0,
-                            };
-                            let payload_str = serde_json::to_string(&payload)?;
-                            let queue_key = format!("{}:{}", prefix, job_instance.queue_name());
-                            let _: () = conn.rpush(queue_key, payload_str).await?;
-                        }
+        for entry in entries.iter_mut() {
+            entry.next = entry.after(start);
+            let task = entry.task;
+
+            if !task.catch_up {
+                continue;
+            }
+            let last_run = locks
+                .last_run(&format!("{}:cron:last_run", prefix), &task.id())
+                .await;
+            let Some(last_run) = last_run.and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0))
+            else {
+                continue;
+            };
+            if let (missed, Some(latest)) = entry.missed(last_run, start) {
+                tracing::info!(
+                    "Catching up {}: {} missed run(s) since {}",
+                    task.name,
+                    missed,
+                    last_run
+                );
+                if let Err(e) = self.fire(locks, prefix, task, latest).await {
+                    tracing::error!("Catch-up of {} failed: {}", task.name, e);
+                }
+            }
+        }
+    }
+
+    /// Enqueue one run of `task` scheduled for `at`. When the push fails its locks
+    /// are released, so the run can be retried.
+    async fn fire(
+        &self,
+        locks: &mut Locks,
+        prefix: &str,
+        task: &Task,
+        at: DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        // 1. Check Condition
+        if let Some(cond) = &task.condition {
+            if !cond() {
+                tracing::info!("Skipping {}: Condition false", task.name);
+                return Ok(());
+            }
+        }
+
+        // 2. Per-run lock (deduplicates across leader handovers). Taken before the
+        // overlap lock, so a duplicate fire never holds the overlap key.
+        let mut held = vec![format!("{}:cron:{}:{}", prefix, task.id(), at.timestamp())];
+        if !locks.claim(&held[0], HANDOVER_WINDOW_SECS as u64 * 2).await {
+            return Ok(());
+        }
+
+        // 3. Check Overlapping Lock (if configured)
+        if let Some(ttl) = task.without_overlapping_ttl {
+            let overlap_key = format!("{}:cron:overlap:{}", prefix, task.name);
+            if !locks.claim(&overlap_key, ttl as u64).await {
+                tracing::info!("Skipping {}: Overlap lock exists", task.name);
+                return Ok(());
+            }
+            held.push(overlap_key);
+        }
+
+        tracing::info!("Enqueueing Scheduled Task: {} ({})", task.name, at);
+        let pushed = match self.enqueue(task).await {
+            Ok(pushed) => pushed,
+            Err(e) => {
+                if let Err(release) = locks.release(&held).await {
+                    tracing::warn!("Failed to release locks of {}: {}", task.name, release);
+                }
+                return Err(e);
+            }
+        };
+        if !pushed {
+            tracing::info!("Skipping {}: unique job still pending", task.name);
+        }
+        locks
+            .set_last_run(
+                &format!("{}:cron:last_run", prefix),
+                &task.id(),
+                at.timestamp(),
+            )
+            .await;
+        Ok(())
+    }
+
+    async fn enqueue(&self, task: &Task) -> anyhow::Result<bool> {
+        let payload = (task.job_factory)().payload()?;
+        match &self.driver {
+            Some(driver) => driver.push_payload(&payload).await,
+            None => self.redis.push_payload(&payload).await,
+        }
+    }
+}
+
+/// Where a running scheduler keeps its leader lease, run locks, cursor and last runs.
+enum Locks {
+    Redis {
+        conn: redis::aio::MultiplexedConnection,
+        /// Lease holder id.
+        instance: String,
+    },
+    /// `cron_state` rows; the lease is a session advisory lock on `lease`.
+    Postgres {
+        db: sqlx::PgPool,
+        lease: Option<sqlx::pool::PoolConnection<sqlx::Postgres>>,
+    },
+}
+
+impl Locks {
+    /// Take the lease if free, or keep it if we already hold it. Returns true when held.
+    async fn hold_lease(&mut self, key: &str) -> bool {
+        match self {
+            Locks::Redis { conn, instance } => LEASE_SCRIPT
+                .key(key)
+                .arg(instance.as_str())
+                .arg(LEASE_TTL.as_millis() as u64)
+                .invoke_async(conn)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::error!("Scheduler lease error: {}", e);
+                    false
+                }),
+            Locks::Postgres { db, lease } => {
+                if let Some(conn) = lease {
+                    if sqlx::query("SELECT 1").execute(&mut **conn).await.is_ok() {
+                        return true;
+                    }
+                    // Close the session so the advisory lock goes with it.
+                    tracing::error!("Scheduler lease connection lost");
+                    if let Some(conn) = lease.take() {
+                        drop(conn.detach());
+                    }
+                }
+                let acquired = async {
+                    let mut conn = db.acquire().await?;
+                    let locked: bool =
+                        sqlx::query_scalar("SELECT pg_try_advisory_lock(hashtext($1))")
+                            .bind(key)
+                            .fetch_one(&mut *conn)
+                            .await?;
+                    Ok::<_, sqlx::Error>(locked.then_some(conn))
+                };
+                match acquired.await {
+                    Ok(conn) => {
+                        *lease = conn;
+                        lease.is_some()
+                    }
+                    Err(e) => {
+                        tracing::error!("Scheduler lease error: {}", e);
+                        false
                     }
                 }
             }
         }
     }
+
+    async fn release_lease(&mut self, key: &str) {
+        match self {
+            Locks::Redis { conn, instance } => {
+                let _: redis::RedisResult<i64> = RELEASE_SCRIPT
+                    .key(key)
+                    .arg(instance.as_str())
+                    .invoke_async(conn)
+                    .await;
+            }
+            Locks::Postgres { lease, .. } => {
+                if let Some(mut conn) = lease.take() {
+                    let unlocked = sqlx::query("SELECT pg_advisory_unlock(hashtext($1))")
+                        .bind(key)
+                        .execute(&mut *conn)
+                        .await;
+                    if unlocked.is_err() {
+                        drop(conn.detach());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Take `key` for `ttl` seconds unless someone else holds it. Returns true when taken.
+    async fn claim(&mut self, key: &str, ttl: u64) -> bool {
+        match self {
+            Locks::Redis { conn, .. } => redis::cmd("SET")
+                .arg(key)
+                .arg("1")
+                .arg("NX")
+                .arg("EX")
+                .arg(ttl)
+                .query_async::<Option<String>>(conn)
+                .await
+                .map(|reply| reply.is_some())
+                .unwrap_or(false),
+            Locks::Postgres { db, .. } => sqlx::query(
+                r#"INSERT INTO cron_state (key, value, expires_at)
+                   VALUES ($1, 1, NOW() + make_interval(secs => $2))
+                   ON CONFLICT (key) DO UPDATE
+                   SET value = EXCLUDED.value, expires_at = EXCLUDED.expires_at
+                   WHERE cron_state.expires_at <= NOW()
+                   RETURNING key"#,
+            )
+            .bind(key)
+            .bind(ttl as f64)
+            .fetch_optional(&*db)
+            .await
+            .map(|row| row.is_some())
+            .unwrap_or(false),
+        }
+    }
+
+    async fn release(&mut self, keys: &[String]) -> anyhow::Result<()> {
+        match self {
+            Locks::Redis { conn, .. } => conn.del::<_, ()>(keys).await?,
+            Locks::Postgres { db, .. } => {
+                sqlx::query("DELETE FROM cron_state WHERE key = ANY($1)")
+                    .bind(keys)
+                    .execute(&*db)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn get(&mut self, key: &str) -> Option<i64> {
+        match self {
+            Locks::Redis { conn, .. } => conn.get(key).await.unwrap_or(None),
+            Locks::Postgres { db, .. } => sqlx::query_scalar(
+                r#"SELECT value FROM cron_state
+                   WHERE key = $1 AND (expires_at IS NULL OR expires_at > NOW())"#,
+            )
+            .bind(key)
+            .fetch_optional(&*db)
+            .await
+            .unwrap_or(None),
+        }
+    }
+
+    /// Store `value` under `key`. On Postgres this also prunes expired run locks.
+    async fn set(&mut self, key: &str, value: i64) {
+        match self {
+            Locks::Redis { conn, .. } => {
+                let _: () = conn.set(key, value).await.unwrap_or(());
+            }
+            Locks::Postgres { db, .. } => {
+                let _ = sqlx::query(
+                    r#"INSERT INTO cron_state (key, value) VALUES ($1, $2)
+                       ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, expires_at = NULL"#,
+                )
+                .bind(key)
+                .bind(value)
+                .execute(&*db)
+                .await;
+                let _ = sqlx::query("DELETE FROM cron_state WHERE expires_at <= NOW()")
+                    .execute(&*db)
+                    .await;
+            }
+        }
+    }
+
+    /// Last fire of task `id`: a field of the `hash` key on Redis, a `{hash}:{id}` row on Postgres.
+    async fn last_run(&mut self, hash: &str, id: &str) -> Option<i64> {
+        match self {
+            Locks::Redis { conn, .. } => conn.hget(hash, id).await.unwrap_or(None),
+            Locks::Postgres { .. } => self.get(&format!("{}:{}", hash, id)).await,
+        }
+    }
+
+    async fn set_last_run(&mut self, hash: &str, id: &str, at: i64) {
+        match self {
+            Locks::Redis { conn, .. } => {
+                let _: () = conn.hset(hash, id, at).await.unwrap_or(());
+            }
+            Locks::Postgres { .. } => self.set(&format!("{}:{}", hash, id), at).await,
+        }
+    }
+}
+
+/// Where a new leader starts: the previous leader's cursor when it is recent enough, else now.
+fn resume_start(cursor: Option<i64>, now: DateTime<Utc>) -> DateTime<Utc> {
+    cursor
+        .filter(|ts| now.timestamp() - ts <= HANDOVER_WINDOW_SECS)
+        .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0))
+        .unwrap_or(now)
+}
+
+/// Parse `"HH:MM"` / `"HH:MM:SS"` into (hour, minute, second).
+fn parse_time_of_day(value: &str) -> (u32, u32, u32) {
+    let parts: Vec<u32> = value
+        .split(':')
+        .map(|part| part.trim().parse::<u32>())
+        .collect::<Result<_, _>>()
+        .unwrap_or_default();
+    match parts.as_slice() {
+        [h, m] if *h < 24 && *m < 60 => (*h, *m, 0),
+        [h, m, s] if *h < 24 && *m < 60 && *s < 60 => (*h, *m, *s),
+        _ => panic!(
+            "Invalid schedule time {:?}: expected HH:MM or HH:MM:SS",
+            value
+        ),
+    }
+}
+
+fn format_offset(offset: time::UtcOffset) -> String {
+    let (h, m, _) = offset.as_hms();
+    let sign = if offset.is_negative() { '-' } else { '+' };
+    format!("{}{:02}:{:02}", sign, h.abs(), m.abs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_offset, parse_time_of_day, resume_start, Scheduler};
+    use crate::{queue::RedisQueue, Job, JobContext};
+    use chrono::{DateTime, TimeZone, Utc};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+    struct PruneTokens;
+
+    #[async_trait::async_trait]
+    impl Job for PruneTokens {
+        const NAME: &'static str = "PruneTokens";
+
+        async fn handle(&self, _ctx: &JobContext) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn scheduler() -> Scheduler {
+        Scheduler::new(RedisQueue::new("redis://127.0.0.1/").expect("redis url"))
+    }
+
+    fn at(h: u32, m: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 5, h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn advance_skips_runs_that_passed_while_late() {
+        let mut scheduler = scheduler();
+        scheduler.cron::<PruneTokens>("0 0 * * * *");
+        let entry = scheduler.entry(&scheduler.tasks[0]).expect("entry");
+
+        assert_eq!(entry.advance(at(10, 0), at(10, 0)), Some(at(11, 0)));
+        assert_eq!(entry.advance(at(10, 0), at(10, 30)), Some(at(11, 0)));
+        // 11:00 and 12:00 passed during the late fire of 10:00
+        assert_eq!(entry.advance(at(10, 0), at(12, 15)), Some(at(13, 0)));
+    }
+
+    #[test]
+    fn missed_counts_runs_before_the_resume_point() {
+        let mut scheduler = scheduler();
+        scheduler.cron::<PruneTokens>("0 0 * * * *");
+        let entry = scheduler.entry(&scheduler.tasks[0]).expect("entry");
+
+        assert_eq!(entry.missed(at(8, 0), at(11, 30)), (3, Some(at(11, 0))));
+        assert_eq!(entry.missed(at(8, 0), at(11, 0)), (2, Some(at(10, 0))));
+        assert_eq!(entry.missed(at(11, 0), at(11, 30)), (0, None));
+    }
+
+    #[test]
+    fn missed_runs_follow_the_task_timezone() {
+        let mut scheduler = scheduler();
+        scheduler
+            .cron::<PruneTokens>("0 0 0 * * *")
+            .daily_at("06:00")
+            .timezone("+08:00");
+        let entry = scheduler.entry(&scheduler.tasks[0]).expect("entry");
+
+        // 06:00 +08:00 is 22:00 UTC the day before
+        let (count, latest) = entry.missed(at(0, 0) - chrono::Duration::days(2), at(23, 0));
+        assert_eq!(count, 3);
+        assert_eq!(latest, Some(at(22, 0)));
+    }
+
+    #[test]
+    fn resume_start_uses_a_recent_cursor_only() {
+        let now = at(12, 0);
+        let recent = now.timestamp() - 60;
+        let stale = now.timestamp() - 121;
+
+        assert_eq!(resume_start(Some(recent), now).timestamp(), recent);
+        assert_eq!(resume_start(Some(stale), now), now);
+        assert_eq!(resume_start(None, now), now);
+    }
+
+    #[test]
+    fn parse_time_of_day_accepts_minutes_and_seconds() {
+        assert_eq!(parse_time_of_day("09:30"), (9, 30, 0));
+        assert_eq!(parse_time_of_day("23:59:58"), (23, 59, 58));
+        assert_eq!(parse_time_of_day(" 7 : 05 "), (7, 5, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid schedule time")]
+    fn parse_time_of_day_rejects_out_of_range_hour() {
+        parse_time_of_day("24:00");
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid schedule time")]
+    fn parse_time_of_day_rejects_missing_minutes() {
+        parse_time_of_day("9");
+    }
+
+    #[test]
+    fn weekly_on_builds_a_parseable_expression() {
+        let mut scheduler = scheduler();
+        scheduler
+            .cron::<PruneTokens>("0 0 0 * * *")
+            .weekly_on(time::Weekday::Monday, "09:30");
+
+        let task = &scheduler.list()[0];
+        assert_eq!(task.expression, "0 30 9 * * Mon");
+        let next = task.next_run.expect("next run");
+        assert_eq!(next.weekday(), time::Weekday::Monday);
+        assert_eq!((next.hour(), next.minute()), (9, 30));
+    }
+
+    #[test]
+    fn task_timezone_overrides_scheduler_default() {
+        let mut scheduler = scheduler();
+        scheduler.set_timezone(time::UtcOffset::from_hms(-5, -30, 0).expect("offset"));
+        scheduler.cron::<PruneTokens>("0 0 0 * * *");
+        scheduler
+            .cron::<PruneTokens>("0 0 0 * * *")
+            .daily_at("06:00")
+            .timezone("+08:00")
+            .name("prune-asia");
+
+        let tasks = scheduler.list();
+        assert_eq!(tasks[0].timezone, "-05:30");
+        assert_eq!(tasks[1].timezone, "+08:00");
+        assert_eq!(tasks[1].name, "prune-asia");
+        let next = tasks[1].next_run.expect("next run");
+        assert_eq!((next.hour(), next.minute()), (6, 0));
+    }
+
+    #[test]
+    fn format_offset_pads_and_signs() {
+        assert_eq!(format_offset(time::UtcOffset::UTC), "+00:00");
+        assert_eq!(
+            format_offset(time::UtcOffset::from_hms(8, 0, 0).expect("offset")),
+            "+08:00"
+        );
+        assert_eq!(
+            format_offset(time::UtcOffset::from_hms(-9, -30, 0).expect("offset")),
+            "-09:30"
+        );
+    }
 }
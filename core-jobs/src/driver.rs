@@ -0,0 +1,103 @@
+use crate::{events::JobEvents, postgres::PgQueue, queue::RedisQueue, JobPayload};
+use async_trait::async_trait;
+use std::sync::Arc;
+use time::OffsetDateTime;
+
+/// Storage backend jobs are dispatched into (`[worker] driver`).
+///
+/// `RedisQueue` is the default; `PgQueue` keeps jobs in the `queue_jobs` table
+/// for deployments without Redis.
+#[async_trait]
+pub trait QueueDriver: Send + Sync {
+    /// Enqueue an already-built payload for immediate processing.
+    /// Returns `false` when the payload's unique key is already held (nothing is pushed).
+    async fn push_payload(&self, payload: &JobPayload) -> anyhow::Result<bool>;
+
+    /// Schedule an already-built payload. Times in the past are enqueued immediately.
+    /// Returns `false` when the payload's unique key is already held (nothing is pushed).
+    async fn schedule_payload(
+        &self,
+        payload: &JobPayload,
+        at: OffsetDateTime,
+    ) -> anyhow::Result<bool>;
+
+    /// Where `batch::JobBatch` keeps counters for jobs pushed through this driver.
+    fn batch_store(&self) -> BatchStore<'_> {
+        BatchStore::Unsupported
+    }
+}
+
+/// Batch bookkeeping of a `QueueDriver`.
+pub enum BatchStore<'a> {
+    /// Counters in the Redis hash `{prefix}:batch:{id}`; callbacks go back through the driver.
+    Redis(&'a RedisQueue),
+    /// Member jobs are pushed with their batch id but nothing is tracked (test drivers).
+    Untracked,
+    /// `JobBatch::dispatch` fails on this driver.
+    Unsupported,
+}
+
+#[async_trait]
+impl QueueDriver for RedisQueue {
+    async fn push_payload(&self, payload: &JobPayload) -> anyhow::Result<bool> {
+        RedisQueue::push_payload(self, payload).await
+    }
+
+    async fn schedule_payload(
+        &self,
+        payload: &JobPayload,
+        at: OffsetDateTime,
+    ) -> anyhow::Result<bool> {
+        RedisQueue::schedule_payload(self, payload, at).await
+    }
+
+    fn batch_store(&self) -> BatchStore<'_> {
+        BatchStore::Redis(self)
+    }
+}
+
+#[async_trait]
+impl QueueDriver for PgQueue {
+    async fn push_payload(&self, payload: &JobPayload) -> anyhow::Result<bool> {
+        PgQueue::push_payload(self, payload).await
+    }
+
+    async fn schedule_payload(
+        &self,
+        payload: &JobPayload,
+        at: OffsetDateTime,
+    ) -> anyhow::Result<bool> {
+        PgQueue::schedule_payload(self, payload, at).await
+    }
+
+    fn batch_store(&self) -> BatchStore<'_> {
+        match &self.batch_store {
+            Some(store) => BatchStore::Redis(store),
+            None => BatchStore::Unsupported,
+        }
+    }
+}
+
+/// Build the driver selected by `[worker] driver` (`redis` or `postgres`).
+/// Every accepted push emits `Queued` into `events`.
+pub fn from_settings(
+    settings: &core_config::Settings,
+    db: sqlx::PgPool,
+    events: JobEvents,
+) -> anyhow::Result<Arc<dyn QueueDriver>> {
+    let queue_prefix = settings
+        .redis
+        .prefix
+        .as_ref()
+        .map(|prefix| format!("{prefix}:queue"))
+        .unwrap_or_else(|| "queue".to_string());
+    let redis = RedisQueue::new_with_prefix(&settings.redis.url, &queue_prefix)?;
+    match settings.worker.driver.as_str() {
+        "redis" => Ok(Arc::new(redis.with_events(events))),
+        // Batch counters stay in Redis, like throttles; the client connects on first use
+        "postgres" => Ok(Arc::new(
+            PgQueue::new(db).with_batch_store(redis).with_events(events),
+        )),
+        other => anyhow::bail!("Unknown queue driver '{other}' (expected redis or postgres)"),
+    }
+}
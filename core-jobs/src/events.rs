@@ -0,0 +1,518 @@
+use crate::{driver::QueueDriver, JobPayload};
+use async_trait::async_trait;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use time::OffsetDateTime;
+
+const RUNTIME_BUCKETS_MS: [u64; 12] = [
+    5, 10, 25, 50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 60_000,
+];
+const WAIT_BUCKETS_MS: [u64; 12] = [
+    10, 50, 100, 250, 500, 1_000, 5_000, 10_000, 30_000, 60_000, 300_000, 900_000,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobEventKind {
+    /// Accepted by a `RedisQueue`/`PgQueue` built `with_events`, or by a queue
+    /// wrapped with `JobEvents::observe`.
+    Queued,
+    /// A worker started running the job (after throttles admitted it).
+    Started,
+    Succeeded,
+    /// The attempt failed and the job was scheduled again.
+    Retried,
+    /// The attempt failed with no retries left, or the payload could not run at all.
+    Failed,
+}
+
+/// One step in a job's life, handed to every `JobListener`.
+#[derive(Debug, Clone, Serialize)]
+pub struct JobEvent {
+    pub kind: JobEventKind,
+    pub job: String,
+    pub queue: String,
+    pub attempt: u32,
+    pub group_id: Option<String>,
+    pub at: OffsetDateTime,
+    /// Time between becoming available and starting (`Started` only).
+    pub wait: Option<Duration>,
+    /// Run time of the attempt (`Succeeded`, `Retried`, `Failed`).
+    pub runtime: Option<Duration>,
+    /// Seconds until the next attempt (`Retried` only).
+    pub retry_in: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl JobEvent {
+    pub(crate) fn new(kind: JobEventKind, payload: &JobPayload) -> Self {
+        Self {
+            kind,
+            job: payload.job.clone(),
+            queue: payload.queue.clone(),
+            attempt: payload.attempts,
+            group_id: payload.group_id.clone(),
+            at: OffsetDateTime::now_utc(),
+            wait: None,
+            runtime: None,
+            retry_in: None,
+            error: None,
+        }
+    }
+}
+
+/// Receives job lifecycle events. Called inline on the worker task, so keep it cheap
+/// and hand slow work (HTTP calls, DB writes) to a channel or `tokio::spawn`.
+pub trait JobListener: Send + Sync + 'static {
+    fn on_event(&self, event: &JobEvent);
+}
+
+impl<F> JobListener for F
+where
+    F: Fn(&JobEvent) + Send + Sync + 'static,
+{
+    fn on_event(&self, event: &JobEvent) {
+        self(event)
+    }
+}
+
+/// Lifecycle event hub: fans events out to listeners and keeps in-process metrics.
+///
+/// Cheap to clone; clones share listeners and metrics. A worker emits into its own hub
+/// (`Worker::events`), so share one with `Worker::set_events` to read worker metrics
+/// from the web process when the worker is embedded.
+///
+/// Metrics live in this process only: a web process serving `api::metrics_routes`
+/// sees `Queued` for its own pushes but nothing from a standalone worker, so serve
+/// `/metrics` from the worker process (or embed the worker) to get run counters.
+#[derive(Clone, Default)]
+pub struct JobEvents {
+    listeners: Arc<RwLock<Vec<Arc<dyn JobListener>>>>,
+    metrics: Arc<JobMetrics>,
+}
+
+impl JobEvents {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a listener. Listeners run in registration order.
+    pub fn listen<L: JobListener>(&self, listener: L) {
+        if let Ok(mut listeners) = self.listeners.write() {
+            listeners.push(Arc::new(listener));
+        }
+    }
+
+    pub fn emit(&self, event: JobEvent) {
+        self.metrics.record(&event);
+        let listeners = match self.listeners.read() {
+            Ok(listeners) => listeners.clone(),
+            Err(_) => return,
+        };
+        for listener in listeners {
+            listener.on_event(&event);
+        }
+    }
+
+    pub fn metrics_snapshot(&self) -> JobMetricsSnapshot {
+        self.metrics.snapshot()
+    }
+
+    /// Wrap a custom driver so every accepted push emits `Queued`.
+    /// Built-in queues emit on their own through `with_events`; do not wrap those.
+    pub fn observe(&self, queue: Arc<dyn QueueDriver>) -> Arc<dyn QueueDriver> {
+        Arc::new(ObservedQueue {
+            inner: queue,
+            events: self.clone(),
+        })
+    }
+}
+
+struct ObservedQueue {
+    inner: Arc<dyn QueueDriver>,
+    events: JobEvents,
+}
+
+#[async_trait]
+impl QueueDriver for ObservedQueue {
+    async fn push_payload(&self, payload: &JobPayload) -> anyhow::Result<bool> {
+        let pushed = self.inner.push_payload(payload).await?;
+        if pushed {
+            self.events
+                .emit(JobEvent::new(JobEventKind::Queued, payload));
+        }
+        Ok(pushed)
+    }
+
+    async fn schedule_payload(
+        &self,
+        payload: &JobPayload,
+        at: OffsetDateTime,
+    ) -> anyhow::Result<bool> {
+        let pushed = self.inner.schedule_payload(payload, at).await?;
+        if pushed {
+            self.events
+                .emit(JobEvent::new(JobEventKind::Queued, payload));
+        }
+        Ok(pushed)
+    }
+
+    fn batch_store(&self) -> crate::driver::BatchStore<'_> {
+        self.inner.batch_store()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JobHistogramSnapshot {
+    pub buckets_ms: Vec<u64>,
+    pub bucket_counts: Vec<u64>,
+    pub count: u64,
+    pub sum_ms: u64,
+}
+
+#[derive(Debug, Clone)]
+struct JobHistogram {
+    buckets_ms: Vec<u64>,
+    bucket_counts: Vec<u64>,
+    count: u64,
+    sum_ms: u64,
+}
+
+impl JobHistogram {
+    fn with_buckets(buckets_ms: &[u64]) -> Self {
+        Self {
+            buckets_ms: buckets_ms.to_vec(),
+            bucket_counts: vec![0; buckets_ms.len() + 1],
+            count: 0,
+            sum_ms: 0,
+        }
+    }
+
+    fn observe(&mut self, sample_ms: u64) {
+        let idx = self
+            .buckets_ms
+            .iter()
+            .position(|bound| sample_ms <= *bound)
+            .unwrap_or(self.buckets_ms.len());
+        self.bucket_counts[idx] = self.bucket_counts[idx].saturating_add(1);
+        self.count = self.count.saturating_add(1);
+        self.sum_ms = self.sum_ms.saturating_add(sample_ms);
+    }
+
+    fn snapshot(&self) -> JobHistogramSnapshot {
+        JobHistogramSnapshot {
+            buckets_ms: self.buckets_ms.clone(),
+            bucket_counts: self.bucket_counts.clone(),
+            count: self.count,
+            sum_ms: self.sum_ms,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct JobTypeMetrics {
+    queue: String,
+    queued: u64,
+    started: u64,
+    succeeded: u64,
+    retried: u64,
+    failed: u64,
+    runtime_ms: JobHistogram,
+    wait_ms: JobHistogram,
+}
+
+impl JobTypeMetrics {
+    fn new(queue: &str) -> Self {
+        Self {
+            queue: queue.to_string(),
+            queued: 0,
+            started: 0,
+            succeeded: 0,
+            retried: 0,
+            failed: 0,
+            runtime_ms: JobHistogram::with_buckets(&RUNTIME_BUCKETS_MS),
+            wait_ms: JobHistogram::with_buckets(&WAIT_BUCKETS_MS),
+        }
+    }
+}
+
+#[derive(Default)]
+struct JobMetrics {
+    in_flight: AtomicU64,
+    per_job: Mutex<HashMap<String, JobTypeMetrics>>,
+}
+
+impl JobMetrics {
+    fn record(&self, event: &JobEvent) {
+        match event.kind {
+            JobEventKind::Started => {
+                self.in_flight.fetch_add(1, Ordering::Relaxed);
+            }
+            JobEventKind::Succeeded | JobEventKind::Retried | JobEventKind::Failed
+                if event.runtime.is_some() =>
+            {
+                let _ = self
+                    .in_flight
+                    .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
+            }
+            _ => {}
+        }
+
+        let Ok(mut per_job) = self.per_job.lock() else {
+            return;
+        };
+        let metrics = per_job
+            .entry(event.job.clone())
+            .or_insert_with(|| JobTypeMetrics::new(&event.queue));
+        match event.kind {
+            JobEventKind::Queued => metrics.queued += 1,
+            JobEventKind::Started => metrics.started += 1,
+            JobEventKind::Succeeded => metrics.succeeded += 1,
+            JobEventKind::Retried => metrics.retried += 1,
+            JobEventKind::Failed => metrics.failed += 1,
+        }
+        if let Some(wait) = event.wait {
+            metrics.wait_ms.observe(wait.as_millis() as u64);
+        }
+        if let Some(runtime) = event.runtime {
+            metrics.runtime_ms.observe(runtime.as_millis() as u64);
+        }
+    }
+
+    fn snapshot(&self) -> JobMetricsSnapshot {
+        let mut jobs = self
+            .per_job
+            .lock()
+            .map(|per_job| {
+                per_job
+                    .iter()
+                    .map(|(job, value)| JobTypeMetricsSnapshot {
+                        job: job.clone(),
+                        queue: value.queue.clone(),
+                        queued: value.queued,
+                        started: value.started,
+                        succeeded: value.succeeded,
+                        retried: value.retried,
+                        failed: value.failed,
+                        runtime_ms: value.runtime_ms.snapshot(),
+                        wait_ms: value.wait_ms.snapshot(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        jobs.sort_by(|a, b| a.job.cmp(&b.job));
+
+        JobMetricsSnapshot {
+            in_flight: self.in_flight.load(Ordering::Relaxed),
+            queued: jobs.iter().map(|job| job.queued).sum(),
+            started: jobs.iter().map(|job| job.started).sum(),
+            succeeded: jobs.iter().map(|job| job.succeeded).sum(),
+            retried: jobs.iter().map(|job| job.retried).sum(),
+            failed: jobs.iter().map(|job| job.failed).sum(),
+            jobs,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JobTypeMetricsSnapshot {
+    pub job: String,
+    pub queue: String,
+    pub queued: u64,
+    pub started: u64,
+    pub succeeded: u64,
+    pub retried: u64,
+    pub failed: u64,
+    pub runtime_ms: JobHistogramSnapshot,
+    pub wait_ms: JobHistogramSnapshot,
+}
+
+/// Totals since the process started, plus per job type counters and histograms.
+#[derive(Debug, Clone, Serialize)]
+pub struct JobMetricsSnapshot {
+    pub in_flight: u64,
+    pub queued: u64,
+    pub started: u64,
+    pub succeeded: u64,
+    pub retried: u64,
+    pub failed: u64,
+    pub jobs: Vec<JobTypeMetricsSnapshot>,
+}
+
+impl JobMetricsSnapshot {
+    /// Render in the Prometheus text exposition format (version 0.0.4).
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# HELP core_jobs_in_flight Jobs currently running.");
+        let _ = writeln!(out, "# TYPE core_jobs_in_flight gauge");
+        let _ = writeln!(out, "core_jobs_in_flight {}", self.in_flight);
+
+        self.write_counter(&mut out, "queued", "Jobs pushed to a queue.", |job| {
+            job.queued
+        });
+        self.write_counter(&mut out, "started", "Job attempts started.", |job| {
+            job.started
+        });
+        self.write_counter(
+            &mut out,
+            "succeeded",
+            "Jobs finished successfully.",
+            |job| job.succeeded,
+        );
+        self.write_counter(&mut out, "retried", "Failed attempts retried.", |job| {
+            job.retried
+        });
+        self.write_counter(&mut out, "failed", "Jobs failed permanently.", |job| {
+            job.failed
+        });
+        self.write_histogram(&mut out, "runtime", "Job attempt run time.", |job| {
+            &job.runtime_ms
+        });
+        self.write_histogram(&mut out, "wait", "Time from available to started.", |job| {
+            &job.wait_ms
+        });
+        out
+    }
+
+    fn write_counter(
+        &self,
+        out: &mut String,
+        name: &str,
+        help: &str,
+        value: impl Fn(&JobTypeMetricsSnapshot) -> u64,
+    ) {
+        let _ = writeln!(out, "# HELP core_jobs_{name}_total {help}");
+        let _ = writeln!(out, "# TYPE core_jobs_{name}_total counter");
+        for job in &self.jobs {
+            let _ = writeln!(
+                out,
+                "core_jobs_{name}_total{{{}}} {}",
+                labels(job),
+                value(job)
+            );
+        }
+    }
+
+    fn write_histogram(
+        &self,
+        out: &mut String,
+        name: &str,
+        help: &str,
+        histogram: impl Fn(&JobTypeMetricsSnapshot) -> &JobHistogramSnapshot,
+    ) {
+        let _ = writeln!(out, "# HELP core_jobs_{name}_seconds {help}");
+        let _ = writeln!(out, "# TYPE core_jobs_{name}_seconds histogram");
+        for job in &self.jobs {
+            let labels = labels(job);
+            let histogram = histogram(job);
+            let mut cumulative = 0;
+            for (bound, count) in histogram.buckets_ms.iter().zip(&histogram.bucket_counts) {
+                cumulative += count;
+                let _ = writeln!(
+                    out,
+                    "core_jobs_{name}_seconds_bucket{{{labels},le=\"{}\"}} {cumulative}",
+                    *bound as f64 / 1000.0
+                );
+            }
+            let _ = writeln!(
+                out,
+                "core_jobs_{name}_seconds_bucket{{{labels},le=\"+Inf\"}} {}",
+                histogram.count
+            );
+            let _ = writeln!(
+                out,
+                "core_jobs_{name}_seconds_sum{{{labels}}} {}",
+                histogram.sum_ms as f64 / 1000.0
+            );
+            let _ = writeln!(
+                out,
+                "core_jobs_{name}_seconds_count{{{labels}}} {}",
+                histogram.count
+            );
+        }
+    }
+}
+
+fn labels(job: &JobTypeMetricsSnapshot) -> String {
+    format!(
+        "job=\"{}\",queue=\"{}\"",
+        escape_label(&job.job),
+        escape_label(&job.queue)
+    )
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{escape_label, JobEvent, JobEventKind, JobEvents};
+    use crate::JobPayload;
+
+    fn event(kind: JobEventKind, runtime_ms: Option<u64>) -> JobEvent {
+        let payload: JobPayload = serde_json::from_value(serde_json::json!({
+            "job": "SendMail",
+            "data": {},
+            "queue": "mail",
+        }))
+        .expect("payload");
+        let mut event = JobEvent::new(kind, &payload);
+        event.runtime = runtime_ms.map(Duration::from_millis);
+        event
+    }
+
+    #[test]
+    fn escape_label_escapes_backslash_quote_and_newline() {
+        assert_eq!(escape_label("plain"), "plain");
+        assert_eq!(escape_label(r#"a"b"#), r#"a\"b"#);
+        assert_eq!(escape_label(r"a\b"), r"a\\b");
+        assert_eq!(escape_label("a\nb"), r"a\nb");
+    }
+
+    #[test]
+    fn prometheus_renders_counters_and_cumulative_buckets() {
+        let events = JobEvents::new();
+        events.emit(event(JobEventKind::Queued, None));
+        events.emit(event(JobEventKind::Started, None));
+        events.emit(event(JobEventKind::Succeeded, Some(30)));
+
+        let text = events.metrics_snapshot().to_prometheus();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert!(lines.contains(&"# TYPE core_jobs_in_flight gauge"));
+        assert!(lines.contains(&"core_jobs_in_flight 0"));
+        assert!(lines.contains(&"# TYPE core_jobs_queued_total counter"));
+        assert!(lines.contains(&r#"core_jobs_queued_total{job="SendMail",queue="mail"} 1"#));
+        assert!(lines.contains(&r#"core_jobs_succeeded_total{job="SendMail",queue="mail"} 1"#));
+        assert!(lines.contains(&r#"core_jobs_failed_total{job="SendMail",queue="mail"} 0"#));
+        assert!(lines.contains(&"# TYPE core_jobs_runtime_seconds histogram"));
+        assert!(lines.contains(
+            &r#"core_jobs_runtime_seconds_bucket{job="SendMail",queue="mail",le="0.025"} 0"#
+        ));
+        assert!(lines.contains(
+            &r#"core_jobs_runtime_seconds_bucket{job="SendMail",queue="mail",le="0.05"} 1"#
+        ));
+        assert!(lines.contains(
+            &r#"core_jobs_runtime_seconds_bucket{job="SendMail",queue="mail",le="60"} 1"#
+        ));
+        assert!(lines.contains(
+            &r#"core_jobs_runtime_seconds_bucket{job="SendMail",queue="mail",le="+Inf"} 1"#
+        ));
+        assert!(
+            lines.contains(&r#"core_jobs_runtime_seconds_sum{job="SendMail",queue="mail"} 0.03"#)
+        );
+        assert!(
+            lines.contains(&r#"core_jobs_runtime_seconds_count{job="SendMail",queue="mail"} 1"#)
+        );
+    }
+}
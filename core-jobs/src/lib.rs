@@ -4,11 +4,20 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::fmt::Debug;
 
 pub mod api;
+pub mod batch;
 pub mod buffer;
 // pub mod config;
 pub mod cron;
+pub mod driver;
+pub mod events;
+pub mod manager;
+pub mod middleware;
+pub mod postgres;
 pub mod queue;
+pub mod reliable;
 pub mod runtime;
+pub mod testing;
+pub mod throttle;
 pub mod utils;
 pub mod worker;
 
@@ -21,20 +30,139 @@ pub struct JobContext {
     // Extensions for extra services (e.g. ChannelManager)
     // Extensions for extra services (e.g. ChannelManager)
     pub extensions: axum::http::Extensions,
+    /// Cancelled when the worker shuts down. Long jobs should check it and stop cleanly.
+    pub cancellation: tokio_util::sync::CancellationToken,
+}
+
+impl JobContext {
+    /// `true` once the worker started shutting down.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    /// Resolves when the worker starts shutting down (use in `tokio::select!`).
+    pub async fn cancelled(&self) {
+        self.cancellation.cancelled().await
+    }
 }
 
 fn default_queue_name() -> String {
     "default".to_string()
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+fn default_payload_version() -> u32 {
+    1
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct JobPayload {
+    /// Per-dispatch id. Keeps identical jobs scheduled for the same time distinct
+    /// in the delayed ZSET; dedupe is opt-in through `unique_key`.
+    #[serde(default = "uuid::Uuid::new_v4")]
+    pub id: uuid::Uuid,
     pub job: String,
     pub data: serde_json::Value,
     #[serde(default = "default_queue_name")]
     pub queue: String,
     #[serde(default)]
     pub attempts: u32,
+    /// Ordered-group id captured at dispatch time, so delayed and outbox
+    /// payloads can be routed back into their group list.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<String>,
+    /// Dedupe key (`{NAME}:{Job::unique_key}`) held in Redis while the job is pending or running.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unique_key: Option<String>,
+    /// Lifetime of the dedupe lock in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unique_ttl: Option<u64>,
+    /// Owning `batch::JobBatch`, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_id: Option<String>,
+    /// Remaining links of a `batch::JobChain`, pushed one by one as each job succeeds.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub chain: Vec<JobPayload>,
+    /// Locale active at dispatch time, restored by `middleware::LocaleMiddleware`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// When the job became available to workers (unix ms): dispatch time, scheduled time,
+    /// or the end of a retry backoff. Used for the wait-time metric in `events`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub available_at_ms: Option<u64>,
+    /// `Job::VERSION` at dispatch time. Older payloads go through `Job::upgrade` before decoding.
+    #[serde(default = "default_payload_version")]
+    pub version: u32,
+    /// `data` is a string sealed with the app key (`Job::ENCRYPTED`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub encrypted: bool,
+}
+
+impl JobPayload {
+    /// Build a fresh (attempt 0) payload for a job instance.
+    pub fn from_job<J: Job>(job: &J) -> anyhow::Result<Self> {
+        let unique_key = job.unique_key();
+        let data = if J::ENCRYPTED {
+            let plain = serde_json::to_string(job)?;
+            serde_json::Value::String(payload_crypt()?.encrypt(&plain)?)
+        } else {
+            serde_json::to_value(job)?
+        };
+        Ok(Self {
+            id: uuid::Uuid::new_v4(),
+            job: J::NAME.to_string(),
+            data,
+            queue: J::QUEUE.to_string(),
+            attempts: 0,
+            group_id: job.group_id(),
+            unique_ttl: unique_key.as_ref().map(|_| job.unique_ttl()),
+            unique_key: unique_key.map(|key| format!("{}:{}", J::NAME, key)),
+            batch_id: None,
+            chain: Vec::new(),
+            locale: Some(core_i18n::current_locale().to_string()),
+            available_at_ms: Some(unix_millis(time::OffsetDateTime::now_utc())),
+            version: J::VERSION,
+            encrypted: J::ENCRYPTED,
+        })
+    }
+
+    /// Decode `data` as `J`: decrypt it if needed, then run `Job::upgrade`
+    /// when the payload was dispatched with an older `Job::VERSION`.
+    pub fn decode<J: Job>(&self) -> anyhow::Result<J> {
+        let mut data = if self.encrypted {
+            let sealed = self
+                .data
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("Encrypted job {} has no ciphertext", self.job))?;
+            serde_json::from_str(&payload_crypt()?.decrypt(sealed)?)?
+        } else {
+            self.data.clone()
+        };
+        if self.version < J::VERSION {
+            data = J::upgrade(data, self.version)?;
+        }
+        Ok(serde_json::from_value(data)?)
+    }
+
+    /// Copy with `available_at_ms` moved to `at` (delayed dispatch and retries).
+    pub(crate) fn available_at(&self, at: time::OffsetDateTime) -> Self {
+        Self {
+            available_at_ms: Some(unix_millis(at)),
+            ..self.clone()
+        }
+    }
+}
+
+/// Cipher for `Job::ENCRYPTED` payloads, keyed by `[app] key` from `global_config`.
+fn payload_crypt() -> anyhow::Result<core_db::common::crypt::Crypt> {
+    let settings = core_config::global_config::get::<std::sync::Arc<core_config::Settings>>()
+        .ok_or_else(|| {
+            anyhow::anyhow!("Encrypted jobs need the app key; register Settings in global_config")
+        })?;
+    core_db::common::crypt::Crypt::new(&settings.app.key)
+}
+
+pub(crate) fn unix_millis(at: time::OffsetDateTime) -> u64 {
+    (at.unix_timestamp_nanos() / 1_000_000).max(0) as u64
 }
 
 #[async_trait]
@@ -46,6 +174,20 @@ pub trait Job: Serialize + DeserializeOwned + Send + Sync + Debug + 'static {
     /// The Queue name to push to. Default "default".
     const QUEUE: &'static str = "default";
 
+    /// Encrypt the job data with the app key (AES-GCM) while it sits in the queue
+    /// and in `failed_jobs`. Use for jobs carrying PII.
+    const ENCRYPTED: bool = false;
+
+    /// Shape version of the job data. Bump it when the struct changes incompatibly
+    /// and convert older payloads in `upgrade`.
+    const VERSION: u32 = 1;
+
+    /// Convert data dispatched with an older `VERSION` (`from`) to the current shape.
+    /// Runs before deserializing; the default passes the data through unchanged.
+    fn upgrade(data: serde_json::Value, _from: u32) -> anyhow::Result<serde_json::Value> {
+        Ok(data)
+    }
+
     /// Execute the job logic.
     async fn handle(&self, ctx: &JobContext) -> anyhow::Result<()>;
 
@@ -54,6 +196,44 @@ pub trait Job: Serialize + DeserializeOwned + Send + Sync + Debug + 'static {
         None
     }
 
+    /// Optional dedupe key. While a job with the same key is pending or running,
+    /// pushing another one is a no-op. The worker clears the key when the job
+    /// finishes or permanently fails.
+    fn unique_key(&self) -> Option<String> {
+        None
+    }
+
+    /// Safety TTL (seconds) for the dedupe lock, in case a worker never clears it.
+    /// Default 1 hour.
+    fn unique_ttl(&self) -> u64 {
+        3600
+    }
+
+    /// Maximum run time of `handle`. A timed-out attempt counts as a failure and is retried
+    /// like any other error. Default `None` (no limit).
+    fn timeout(&self) -> Option<std::time::Duration> {
+        None
+    }
+
+    /// Allow at most `n` starts per `window` across all workers (fixed window).
+    /// Jobs over the limit go back to the delayed set until the window resets;
+    /// this does not count as a failed attempt.
+    fn rate_limit(&self) -> Option<(u32, std::time::Duration)> {
+        None
+    }
+
+    /// Allow at most `n` instances running at once across all workers.
+    /// Jobs over the cap go back to the delayed set for a couple of seconds.
+    fn max_concurrency(&self) -> Option<u32> {
+        None
+    }
+
+    /// Bucket shared by `rate_limit` and `max_concurrency`. Default: the job `NAME`.
+    /// Return the same key from several job types (or a queue name) to share one quota.
+    fn throttle_key(&self) -> String {
+        Self::NAME.to_string()
+    }
+
     /// Maximum number of retries. Default 5.
     fn max_retries(&self) -> u32 {
         5
@@ -71,9 +251,33 @@ pub trait Job: Serialize + DeserializeOwned + Send + Sync + Debug + 'static {
         }
     }
 
-    /// Dispatch this job to the queue.
-    async fn dispatch(&self, queue: &queue::RedisQueue) -> anyhow::Result<()> {
-        queue.push(self).await
+    /// Dispatch this job to the queue (`RedisQueue`, `PgQueue` or any `driver::QueueDriver`).
+    async fn dispatch<Q: driver::QueueDriver + ?Sized>(&self, queue: &Q) -> anyhow::Result<()> {
+        queue.push_payload(&JobPayload::from_job(self)?).await?;
+        Ok(())
+    }
+
+    /// Dispatch this job so it becomes available at `at`.
+    /// A time in the past is enqueued immediately.
+    async fn dispatch_at<Q: driver::QueueDriver + ?Sized>(
+        &self,
+        queue: &Q,
+        at: time::OffsetDateTime,
+    ) -> anyhow::Result<()> {
+        queue
+            .schedule_payload(&JobPayload::from_job(self)?, at)
+            .await?;
+        Ok(())
+    }
+
+    /// Dispatch this job so it becomes available after `delay`.
+    async fn dispatch_after<Q: driver::QueueDriver + ?Sized>(
+        &self,
+        queue: &Q,
+        delay: std::time::Duration,
+    ) -> anyhow::Result<()> {
+        self.dispatch_at(queue, time::OffsetDateTime::now_utc() + delay)
+            .await
     }
 
     /// Called when all retries are exhausted, before persisting to failed_jobs.
@@ -83,3 +287,122 @@ pub trait Job: Serialize + DeserializeOwned + Send + Sync + Debug + 'static {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Job, JobContext, JobPayload};
+    use serde::{Deserialize, Serialize};
+    use std::sync::Arc;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct ExportReport {
+        email: String,
+    }
+
+    #[async_trait::async_trait]
+    impl Job for ExportReport {
+        const NAME: &'static str = "ExportReport";
+        const ENCRYPTED: bool = true;
+        const VERSION: u32 = 2;
+
+        // v1 called the field `address`.
+        fn upgrade(mut data: serde_json::Value, from: u32) -> anyhow::Result<serde_json::Value> {
+            if from < 2 {
+                if let Some(address) = data.as_object_mut().and_then(|o| o.remove("address")) {
+                    data["email"] = address;
+                }
+            }
+            Ok(data)
+        }
+
+        async fn handle(&self, _ctx: &JobContext) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Register settings with an app key so `payload_crypt` works.
+    fn register_app_key() {
+        if core_config::global_config::get::<Arc<core_config::Settings>>().is_some() {
+            return;
+        }
+        let path = std::env::temp_dir().join(format!("core-jobs-{}.toml", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            "[app]\nkey = \"0123456789abcdef0123456789abcdef\"\n\n\
+             [database]\nurl = \"postgres://localhost/test\"\n",
+        )
+        .expect("write settings");
+        std::env::set_var("SETTINGS_PATH", &path);
+        let settings = core_config::Settings::load().expect("load settings");
+        let _ = std::fs::remove_file(&path);
+        let _ = core_config::global_config::try_set(Arc::new(settings));
+    }
+
+    #[test]
+    fn encrypted_payload_round_trips() {
+        register_app_key();
+        let job = ExportReport {
+            email: "ada@example.com".to_string(),
+        };
+
+        let payload = JobPayload::from_job(&job).expect("payload");
+        assert!(payload.encrypted);
+        assert_eq!(payload.version, 2);
+        let sealed = payload.data.as_str().expect("ciphertext");
+        assert!(!sealed.contains("ada@example.com"));
+
+        // Survives the trip through the queue as JSON.
+        let stored = serde_json::to_string(&payload).expect("serialize");
+        let payload: JobPayload = serde_json::from_str(&stored).expect("deserialize");
+        let decoded: ExportReport = payload.decode().expect("decode");
+        assert_eq!(decoded.email, "ada@example.com");
+    }
+
+    #[test]
+    fn older_payload_is_upgraded_before_decoding() {
+        let payload: JobPayload = serde_json::from_value(serde_json::json!({
+            "job": "ExportReport",
+            "data": { "address": "ada@example.com" },
+        }))
+        .expect("payload");
+        assert_eq!(payload.version, 1);
+
+        let decoded: ExportReport = payload.decode().expect("decode");
+        assert_eq!(decoded.email, "ada@example.com");
+    }
+
+    #[test]
+    fn identical_dispatches_get_distinct_payloads() {
+        register_app_key();
+        let job = ExportReport {
+            email: "ada@example.com".to_string(),
+        };
+        let at = time::OffsetDateTime::now_utc() + time::Duration::hours(1);
+
+        let first = JobPayload::from_job(&job)
+            .expect("payload")
+            .available_at(at);
+        let second = JobPayload::from_job(&job)
+            .expect("payload")
+            .available_at(at);
+        assert_ne!(first.id, second.id);
+        assert_ne!(
+            serde_json::to_value(&first).unwrap()["id"],
+            serde_json::to_value(&second).unwrap()["id"]
+        );
+    }
+
+    #[test]
+    fn encrypted_payload_without_ciphertext_fails() {
+        let payload: JobPayload = serde_json::from_value(serde_json::json!({
+            "job": "ExportReport",
+            "data": { "email": "ada@example.com" },
+            "version": 2,
+            "encrypted": true,
+        }))
+        .expect("payload");
+
+        let err = payload.decode::<ExportReport>().expect_err("plain data");
+        assert!(err.to_string().contains("has no ciphertext"));
+    }
+}
@@ -0,0 +1,235 @@
+use crate::{driver::QueueDriver, JobPayload};
+use core_db::{
+    common::{
+        model_api::{Page, Query},
+        sql::{DbConn, Op, OrderDir},
+    },
+    generated::models::{FailedJobCol, FailedJobModel, FailedJobRecord},
+};
+use std::collections::HashSet;
+use std::sync::Arc;
+use time::OffsetDateTime;
+
+/// How often workers reload the paused queue list.
+pub const PAUSE_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Rows loaded per page by `JobManager::retry_matching`.
+const RETRY_PAGE_SIZE: i64 = 100;
+
+/// Filters for `JobManager::failed_jobs`. `None` matches everything.
+#[derive(Debug, Clone, Default)]
+pub struct FailedJobFilter {
+    /// Exact job `NAME`.
+    pub job: Option<String>,
+    pub queue: Option<String>,
+    /// Case-insensitive substring of the error message.
+    pub error: Option<String>,
+    /// Failed at or after.
+    pub from: Option<OffsetDateTime>,
+    /// Failed at or before.
+    pub to: Option<OffsetDateTime>,
+}
+
+/// Result of a bulk action.
+#[derive(Debug, Clone, Copy, Default, serde::Serialize)]
+pub struct BulkOutcome {
+    /// Jobs retried or deleted.
+    pub affected: u64,
+    /// Jobs left in `failed_jobs`: unreadable payloads, or a retry dropped because
+    /// the same unique job is already pending.
+    pub skipped: u64,
+}
+
+/// A queue workers currently skip.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PausedQueue {
+    pub queue: String,
+    pub paused_at: OffsetDateTime,
+}
+
+/// Operations behind the job management API: failed-job search, retry and
+/// forget, and pausing queues. Retries go through the configured queue driver.
+#[derive(Clone)]
+pub struct JobManager {
+    db: sqlx::PgPool,
+    queue: Arc<dyn QueueDriver>,
+}
+
+impl JobManager {
+    pub fn new(db: sqlx::PgPool, queue: Arc<dyn QueueDriver>) -> Self {
+        Self { db, queue }
+    }
+
+    /// Failed jobs matching `filter`, newest first.
+    pub async fn failed_jobs(
+        &self,
+        filter: &FailedJobFilter,
+        page: i64,
+        per_page: i64,
+    ) -> anyhow::Result<Page<FailedJobRecord>> {
+        filtered(filter)
+            .order_by(FailedJobCol::FAILED_AT, OrderDir::Desc)
+            .paginate(DbConn::pool(&self.db), page.max(1), per_page.clamp(1, 100))
+            .await
+    }
+
+    pub async fn failed_job(&self, id: uuid::Uuid) -> anyhow::Result<Option<FailedJobRecord>> {
+        FailedJobModel::query()
+            .where_col(FailedJobCol::ID, Op::Eq, id)
+            .first(DbConn::pool(&self.db))
+            .await
+    }
+
+    /// Push the given failed jobs back onto their queues and remove them from `failed_jobs`.
+    pub async fn retry(&self, ids: &[uuid::Uuid]) -> anyhow::Result<BulkOutcome> {
+        if ids.is_empty() {
+            return Ok(BulkOutcome::default());
+        }
+        let records = FailedJobModel::query()
+            .where_in(FailedJobCol::ID, ids.iter().copied())
+            .all(DbConn::pool(&self.db))
+            .await?;
+        self.retry_records(records).await
+    }
+
+    /// Retry every failed job matching `filter` (e.g. all failures of one job), oldest first,
+    /// in pages of `RETRY_PAGE_SIZE`. Jobs failing again while this runs are left for later.
+    pub async fn retry_matching(&self, filter: &FailedJobFilter) -> anyhow::Result<BulkOutcome> {
+        let filter = FailedJobFilter {
+            to: Some(filter.to.unwrap_or_else(OffsetDateTime::now_utc)),
+            ..filter.clone()
+        };
+        let mut outcome = BulkOutcome::default();
+        loop {
+            // Retried rows are gone, skipped rows stay at the front of the ordering
+            let records = filtered(&filter)
+                .order_by(FailedJobCol::FAILED_AT, OrderDir::Asc)
+                .order_by(FailedJobCol::ID, OrderDir::Asc)
+                .offset(outcome.skipped as i64)
+                .limit(RETRY_PAGE_SIZE)
+                .all(DbConn::pool(&self.db))
+                .await?;
+            let done = (records.len() as i64) < RETRY_PAGE_SIZE;
+            let page = self.retry_records(records).await?;
+            outcome.affected += page.affected;
+            outcome.skipped += page.skipped;
+            if done {
+                return Ok(outcome);
+            }
+        }
+    }
+
+    /// Delete failed jobs without retrying them.
+    pub async fn forget(&self, ids: &[uuid::Uuid]) -> anyhow::Result<u64> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        FailedJobModel::query()
+            .where_in(FailedJobCol::ID, ids.iter().copied())
+            .delete(DbConn::pool(&self.db))
+            .await
+    }
+
+    /// Stop workers from pulling new jobs of `queue`. Running jobs finish normally.
+    pub async fn pause(&self, queue: &str) -> anyhow::Result<()> {
+        sqlx::query("INSERT INTO paused_queues (queue) VALUES ($1) ON CONFLICT (queue) DO NOTHING")
+            .bind(queue)
+            .execute(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    /// Let workers pull from `queue` again. Returns `false` if it was not paused.
+    pub async fn resume(&self, queue: &str) -> anyhow::Result<bool> {
+        let removed = sqlx::query("DELETE FROM paused_queues WHERE queue = $1")
+            .bind(queue)
+            .execute(&self.db)
+            .await?
+            .rows_affected();
+        Ok(removed > 0)
+    }
+
+    pub async fn paused(&self) -> anyhow::Result<Vec<PausedQueue>> {
+        let rows: Vec<(String, OffsetDateTime)> =
+            sqlx::query_as("SELECT queue, paused_at FROM paused_queues ORDER BY queue")
+                .fetch_all(&self.db)
+                .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(queue, paused_at)| PausedQueue { queue, paused_at })
+            .collect())
+    }
+
+    async fn retry_records(&self, records: Vec<FailedJobRecord>) -> anyhow::Result<BulkOutcome> {
+        let mut outcome = BulkOutcome::default();
+        for record in records {
+            let payload: JobPayload = match serde_json::from_value(record.payload) {
+                Ok(payload) => payload,
+                Err(e) => {
+                    tracing::warn!("Cannot retry failed job {}: {}", record.id, e);
+                    outcome.skipped += 1;
+                    continue;
+                }
+            };
+            if self.retry_one(record.id, &payload).await? {
+                outcome.affected += 1;
+            } else {
+                outcome.skipped += 1;
+            }
+        }
+        Ok(outcome)
+    }
+
+    /// Delete the row and push its payload in one step: the delete is only committed once
+    /// the push succeeded, so a failed push leaves the row in `failed_jobs`.
+    async fn retry_one(&self, id: uuid::Uuid, payload: &JobPayload) -> anyhow::Result<bool> {
+        let pool_conn = DbConn::pool(&self.db);
+        let scope = pool_conn.begin_scope().await?;
+        let deleted = FailedJobModel::query()
+            .where_col(FailedJobCol::ID, Op::Eq, id)
+            .delete(scope.conn())
+            .await?;
+        // Already retried or forgotten by a concurrent call
+        if deleted == 0 || !self.queue.push_payload(payload).await? {
+            scope.rollback().await?;
+            return Ok(false);
+        }
+        scope.commit().await?;
+        Ok(true)
+    }
+}
+
+/// Names of paused queues (read by workers every `PAUSE_REFRESH_INTERVAL`).
+pub(crate) async fn paused_queues(db: &sqlx::PgPool) -> anyhow::Result<HashSet<String>> {
+    let rows: Vec<(String,)> = sqlx::query_as("SELECT queue FROM paused_queues")
+        .fetch_all(db)
+        .await?;
+    Ok(rows.into_iter().map(|(queue,)| queue).collect())
+}
+
+fn filtered(filter: &FailedJobFilter) -> Query<'static, FailedJobModel> {
+    let mut query = FailedJobModel::query();
+    if let Some(job) = &filter.job {
+        query = query.where_col(FailedJobCol::JOB_NAME, Op::Eq, job.clone());
+    }
+    if let Some(queue) = &filter.queue {
+        query = query.where_col(FailedJobCol::QUEUE, Op::Eq, queue.clone());
+    }
+    if let Some(error) = &filter.error {
+        let pattern = format!(
+            "%{}%",
+            error
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        );
+        query = query.where_col(FailedJobCol::ERROR, Op::ILike, pattern);
+    }
+    if let Some(from) = filter.from {
+        query = query.where_col(FailedJobCol::FAILED_AT, Op::Ge, from);
+    }
+    if let Some(to) = filter.to {
+        query = query.where_col(FailedJobCol::FAILED_AT, Op::Le, to);
+    }
+    query
+}
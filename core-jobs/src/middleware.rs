@@ -0,0 +1,165 @@
+use crate::{JobContext, JobPayload};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::Instrument;
+
+/// The rest of the pipeline (inner middlewares and the job itself).
+pub type Next<'a> = Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send + 'a>>;
+
+/// Cross-cutting code wrapped around every job execution.
+///
+/// Register with `Worker::middleware`; the first registered middleware is the outermost.
+/// `before` runs inside `around`, then the inner pipeline, then `after` or `on_error`.
+/// Timeouts are reported to `on_error` like any other job error.
+#[async_trait]
+pub trait JobMiddleware: Send + Sync + 'static {
+    /// Runs before the job. An error skips the job and counts as a failed attempt.
+    async fn before(&self, _job: &JobPayload, _ctx: &JobContext) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Runs after the job succeeded.
+    async fn after(&self, _job: &JobPayload, _ctx: &JobContext) {}
+
+    /// Runs after the job (or an inner middleware) failed.
+    async fn on_error(&self, _job: &JobPayload, _ctx: &JobContext, _error: &anyhow::Error) {}
+
+    /// Wrap the rest of the pipeline, e.g. to enter a span or a task-local scope.
+    fn around<'a>(&'a self, _job: &'a JobPayload, next: Next<'a>) -> Next<'a> {
+        next
+    }
+}
+
+/// Run `handle` through `middlewares` (outermost first).
+pub(crate) async fn run<'a>(
+    middlewares: &'a [Arc<dyn JobMiddleware>],
+    job: &'a JobPayload,
+    ctx: &'a JobContext,
+    handle: Next<'a>,
+) -> anyhow::Result<()> {
+    let mut next = handle;
+    for middleware in middlewares.iter().rev() {
+        let inner = next;
+        next = middleware.around(
+            job,
+            Box::pin(async move {
+                middleware.before(job, ctx).await?;
+                let result = inner.await;
+                match &result {
+                    Ok(()) => middleware.after(job, ctx).await,
+                    Err(e) => middleware.on_error(job, ctx, e).await,
+                }
+                result
+            }),
+        );
+    }
+    next.await
+}
+
+/// Runs every job inside a `job` tracing span (name, queue, attempt, group, batch).
+pub struct TracingMiddleware;
+
+impl JobMiddleware for TracingMiddleware {
+    fn around<'a>(&'a self, job: &'a JobPayload, next: Next<'a>) -> Next<'a> {
+        let span = tracing::info_span!(
+            "job",
+            name = %job.job,
+            queue = %job.queue,
+            attempt = job.attempts,
+            group = job.group_id.as_deref().unwrap_or(""),
+            batch = job.batch_id.as_deref().unwrap_or(""),
+        );
+        Box::pin(next.instrument(span))
+    }
+}
+
+/// Runs every job with the locale that was active when it was dispatched
+/// (`core_i18n::current_locale()`), falling back to the default locale.
+pub struct LocaleMiddleware;
+
+impl JobMiddleware for LocaleMiddleware {
+    fn around<'a>(&'a self, job: &'a JobPayload, next: Next<'a>) -> Next<'a> {
+        let Some(locale) = job
+            .locale
+            .as_deref()
+            .and_then(core_i18n::match_supported_locale)
+        else {
+            return next;
+        };
+        Box::pin(core_i18n::scope_locale(locale, || next))
+    }
+}
+
+/// Per-job-type execution statistics collected by `TimingMiddleware`.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct JobTiming {
+    pub runs: u64,
+    pub failures: u64,
+    pub total_ms: u64,
+    pub max_ms: u64,
+}
+
+/// Measures every execution, logs slow jobs and keeps in-process totals per job type.
+#[derive(Clone)]
+pub struct TimingMiddleware {
+    slow_threshold: Duration,
+    timings: Arc<Mutex<HashMap<String, JobTiming>>>,
+}
+
+impl TimingMiddleware {
+    /// Jobs slower than `slow_threshold` are logged as warnings.
+    pub fn new(slow_threshold: Duration) -> Self {
+        Self {
+            slow_threshold,
+            timings: Arc::default(),
+        }
+    }
+
+    /// Totals since the worker started, keyed by job name.
+    pub fn snapshot(&self) -> HashMap<String, JobTiming> {
+        self.timings
+            .lock()
+            .map(|timings| timings.clone())
+            .unwrap_or_default()
+    }
+
+    fn record(&self, job: &str, elapsed: Duration, failed: bool) {
+        let elapsed_ms = elapsed.as_millis() as u64;
+        if elapsed > self.slow_threshold {
+            tracing::warn!("Slow job {}: {}ms", job, elapsed_ms);
+        } else {
+            tracing::debug!("Job {} took {}ms", job, elapsed_ms);
+        }
+
+        if let Ok(mut timings) = self.timings.lock() {
+            let timing = timings.entry(job.to_string()).or_default();
+            timing.runs += 1;
+            timing.total_ms += elapsed_ms;
+            timing.max_ms = timing.max_ms.max(elapsed_ms);
+            if failed {
+                timing.failures += 1;
+            }
+        }
+    }
+}
+
+impl Default for TimingMiddleware {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(10))
+    }
+}
+
+impl JobMiddleware for TimingMiddleware {
+    fn around<'a>(&'a self, job: &'a JobPayload, next: Next<'a>) -> Next<'a> {
+        Box::pin(async move {
+            let started = Instant::now();
+            let result = next.await;
+            self.record(&job.job, started.elapsed(), result.is_err());
+            result
+        })
+    }
+}
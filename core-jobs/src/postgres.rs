@@ -0,0 +1,352 @@
+use crate::{
+    events::{JobEvent, JobEventKind, JobEvents},
+    Job, JobPayload,
+};
+use std::sync::Arc;
+use time::OffsetDateTime;
+
+/// `LISTEN` channel notified when a job becomes available; the payload is the queue name.
+pub const NOTIFY_CHANNEL: &str = "core_jobs";
+/// A reservation not refreshed for this long belongs to a dead worker and is picked up again.
+pub const RESERVATION_TTL_SECS: f64 = 60.0;
+/// How often a worker process refreshes the reservations of its running jobs.
+pub const HEARTBEAT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15);
+/// Poll period of idle workers. Delayed jobs start at most this long after `available_at`.
+pub const IDLE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Take the next available job of the given queues.
+/// A job of an ordered group is only eligible while no earlier job of the same
+/// group exists, so groups run one job at a time and in order (retries pause the group).
+const RESERVE_SQL: &str = r#"
+UPDATE queue_jobs
+SET reserved_at = NOW(), reserved_by = $2
+WHERE id = (
+    SELECT job.id
+    FROM queue_jobs job
+    WHERE job.queue = ANY($1)
+      AND job.available_at <= NOW()
+      AND (job.reserved_at IS NULL OR job.reserved_at < NOW() - make_interval(secs => $3))
+      AND (job.group_id IS NULL OR NOT EXISTS (
+          SELECT 1
+          FROM queue_jobs earlier
+          WHERE earlier.queue = job.queue
+            AND earlier.group_id = job.group_id
+            AND (earlier.queued_at, earlier.id) < (job.queued_at, job.id)
+      ))
+    ORDER BY job.available_at, job.queued_at
+    LIMIT 1
+    FOR UPDATE SKIP LOCKED
+)
+RETURNING id, payload
+"#;
+
+/// Move up to 100 outbox rows into `queue_jobs` in one statement.
+const FLUSH_OUTBOX_SQL: &str = r#"
+WITH moved AS (
+    DELETE FROM outbox_jobs
+    WHERE id IN (
+        SELECT id FROM outbox_jobs
+        ORDER BY created_at
+        LIMIT 100
+        FOR UPDATE SKIP LOCKED
+    )
+    RETURNING id, queue, payload, available_at, created_at
+)
+INSERT INTO queue_jobs (id, queue, group_id, unique_key, payload, attempts, available_at, queued_at)
+SELECT id,
+       COALESCE(payload->>'queue', queue),
+       payload->>'group_id',
+       payload->>'unique_key',
+       payload,
+       COALESCE((payload->>'attempts')::INT, 0),
+       COALESCE(available_at, NOW()),
+       COALESCE(available_at, created_at)
+FROM moved
+ON CONFLICT (unique_key) DO NOTHING
+RETURNING payload
+"#;
+
+/// Move a `failed_jobs` row back into `queue_jobs` (dedupe key not re-claimed).
+const RETRY_FAILED_SQL: &str = r#"
+WITH failed AS (
+    DELETE FROM failed_jobs WHERE id = $1
+    RETURNING queue, payload
+)
+INSERT INTO queue_jobs (id, queue, group_id, payload, attempts, available_at, queued_at)
+SELECT $2,
+       COALESCE(payload->>'queue', queue),
+       payload->>'group_id',
+       payload,
+       COALESCE((payload->>'attempts')::INT, 0),
+       NOW(),
+       NOW()
+FROM failed
+RETURNING payload
+"#;
+
+/// Postgres queue driver backed by the `queue_jobs` table.
+///
+/// Workers claim jobs with `FOR UPDATE SKIP LOCKED`, delayed jobs wait for
+/// `available_at`, and `pg_notify` on `NOTIFY_CHANNEL` wakes idle workers.
+/// Unique keys are enforced by a unique index and released when the row is deleted.
+#[derive(Clone)]
+pub struct PgQueue {
+    pub db: sqlx::PgPool,
+    pub(crate) batch_store: Option<crate::queue::RedisQueue>,
+    pub(crate) events: Option<JobEvents>,
+}
+
+/// A job claimed by a worker, deleted or released once handled.
+pub(crate) struct Reservation {
+    pub id: uuid::Uuid,
+    pub payload: serde_json::Value,
+}
+
+impl PgQueue {
+    pub fn new(db: sqlx::PgPool) -> Self {
+        Self {
+            db,
+            batch_store: None,
+            events: None,
+        }
+    }
+
+    /// Keep `batch::JobBatch` counters in Redis; without it batches cannot be dispatched.
+    pub fn with_batch_store(mut self, store: crate::queue::RedisQueue) -> Self {
+        self.batch_store = Some(store);
+        self
+    }
+
+    /// Emit `Queued` into `events` for every job this queue accepts.
+    pub fn with_events(mut self, events: JobEvents) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    fn emit_queued(&self, payload: &JobPayload) {
+        if let Some(events) = &self.events {
+            events.emit(JobEvent::new(JobEventKind::Queued, payload));
+        }
+    }
+
+    /// Emit `Queued` for payloads moved in by SQL (outbox flush, failed-job retry).
+    fn emit_queued_values(&self, payloads: &[serde_json::Value]) {
+        if self.events.is_none() {
+            return;
+        }
+        for value in payloads {
+            if let Ok(payload) = serde_json::from_value::<JobPayload>(value.clone()) {
+                self.emit_queued(&payload);
+            }
+        }
+    }
+
+    /// Push a job for immediate processing.
+    /// Jobs with a `unique_key` that is already pending or running are skipped.
+    pub async fn push<J: Job>(&self, job: &J) -> anyhow::Result<()> {
+        let payload = JobPayload::from_job(job)?;
+        self.push_payload(&payload).await?;
+        Ok(())
+    }
+
+    /// Push a job that becomes available at `at`.
+    pub async fn push_at<J: Job>(&self, job: &J, at: OffsetDateTime) -> anyhow::Result<()> {
+        let payload = JobPayload::from_job(job)?;
+        self.schedule_payload(&payload, at).await?;
+        Ok(())
+    }
+
+    /// Push a job that becomes available after `delay`.
+    pub async fn push_after<J: Job>(
+        &self,
+        job: &J,
+        delay: std::time::Duration,
+    ) -> anyhow::Result<()> {
+        self.push_at(job, OffsetDateTime::now_utc() + delay).await
+    }
+
+    /// Enqueue an already-built payload for immediate processing.
+    /// Returns `false` when the payload's unique key is already held (nothing is pushed).
+    pub async fn push_payload(&self, payload: &JobPayload) -> anyhow::Result<bool> {
+        self.schedule_payload(payload, OffsetDateTime::now_utc())
+            .await
+    }
+
+    /// Insert an already-built payload that becomes available at `at`.
+    /// Returns `false` when the payload's unique key is already held (nothing is pushed).
+    pub async fn schedule_payload(
+        &self,
+        payload: &JobPayload,
+        at: OffsetDateTime,
+    ) -> anyhow::Result<bool> {
+        let now = OffsetDateTime::now_utc();
+        let inserted = sqlx::query(
+            "INSERT INTO queue_jobs (id, queue, group_id, unique_key, payload, attempts, available_at, queued_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+             ON CONFLICT (unique_key) DO NOTHING",
+        )
+        .bind(uuid::Uuid::new_v4())
+        .bind(&payload.queue)
+        .bind(&payload.group_id)
+        .bind(&payload.unique_key)
+        .bind(serde_json::to_value(payload.available_at(at.max(now)))?)
+        .bind(payload.attempts as i32)
+        .bind(at)
+        // Delayed ordered jobs join their group when due, like the Redis delayed set.
+        .bind(at.max(now))
+        .execute(&self.db)
+        .await?
+        .rows_affected()
+            > 0;
+
+        if !inserted {
+            tracing::debug!(
+                "Skipping duplicate job {} ({})",
+                payload.job,
+                payload.unique_key.as_deref().unwrap_or_default()
+            );
+        } else {
+            self.emit_queued(payload);
+            if at <= now {
+                self.notify(&payload.queue).await?;
+            }
+        }
+        Ok(inserted)
+    }
+
+    /// Move a failed job back onto its queue. Returns `false` if `id` is unknown.
+    pub async fn retry_failed(&self, id: uuid::Uuid) -> anyhow::Result<bool> {
+        let moved: Vec<serde_json::Value> = sqlx::query_scalar(RETRY_FAILED_SQL)
+            .bind(id)
+            .bind(uuid::Uuid::new_v4())
+            .fetch_all(&self.db)
+            .await?;
+        if moved.is_empty() {
+            return Ok(false);
+        }
+        self.emit_queued_values(&moved);
+        self.notify("").await?;
+        Ok(true)
+    }
+
+    /// Move committed `outbox_jobs` rows into `queue_jobs`. Returns the number queued.
+    pub async fn flush_outbox(&self) -> anyhow::Result<usize> {
+        let moved: Vec<serde_json::Value> = sqlx::query_scalar(FLUSH_OUTBOX_SQL)
+            .fetch_all(&self.db)
+            .await?;
+        if !moved.is_empty() {
+            self.emit_queued_values(&moved);
+            self.notify("").await?;
+        }
+        Ok(moved.len())
+    }
+
+    /// Claim the next available job of `queues` for `worker_id`.
+    pub(crate) async fn reserve(
+        &self,
+        queues: &[String],
+        worker_id: &str,
+    ) -> anyhow::Result<Option<Reservation>> {
+        let row: Option<(uuid::Uuid, serde_json::Value)> = sqlx::query_as(RESERVE_SQL)
+            .bind(queues)
+            .bind(worker_id)
+            .bind(RESERVATION_TTL_SECS)
+            .fetch_optional(&self.db)
+            .await?;
+        Ok(row.map(|(id, payload)| Reservation { id, payload }))
+    }
+
+    /// Remove a finished (or permanently failed) job.
+    pub(crate) async fn delete(&self, id: uuid::Uuid) -> anyhow::Result<()> {
+        sqlx::query("DELETE FROM queue_jobs WHERE id = $1")
+            .bind(id)
+            .execute(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    /// Hand a reserved job back with an updated payload, available again at `at`.
+    /// The job keeps its place in its group, so a retry pauses the group.
+    pub(crate) async fn release(
+        &self,
+        id: uuid::Uuid,
+        payload: &JobPayload,
+        at: OffsetDateTime,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            "UPDATE queue_jobs \
+             SET payload = $2, attempts = $3, available_at = $4, reserved_at = NULL, reserved_by = NULL \
+             WHERE id = $1",
+        )
+        .bind(id)
+        .bind(serde_json::to_value(payload)?)
+        .bind(payload.attempts as i32)
+        .bind(at)
+        .execute(&self.db)
+        .await?;
+        Ok(())
+    }
+
+    /// Refresh the reservations of every job `worker_id` is running.
+    pub(crate) async fn heartbeat(&self, worker_id: &str) -> anyhow::Result<()> {
+        sqlx::query("UPDATE queue_jobs SET reserved_at = NOW() WHERE reserved_by = $1")
+            .bind(worker_id)
+            .execute(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    /// Clear the reservations of `worker_id` (shutdown) so other workers pick the jobs up.
+    pub(crate) async fn release_worker(&self, worker_id: &str) -> anyhow::Result<u64> {
+        let released = sqlx::query(
+            "UPDATE queue_jobs SET reserved_at = NULL, reserved_by = NULL WHERE reserved_by = $1",
+        )
+        .bind(worker_id)
+        .execute(&self.db)
+        .await?
+        .rows_affected();
+        if released > 0 {
+            self.notify("").await?;
+        }
+        Ok(released)
+    }
+
+    /// Forward `NOTIFY_CHANNEL` notifications to `wake`, reconnecting on errors. Never returns.
+    pub(crate) async fn forward_notifications(&self, wake: Arc<tokio::sync::Notify>) {
+        loop {
+            let mut listener = match sqlx::postgres::PgListener::connect_with(&self.db).await {
+                Ok(listener) => listener,
+                Err(e) => {
+                    tracing::error!("Queue LISTEN connection failed: {}", e);
+                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                    continue;
+                }
+            };
+            if let Err(e) = listener.listen(NOTIFY_CHANNEL).await {
+                tracing::error!("Queue LISTEN failed: {}", e);
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                continue;
+            }
+
+            loop {
+                match listener.recv().await {
+                    Ok(_) => wake.notify_one(),
+                    Err(e) => {
+                        tracing::error!("Queue LISTEN error: {}", e);
+                        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn notify(&self, queue: &str) -> anyhow::Result<()> {
+        sqlx::query("SELECT pg_notify($1, $2)")
+            .bind(NOTIFY_CHANNEL)
+            .bind(queue)
+            .execute(&self.db)
+            .await?;
+        Ok(())
+    }
+}
@@ -1,11 +1,15 @@
-use crate::{Job, JobPayload};
-use redis::AsyncCommands;
+use crate::{
+    events::{JobEvent, JobEventKind, JobEvents},
+    Job, JobPayload,
+};
 use serde::Serialize;
+use time::OffsetDateTime;
 
 #[derive(Clone)]
 pub struct RedisQueue {
     pub client: redis::Client, // Using raw redis client for BLPOP
     pub prefix: String,
+    pub(crate) events: Option<JobEvents>,
 }
 
 impl RedisQueue {
@@ -18,6 +22,7 @@ impl RedisQueue {
         Ok(Self {
             client,
             prefix: normalize_prefix(prefix.as_ref()),
+            events: None,
         })
     }
 
@@ -29,39 +34,216 @@ impl RedisQueue {
         Self {
             client,
             prefix: normalize_prefix(prefix.as_ref()),
+            events: None,
         }
     }
 
+    /// Emit `Queued` into `events` for every payload this queue accepts.
+    pub fn with_events(mut self, events: JobEvents) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    pub(crate) fn emit_queued(&self, payload: &JobPayload) {
+        if let Some(events) = &self.events {
+            events.emit(JobEvent::new(JobEventKind::Queued, payload));
+        }
+    }
+
+    /// Push a job for immediate processing.
+    /// Jobs with a `unique_key` that is already pending or running are skipped.
     pub async fn push<J: Job + Serialize>(&self, job: &J) -> anyhow::Result<()> {
-        let payload = JobPayload {
-            job: J::NAME.to_string(),
-            data: serde_json::to_value(job)?,
-            queue: J::QUEUE.to_string(),
-            attempts: 0,
+        let payload = JobPayload::from_job(job)?;
+        self.push_payload(&payload).await?;
+        Ok(())
+    }
+
+    /// Push a job that becomes available at `at` (via the `{prefix}:scheduler` set).
+    pub async fn push_at<J: Job + Serialize>(
+        &self,
+        job: &J,
+        at: OffsetDateTime,
+    ) -> anyhow::Result<()> {
+        let payload = JobPayload::from_job(job)?;
+        self.schedule_payload(&payload, at).await?;
+        Ok(())
+    }
+
+    /// Push a job that becomes available after `delay`.
+    pub async fn push_after<J: Job + Serialize>(
+        &self,
+        job: &J,
+        delay: std::time::Duration,
+    ) -> anyhow::Result<()> {
+        self.push_at(job, OffsetDateTime::now_utc() + delay).await
+    }
+
+    /// Enqueue an already-built payload for immediate processing.
+    /// Returns `false` when the payload's unique key is already held (nothing is pushed).
+    pub async fn push_payload(&self, payload: &JobPayload) -> anyhow::Result<bool> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        if !self.claim_unique(&mut conn, payload, 0).await? {
+            return Ok(false);
+        }
+
+        let mut pipe = redis::pipe();
+        let pushed = match self.enqueue_into(&mut pipe, payload) {
+            Ok(()) => pipe.query_async::<()>(&mut conn).await.map_err(Into::into),
+            Err(e) => Err(e),
         };
-        let payload_str = serde_json::to_string(&payload)?;
+        if let Err(e) = pushed {
+            self.release_unique(payload.unique_key.as_slice()).await;
+            return Err(e);
+        }
+        self.emit_queued(payload);
+        Ok(true)
+    }
+
+    /// Schedule an already-built payload. Times in the past are enqueued immediately.
+    /// Returns `false` when the payload's unique key is already held (nothing is pushed).
+    pub async fn schedule_payload(
+        &self,
+        payload: &JobPayload,
+        at: OffsetDateTime,
+    ) -> anyhow::Result<bool> {
         let mut conn = self.client.get_multiplexed_async_connection().await?;
+        if !self
+            .claim_unique(&mut conn, payload, delay_secs(at))
+            .await?
+        {
+            return Ok(false);
+        }
+
+        let mut pipe = redis::pipe();
+        let scheduled = match self.schedule_into(&mut pipe, payload, at) {
+            Ok(()) => pipe.query_async::<()>(&mut conn).await.map_err(Into::into),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = scheduled {
+            self.release_unique(payload.unique_key.as_slice()).await;
+            return Err(e);
+        }
+        self.emit_queued(payload);
+        Ok(true)
+    }
 
-        if let Some(group) = job.group_id() {
+    /// Take the dedupe lock for `payload` if it has a unique key.
+    /// `extra_secs` extends the TTL for delayed payloads so the lock outlives the delay.
+    pub(crate) async fn claim_unique<C: redis::aio::ConnectionLike>(
+        &self,
+        conn: &mut C,
+        payload: &JobPayload,
+        extra_secs: u64,
+    ) -> anyhow::Result<bool> {
+        let Some(unique_key) = &payload.unique_key else {
+            return Ok(true);
+        };
+
+        let ttl = payload.unique_ttl.unwrap_or(3600).max(1) + extra_secs;
+        let acquired: bool = redis::cmd("SET")
+            .arg(self.unique_lock_key(unique_key))
+            .arg(&payload.job)
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl)
+            .query_async::<Option<String>>(conn)
+            .await?
+            .is_some();
+
+        if !acquired {
+            tracing::debug!("Skipping duplicate job {} ({})", payload.job, unique_key);
+        }
+        Ok(acquired)
+    }
+
+    /// Drop dedupe locks taken for payloads that never reached Redis, so the next
+    /// dispatch with the same unique keys is not skipped for the rest of the TTL.
+    pub(crate) async fn release_unique(&self, unique_keys: &[String]) {
+        if unique_keys.is_empty() {
+            return;
+        }
+        let keys: Vec<String> = unique_keys
+            .iter()
+            .map(|unique_key| self.unique_lock_key(unique_key))
+            .collect();
+        let released = match self.client.get_multiplexed_async_connection().await {
+            Ok(mut conn) => redis::cmd("DEL")
+                .arg(&keys)
+                .query_async::<()>(&mut conn)
+                .await
+                .map_err(anyhow::Error::from),
+            Err(e) => Err(e.into()),
+        };
+        if let Err(e) = released {
+            tracing::warn!("Failed to release unique job locks {:?}: {}", keys, e);
+        }
+    }
+
+    /// Append the commands that enqueue `payload` to `pipe`.
+    pub(crate) fn enqueue_into(
+        &self,
+        pipe: &mut redis::Pipeline,
+        payload: &JobPayload,
+    ) -> anyhow::Result<()> {
+        let payload_str = serde_json::to_string(payload)?;
+
+        if let Some(group) = &payload.group_id {
             // Ordered Queue Logic
-            let group_queue = format!("{}:{}:{}", self.prefix, J::QUEUE, group);
-            let meta_queue = format!("{}:{}:meta", self.prefix, J::QUEUE);
+            let group_queue = format!("{}:{}:{}", self.prefix, payload.queue, group);
+            let meta_queue = format!("{}:{}:meta", self.prefix, payload.queue);
 
             // 1. Push job to group content list
-            let _: () = conn.rpush(&group_queue, payload_str).await?;
+            pipe.rpush(&group_queue, payload_str).ignore();
 
             // 2. Notify meta queue that this group has work
             // Note: We might spam meta queue with same group ID multiple times.
             // Worker must handle this (e.g., if group is empty, ignore).
-            let _: () = conn.rpush(&meta_queue, group).await?;
+            pipe.rpush(&meta_queue, group).ignore();
         } else {
             // Standard Queue Logic
-            let key = format!("{}:{}", self.prefix, J::QUEUE);
-            let _: () = conn.rpush(&key, payload_str).await?;
+            let key = format!("{}:{}", self.prefix, payload.queue);
+            pipe.rpush(&key, payload_str).ignore();
+        }
+
+        Ok(())
+    }
+
+    /// Append the commands that schedule `payload` for `at` to `pipe`.
+    pub(crate) fn schedule_into(
+        &self,
+        pipe: &mut redis::Pipeline,
+        payload: &JobPayload,
+        at: OffsetDateTime,
+    ) -> anyhow::Result<()> {
+        if at <= OffsetDateTime::now_utc() {
+            return self.enqueue_into(pipe, payload);
         }
 
+        let payload_str = serde_json::to_string(&payload.available_at(at))?;
+        pipe.zadd(self.scheduler_key(), payload_str, at.unix_timestamp())
+            .ignore();
         Ok(())
     }
+
+    /// Sorted set holding delayed payloads, scored by unix timestamp (seconds).
+    pub fn scheduler_key(&self) -> String {
+        format!("{}:scheduler", self.prefix)
+    }
+
+    /// Redis hash holding the counters and callbacks of a `JobBatch`.
+    pub fn batch_key(&self, batch_id: &str) -> String {
+        format!("{}:batch:{}", self.prefix, batch_id)
+    }
+
+    /// Redis key of the dedupe lock for a payload's `unique_key`.
+    pub fn unique_lock_key(&self, unique_key: &str) -> String {
+        format!("{}:unique:{}", self.prefix, unique_key)
+    }
+}
+
+/// Seconds from now until `at` (0 when already due).
+pub(crate) fn delay_secs(at: OffsetDateTime) -> u64 {
+    (at - OffsetDateTime::now_utc()).whole_seconds().max(0) as u64
 }
 
 fn normalize_prefix(prefix: &str) -> String {
@@ -0,0 +1,206 @@
+use redis::AsyncCommands;
+use std::sync::LazyLock;
+
+/// Heartbeat refresh period of a reliable worker process.
+pub const HEARTBEAT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
+/// A worker whose heartbeat is older than this is considered dead.
+pub const HEARTBEAT_TTL_SECS: u64 = 30;
+/// How often the reaper looks for processing lists of dead workers.
+pub const REAPER_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+/// Sleep between polls when every queue is empty (reliable mode cannot block on several lists).
+pub const IDLE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Atomically pop the first non-empty source list (KEYS[2..]) and record the
+/// item in the processing list (KEYS[1]) together with its source.
+/// Returns `{source, payload, entry}` or nil.
+static FETCH_SCRIPT: LazyLock<redis::Script> = LazyLock::new(|| {
+    redis::Script::new(
+        r#"
+for i = 2, #KEYS do
+    local value = redis.call('LPOP', KEYS[i])
+    if value then
+        local entry = cjson.encode({source = KEYS[i], payload = value})
+        redis.call('RPUSH', KEYS[1], entry)
+        return {KEYS[i], value, entry}
+    end
+end
+return false
+"#,
+    )
+});
+
+/// Move every entry of a processing list (KEYS[1]) back to the head of its
+/// source list, newest first so the original order is kept. Returns the count.
+static REQUEUE_SCRIPT: LazyLock<redis::Script> = LazyLock::new(|| {
+    redis::Script::new(
+        r#"
+local count = 0
+while true do
+    local entry = redis.call('RPOP', KEYS[1])
+    if not entry then
+        break
+    end
+    local ok, decoded = pcall(cjson.decode, entry)
+    if ok and decoded.source and decoded.payload then
+        redis.call('LPUSH', decoded.source, decoded.payload)
+        count = count + 1
+    end
+end
+return count
+"#,
+    )
+});
+
+/// An item taken off a queue list.
+/// `entry` is the processing-list record to acknowledge (reliable mode only).
+pub(crate) struct Delivery {
+    pub source: String,
+    pub payload: String,
+    pub entry: Option<String>,
+}
+
+/// Per-process state of the reliable (at-least-once) delivery mode.
+///
+/// Items are moved into `{prefix}:processing:{worker_id}` when popped and removed
+/// once handled. A heartbeat key `{prefix}:worker:{worker_id}` is refreshed while
+/// the process lives; the reaper re-queues processing lists whose heartbeat expired.
+#[derive(Clone, Debug)]
+pub struct ReliableDelivery {
+    prefix: String,
+    worker_id: String,
+}
+
+impl ReliableDelivery {
+    pub fn new(prefix: &str) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+            worker_id: format!("{}-{}", std::process::id(), uuid::Uuid::new_v4().simple()),
+        }
+    }
+
+    pub fn worker_id(&self) -> &str {
+        &self.worker_id
+    }
+
+    pub fn processing_key(&self) -> String {
+        processing_key(&self.prefix, &self.worker_id)
+    }
+
+    fn heartbeat_key(&self, worker_id: &str) -> String {
+        format!("{}:worker:{}", self.prefix, worker_id)
+    }
+
+    fn registry_key(&self) -> String {
+        format!("{}:workers", self.prefix)
+    }
+
+    /// Pop the first available item of `sources` into this worker's processing list.
+    pub(crate) async fn fetch(
+        &self,
+        conn: &mut redis::aio::MultiplexedConnection,
+        sources: &[String],
+    ) -> redis::RedisResult<Option<Delivery>> {
+        let mut invocation = FETCH_SCRIPT.key(self.processing_key());
+        for source in sources {
+            invocation.key(source);
+        }
+        let result: Option<(String, String, String)> = invocation.invoke_async(conn).await?;
+        Ok(result.map(|(source, payload, entry)| Delivery {
+            source,
+            payload,
+            entry: Some(entry),
+        }))
+    }
+
+    /// Remove a handled item from the processing list.
+    pub(crate) async fn ack(
+        &self,
+        conn: &mut redis::aio::MultiplexedConnection,
+        delivery: &Delivery,
+    ) {
+        if let Some(entry) = &delivery.entry {
+            let result: redis::RedisResult<i64> = conn.lrem(self.processing_key(), 1, entry).await;
+            if let Err(e) = result {
+                tracing::error!("Failed to ack job from {}: {}", delivery.source, e);
+            }
+        }
+    }
+
+    /// Register this process and refresh its heartbeat.
+    pub async fn heartbeat(
+        &self,
+        conn: &mut redis::aio::MultiplexedConnection,
+    ) -> redis::RedisResult<()> {
+        redis::pipe()
+            .atomic()
+            .sadd(self.registry_key(), &self.worker_id)
+            .ignore()
+            .set_ex(
+                self.heartbeat_key(&self.worker_id),
+                now_unix(),
+                HEARTBEAT_TTL_SECS,
+            )
+            .ignore()
+            .query_async(conn)
+            .await
+    }
+
+    /// Re-queue items held by workers whose heartbeat expired. Returns the number re-queued.
+    pub async fn reap(&self, conn: &mut redis::aio::MultiplexedConnection) -> anyhow::Result<u64> {
+        let workers: Vec<String> = conn.smembers(self.registry_key()).await?;
+        let mut total = 0u64;
+
+        for worker_id in workers {
+            if worker_id == self.worker_id {
+                continue;
+            }
+            let alive: bool = conn.exists(self.heartbeat_key(&worker_id)).await?;
+            if alive {
+                continue;
+            }
+
+            let requeued: u64 = REQUEUE_SCRIPT
+                .key(processing_key(&self.prefix, &worker_id))
+                .invoke_async(conn)
+                .await?;
+            let _: () = conn.srem(self.registry_key(), &worker_id).await?;
+            if requeued > 0 {
+                tracing::warn!(
+                    "Re-queued {} orphaned job(s) from dead worker {}",
+                    requeued,
+                    worker_id
+                );
+            }
+            total += requeued;
+        }
+
+        Ok(total)
+    }
+
+    /// Re-queue anything left in this worker's own processing list and unregister it.
+    pub async fn release(
+        &self,
+        conn: &mut redis::aio::MultiplexedConnection,
+    ) -> anyhow::Result<u64> {
+        let requeued: u64 = REQUEUE_SCRIPT
+            .key(self.processing_key())
+            .invoke_async(conn)
+            .await?;
+        let _: () = redis::pipe()
+            .srem(self.registry_key(), &self.worker_id)
+            .ignore()
+            .del(self.heartbeat_key(&self.worker_id))
+            .ignore()
+            .query_async(conn)
+            .await?;
+        Ok(requeued)
+    }
+}
+
+fn processing_key(prefix: &str, worker_id: &str) -> String {
+    format!("{}:processing:{}", prefix, worker_id)
+}
+
+fn now_unix() -> i64 {
+    time::OffsetDateTime::now_utc().unix_timestamp()
+}
@@ -0,0 +1,301 @@
+//! Queue drivers for tests: `FakeQueue` records dispatched jobs, `SyncQueue` runs them inline.
+//! Both implement `driver::QueueDriver`, so `job.dispatch(&fake)` and
+//! `BootContext::jobs = Arc::new(fake.clone())` need no Redis or worker.
+
+use crate::{driver::QueueDriver, Job, JobContext, JobPayload};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, RwLock};
+use time::OffsetDateTime;
+
+/// A payload recorded by `FakeQueue` (or `SyncQueue`).
+#[derive(Debug, Clone)]
+pub struct PushedJob {
+    pub payload: JobPayload,
+    /// Requested run time for `dispatch_at` / `dispatch_after`; `None` for immediate pushes.
+    pub at: Option<OffsetDateTime>,
+}
+
+/// Records every dispatched job instead of enqueueing it. Clones share the record.
+#[derive(Clone, Default)]
+pub struct FakeQueue {
+    pushed: Arc<Mutex<Vec<PushedJob>>>,
+}
+
+impl FakeQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Everything pushed so far, in dispatch order.
+    pub fn pushed_jobs(&self) -> Vec<PushedJob> {
+        self.pushed
+            .lock()
+            .map(|pushed| pushed.clone())
+            .unwrap_or_default()
+    }
+
+    /// Decoded jobs of type `J`, in dispatch order.
+    /// Panics if a payload named `J::NAME` does not decode as `J`.
+    #[track_caller]
+    pub fn pushed<J: Job>(&self) -> Vec<J> {
+        self.pushed_jobs()
+            .into_iter()
+            .filter(|pushed| pushed.payload.job == J::NAME)
+            .map(|pushed| decode_pushed(&pushed.payload))
+            .collect()
+    }
+
+    /// Forget everything recorded so far.
+    pub fn clear(&self) {
+        if let Ok(mut pushed) = self.pushed.lock() {
+            pushed.clear();
+        }
+    }
+
+    /// Panics unless a `J` matching `matches` was pushed.
+    #[track_caller]
+    pub fn assert_pushed<J: Job>(&self, matches: impl Fn(&J) -> bool) {
+        if !self.pushed::<J>().iter().any(matches) {
+            panic!(
+                "expected a matching {} to be pushed; pushed: {:?}",
+                J::NAME,
+                self.pushed_names()
+            );
+        }
+    }
+
+    /// Panics unless a `J` matching `matches` was pushed onto `queue`.
+    #[track_caller]
+    pub fn assert_pushed_on_queue<J: Job>(&self, queue: &str, matches: impl Fn(&J) -> bool) {
+        let found = self
+            .pushed_jobs()
+            .into_iter()
+            .filter(|pushed| pushed.payload.job == J::NAME && pushed.payload.queue == queue)
+            .map(|pushed| decode_pushed::<J>(&pushed.payload))
+            .any(|job| matches(&job));
+        if !found {
+            panic!(
+                "expected a matching {} to be pushed on queue '{}'; pushed: {:?}",
+                J::NAME,
+                queue,
+                self.pushed_names()
+            );
+        }
+    }
+
+    /// Panics unless exactly `times` jobs of type `J` were pushed.
+    #[track_caller]
+    pub fn assert_pushed_times<J: Job>(&self, times: usize) {
+        let count = self.pushed::<J>().len();
+        if count != times {
+            panic!(
+                "expected {} to be pushed {} time(s), got {}",
+                J::NAME,
+                times,
+                count
+            );
+        }
+    }
+
+    /// Panics if any job of type `J` was pushed.
+    #[track_caller]
+    pub fn assert_not_pushed<J: Job>(&self) {
+        self.assert_pushed_times::<J>(0);
+    }
+
+    /// Panics if anything was pushed.
+    #[track_caller]
+    pub fn assert_nothing_pushed(&self) {
+        let names = self.pushed_names();
+        if !names.is_empty() {
+            panic!("expected no jobs to be pushed; pushed: {:?}", names);
+        }
+    }
+
+    fn record(&self, payload: &JobPayload, at: Option<OffsetDateTime>) {
+        if let Ok(mut pushed) = self.pushed.lock() {
+            pushed.push(PushedJob {
+                payload: payload.clone(),
+                at,
+            });
+        }
+    }
+
+    fn pushed_names(&self) -> Vec<String> {
+        self.pushed_jobs()
+            .into_iter()
+            .map(|pushed| format!("{}@{}", pushed.payload.job, pushed.payload.queue))
+            .collect()
+    }
+}
+
+/// A payload recorded under `J::NAME` that does not decode means the test pushed a
+/// different shape than it asserts on; fail loudly instead of reporting it as missing.
+#[track_caller]
+fn decode_pushed<J: Job>(payload: &JobPayload) -> J {
+    match payload.decode() {
+        Ok(job) => job,
+        Err(e) => panic!("pushed {} payload does not decode: {e:#}", J::NAME),
+    }
+}
+
+#[async_trait]
+impl QueueDriver for FakeQueue {
+    async fn push_payload(&self, payload: &JobPayload) -> anyhow::Result<bool> {
+        self.record(payload, None);
+        Ok(true)
+    }
+
+    async fn schedule_payload(
+        &self,
+        payload: &JobPayload,
+        at: OffsetDateTime,
+    ) -> anyhow::Result<bool> {
+        self.record(payload, Some(at));
+        Ok(true)
+    }
+
+    fn batch_store(&self) -> crate::driver::BatchStore<'_> {
+        crate::driver::BatchStore::Untracked
+    }
+}
+
+type Runner = Arc<
+    dyn Fn(JobPayload, JobContext) -> Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send>>
+        + Send
+        + Sync,
+>;
+
+/// Runs dispatched jobs immediately against `context`, without retries or middlewares.
+///
+/// Delayed dispatches run right away too, and chains continue inline. A failing job makes
+/// `dispatch` return its error. Every push is also recorded; assert on `recorded()`.
+#[derive(Clone)]
+pub struct SyncQueue {
+    context: JobContext,
+    runners: Arc<RwLock<HashMap<&'static str, Runner>>>,
+    recorded: FakeQueue,
+}
+
+impl SyncQueue {
+    pub fn new(context: JobContext) -> Self {
+        Self {
+            context,
+            runners: Arc::default(),
+            recorded: FakeQueue::new(),
+        }
+    }
+
+    /// Run `J` inline when it is dispatched. Unregistered jobs fail the dispatch.
+    pub fn register<J: Job>(&self) -> &Self {
+        let runner: Runner = Arc::new(|payload, ctx| {
+            Box::pin(async move {
+                let job: J = payload.decode()?;
+                job.handle(&ctx).await
+            })
+        });
+        if let Ok(mut runners) = self.runners.write() {
+            runners.insert(J::NAME, runner);
+        }
+        self
+    }
+
+    pub fn context(&self) -> &JobContext {
+        &self.context
+    }
+
+    /// Every payload dispatched through this queue, including chained jobs.
+    pub fn recorded(&self) -> &FakeQueue {
+        &self.recorded
+    }
+
+    async fn run(&self, payload: &JobPayload) -> anyhow::Result<()> {
+        let runner = self
+            .runners
+            .read()
+            .ok()
+            .and_then(|runners| runners.get(payload.job.as_str()).cloned())
+            .ok_or_else(|| anyhow::anyhow!("Job {} is not registered on SyncQueue", payload.job))?;
+        runner(payload.clone(), self.context.clone())
+            .await
+            .map_err(|e| e.context(format!("Job {} failed", payload.job)))?;
+        crate::batch::continue_chain(self, payload).await
+    }
+}
+
+#[async_trait]
+impl QueueDriver for SyncQueue {
+    async fn push_payload(&self, payload: &JobPayload) -> anyhow::Result<bool> {
+        self.recorded.record(payload, None);
+        self.run(payload).await?;
+        Ok(true)
+    }
+
+    async fn schedule_payload(
+        &self,
+        payload: &JobPayload,
+        at: OffsetDateTime,
+    ) -> anyhow::Result<bool> {
+        self.recorded.record(payload, Some(at));
+        self.run(payload).await?;
+        Ok(true)
+    }
+
+    fn batch_store(&self) -> crate::driver::BatchStore<'_> {
+        crate::driver::BatchStore::Untracked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FakeQueue;
+    use crate::{driver::QueueDriver, Job, JobContext, JobPayload};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct SendWelcome {
+        email: String,
+    }
+
+    #[async_trait::async_trait]
+    impl Job for SendWelcome {
+        const NAME: &'static str = "SendWelcome";
+
+        async fn handle(&self, _ctx: &JobContext) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn pushed_decodes_matching_jobs() {
+        let queue = FakeQueue::new();
+        let job = SendWelcome {
+            email: "ada@example.com".to_string(),
+        };
+        queue
+            .push_payload(&JobPayload::from_job(&job).expect("payload"))
+            .await
+            .expect("push");
+
+        let pushed = queue.pushed::<SendWelcome>();
+        assert_eq!(pushed.len(), 1);
+        assert_eq!(pushed[0].email, "ada@example.com");
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "pushed SendWelcome payload does not decode")]
+    async fn pushed_panics_on_undecodable_payload() {
+        let queue = FakeQueue::new();
+        let payload: JobPayload = serde_json::from_value(serde_json::json!({
+            "job": "SendWelcome",
+            "data": { "address": "ada@example.com" },
+        }))
+        .expect("payload");
+        queue.push_payload(&payload).await.expect("push");
+
+        queue.pushed::<SendWelcome>();
+    }
+}
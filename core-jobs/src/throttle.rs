@@ -0,0 +1,129 @@
+use std::sync::LazyLock;
+use std::time::Duration;
+
+/// Seconds a job waits in the delayed set when its concurrency cap is reached.
+const CONCURRENCY_RETRY_SECS: u64 = 2;
+
+/// Fixed-window counter. Returns 0 when the call is allowed, otherwise the
+/// milliseconds left until the window resets.
+static RATE_SCRIPT: LazyLock<redis::Script> = LazyLock::new(|| {
+    redis::Script::new(
+        r#"
+local count = redis.call('INCR', KEYS[1])
+if count == 1 then
+    redis.call('PEXPIRE', KEYS[1], ARGV[2])
+end
+if count > tonumber(ARGV[1]) then
+    local ttl = redis.call('PTTL', KEYS[1])
+    if ttl < 0 then
+        redis.call('PEXPIRE', KEYS[1], ARGV[2])
+        ttl = tonumber(ARGV[2])
+    end
+    return ttl
+end
+return 0
+"#,
+    )
+});
+
+/// Take a slot in a sorted set of running jobs scored by lease expiry
+/// (expired leases of crashed workers are dropped first). Returns 1 when acquired.
+static SLOT_SCRIPT: LazyLock<redis::Script> = LazyLock::new(|| {
+    redis::Script::new(
+        r#"
+redis.call('ZREMRANGEBYSCORE', KEYS[1], '-inf', ARGV[1])
+if redis.call('ZCARD', KEYS[1]) < tonumber(ARGV[3]) then
+    redis.call('ZADD', KEYS[1], ARGV[2], ARGV[4])
+    redis.call('EXPIRE', KEYS[1], ARGV[5])
+    return 1
+end
+return 0
+"#,
+    )
+});
+
+/// Throttles declared by a job (`Job::rate_limit`, `Job::max_concurrency`),
+/// shared by every worker process through Redis under `Job::throttle_key`.
+#[derive(Clone, Debug)]
+pub struct Throttle {
+    pub key: String,
+    pub rate_limit: Option<(u32, Duration)>,
+    pub max_concurrency: Option<u32>,
+    /// How long a concurrency slot survives if the worker holding it dies.
+    pub lease: Duration,
+}
+
+pub(crate) enum Admission {
+    Granted(Option<Slot>),
+    /// Try again after this many seconds.
+    Denied(u64),
+}
+
+/// A held concurrency slot; release it once the job finished.
+pub(crate) struct Slot {
+    key: String,
+    token: String,
+}
+
+impl Throttle {
+    pub(crate) async fn acquire(
+        &self,
+        prefix: &str,
+        conn: &mut redis::aio::MultiplexedConnection,
+    ) -> redis::RedisResult<Admission> {
+        let slot = match self.max_concurrency {
+            Some(max) => {
+                let key = format!("{}:throttle:slots:{}", prefix, self.key);
+                let token = uuid::Uuid::new_v4().to_string();
+                let now = time::OffsetDateTime::now_utc().unix_timestamp();
+                let lease = self.lease.as_secs().max(1);
+                let acquired: i64 = SLOT_SCRIPT
+                    .key(&key)
+                    .arg(now)
+                    .arg(now + lease as i64)
+                    .arg(max)
+                    .arg(&token)
+                    .arg(lease)
+                    .invoke_async(conn)
+                    .await?;
+                if acquired == 0 {
+                    return Ok(Admission::Denied(CONCURRENCY_RETRY_SECS));
+                }
+                Some(Slot { key, token })
+            }
+            None => None,
+        };
+
+        if let Some((limit, window)) = self.rate_limit {
+            let key = format!("{}:throttle:rate:{}", prefix, self.key);
+            let window_ms = window.as_millis().max(1) as u64;
+            let wait_ms: u64 = RATE_SCRIPT
+                .key(&key)
+                .arg(limit)
+                .arg(window_ms)
+                .invoke_async(conn)
+                .await?;
+            if wait_ms > 0 {
+                if let Some(slot) = slot {
+                    slot.release(conn).await;
+                }
+                return Ok(Admission::Denied(wait_ms.div_ceil(1000).max(1)));
+            }
+        }
+
+        Ok(Admission::Granted(slot))
+    }
+}
+
+impl Slot {
+    pub(crate) async fn release(&self, conn: &mut redis::aio::MultiplexedConnection) {
+        let result: redis::RedisResult<i64> = redis::cmd("ZREM")
+            .arg(&self.key)
+            .arg(&self.token)
+            .query_async(conn)
+            .await;
+        if let Err(e) = result {
+            tracing::error!("Failed to release throttle slot {}: {}", self.key, e);
+        }
+    }
+}
@@ -22,9 +22,12 @@ where
                 .expect("Failed to create worker DB pool");
 
             let redis: core_db::infra::cache::Cache =
-                core_db::infra::cache::create_cache(&worker_settings.redis)
-                    .await
-                    .expect("Failed to create worker Redis cache");
+                if worker_settings.worker.driver == "postgres" {
+                    core_db::infra::cache::lazy_cache(&worker_settings.redis)
+                } else {
+                    core_db::infra::cache::create_cache(&worker_settings.redis).await
+                }
+                .expect("Failed to create worker Redis cache");
 
             if let Err(e) = runner(db, redis).await {
                 tracing::error!("Embedded worker crashed: {e}");
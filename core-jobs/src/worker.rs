@@ -6,9 +6,13 @@ use core_db::{
 };
 use redis::AsyncCommands;
 use redis::AsyncConnectionConfig;
-use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use crate::events::{JobEvent, JobEventKind, JobEvents, JobListener};
+use crate::middleware::JobMiddleware;
+use crate::postgres::PgQueue;
+use crate::reliable::{Delivery, ReliableDelivery};
+use crate::throttle::{Admission, Throttle};
 use crate::JobPayload;
 
 /// Result of execution
@@ -19,6 +23,11 @@ pub enum JobResult {
         max_retries: u32,
         err: String,
     },
+    /// Over a rate limit or concurrency cap: run again after `retry_after` seconds
+    /// without counting an attempt.
+    Throttled {
+        retry_after: u64,
+    },
 }
 
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -32,12 +41,19 @@ struct DelayedGroupSignal {
 trait JobHandler: Send + Sync {
     async fn execute(
         &self,
-        json: Value,
+        wrapper: &JobPayload,
         ctx: &JobContext,
-        attempts: u32,
+        middlewares: &[Arc<dyn JobMiddleware>],
     ) -> anyhow::Result<JobResult>;
 
-    async fn on_failed(&self, json: Value, ctx: &JobContext, error: &str) -> anyhow::Result<()>;
+    async fn on_failed(
+        &self,
+        wrapper: &JobPayload,
+        ctx: &JobContext,
+        error: &str,
+    ) -> anyhow::Result<()>;
+
+    fn throttle(&self, wrapper: &JobPayload) -> Option<Throttle>;
 }
 
 struct JobShim<J>(std::marker::PhantomData<J>);
@@ -46,25 +62,61 @@ struct JobShim<J>(std::marker::PhantomData<J>);
 impl<J: Job> JobHandler for JobShim<J> {
     async fn execute(
         &self,
-        json: Value,
+        wrapper: &JobPayload,
         ctx: &JobContext,
-        attempts: u32,
+        middlewares: &[Arc<dyn JobMiddleware>],
     ) -> anyhow::Result<JobResult> {
-        let job: J = serde_json::from_value(json)?;
-        match job.handle(ctx).await {
+        let job: J = wrapper.decode()?;
+        let handle = Box::pin(async {
+            match job.timeout() {
+                Some(limit) => match tokio::time::timeout(limit, job.handle(ctx)).await {
+                    Ok(result) => result,
+                    Err(_) => Err(anyhow::anyhow!(
+                        "Job timed out after {}s",
+                        limit.as_secs_f64()
+                    )),
+                },
+                None => job.handle(ctx).await,
+            }
+        });
+        let result = crate::middleware::run(middlewares, wrapper, ctx, handle).await;
+        match result {
             Ok(_) => Ok(JobResult::Success),
             Err(e) => Ok(JobResult::Failure {
-                backoff: job.backoff(attempts),
+                backoff: job.backoff(wrapper.attempts),
                 max_retries: job.max_retries(),
                 err: e.to_string(),
             }),
         }
     }
 
-    async fn on_failed(&self, json: Value, ctx: &JobContext, error: &str) -> anyhow::Result<()> {
-        let job: J = serde_json::from_value(json)?;
+    async fn on_failed(
+        &self,
+        wrapper: &JobPayload,
+        ctx: &JobContext,
+        error: &str,
+    ) -> anyhow::Result<()> {
+        let job: J = wrapper.decode()?;
         job.failed(ctx, error).await
     }
+
+    fn throttle(&self, wrapper: &JobPayload) -> Option<Throttle> {
+        let job: J = wrapper.decode().ok()?;
+        let rate_limit = job.rate_limit();
+        let max_concurrency = job.max_concurrency();
+        if rate_limit.is_none() && max_concurrency.is_none() {
+            return None;
+        }
+        Some(Throttle {
+            key: job.throttle_key(),
+            rate_limit,
+            max_concurrency,
+            lease: job
+                .timeout()
+                .map(|limit| limit + std::time::Duration::from_secs(60))
+                .unwrap_or(std::time::Duration::from_secs(3600)),
+        })
+    }
 }
 
 use std::sync::Arc;
@@ -77,6 +129,12 @@ pub struct Worker {
     context: JobContext,
     prefix: String,
     sweeper_config: Option<(sqlx::PgPool, std::time::Duration)>,
+    reliable: Option<ReliableDelivery>,
+    postgres: Option<PgQueue>, // Some: Postgres queue driver instead of Redis
+    middlewares: Vec<Arc<dyn JobMiddleware>>,
+    held_locks: Arc<std::sync::Mutex<HashSet<String>>>,
+    paused: Arc<std::sync::RwLock<HashSet<String>>>, // reloaded from `paused_queues`
+    events: JobEvents,
     config: Option<WorkerInternalConfig>,
 }
 
@@ -84,6 +142,7 @@ pub struct Worker {
 pub struct WorkerInternalConfig {
     pub concurrency: usize,
     pub sweep_interval: std::time::Duration,
+    pub shutdown_grace: std::time::Duration,
     pub redis_url: String,
 }
 
@@ -97,6 +156,12 @@ impl Worker {
             context,
             prefix: "queue".to_string(),
             sweeper_config: None,
+            reliable: None,
+            postgres: None,
+            middlewares: Vec::new(),
+            held_locks: Arc::default(),
+            paused: Arc::default(),
+            events: JobEvents::default(),
             config: None,
         })
     }
@@ -134,18 +199,36 @@ impl Worker {
             context: context.clone(),
             prefix: queue_prefix,
             sweeper_config: None,
+            reliable: None,
+            postgres: None,
+            middlewares: Vec::new(),
+            held_locks: Arc::default(),
+            paused: Arc::default(),
+            events: JobEvents::default(),
             config: Some(WorkerInternalConfig {
                 concurrency: config.concurrency,
                 sweep_interval: std::time::Duration::from_secs(config.sweep_interval),
+                shutdown_grace: std::time::Duration::from_secs(config.shutdown_grace),
                 redis_url: redis_url,
             }),
         };
 
         // Enable sweeper by default with config settings
         worker.enable_outbox_sweeper(
-            context.db,
+            context.db.clone(),
             std::time::Duration::from_secs(config.sweep_interval),
         );
+        match config.driver.as_str() {
+            "redis" => {
+                if config.reliable {
+                    worker.enable_reliable_delivery();
+                }
+            }
+            "postgres" => worker.use_postgres_driver(context.db),
+            other => anyhow::bail!("Unknown queue driver '{other}' (expected redis or postgres)"),
+        }
+        worker.middleware(crate::middleware::TracingMiddleware);
+        worker.middleware(crate::middleware::LocaleMiddleware);
 
         Ok(worker)
     }
@@ -162,14 +245,66 @@ impl Worker {
         }
     }
 
+    /// Wrap every job execution with `middleware`. Registration order is outermost first.
+    pub fn middleware<M: JobMiddleware>(&mut self, middleware: M) -> &mut Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    /// Receive lifecycle events (started, succeeded, retried, failed) of every job.
+    pub fn listen<L: JobListener>(&mut self, listener: L) -> &mut Self {
+        self.events.listen(listener);
+        self
+    }
+
+    /// Emit into a shared hub, e.g. one the web process serves metrics from.
+    /// Call before `listen`; listeners added earlier stay on the previous hub.
+    pub fn set_events(&mut self, events: JobEvents) -> &mut Self {
+        if let Some(pg) = &mut self.postgres {
+            pg.events = Some(events.clone());
+        }
+        self.events = events;
+        self
+    }
+
+    /// Event hub of this worker; `metrics_snapshot()` reads throughput and latency.
+    pub fn events(&self) -> &JobEvents {
+        &self.events
+    }
+
     pub fn context(&self) -> &JobContext {
         &self.context
     }
 
+    /// Token handed to jobs as `JobContext::cancellation`.
+    /// Cancelling it shuts the worker down: no new jobs are pulled and running jobs
+    /// get the configured grace period to finish (see `run_concurrent`).
+    pub fn cancellation_token(&self) -> tokio_util::sync::CancellationToken {
+        self.context.cancellation.clone()
+    }
+
     pub fn enable_outbox_sweeper(&mut self, db: sqlx::PgPool, interval: std::time::Duration) {
         self.sweeper_config = Some((db, interval));
     }
 
+    /// Track popped jobs in a per-worker processing list so a crash never loses them.
+    /// Orphaned jobs of dead workers are re-queued by the reaper.
+    pub fn enable_reliable_delivery(&mut self) {
+        self.reliable = Some(ReliableDelivery::new(&self.prefix));
+    }
+
+    /// Pull jobs from the `queue_jobs` table instead of Redis (`[worker] driver = "postgres"`).
+    /// The outbox sweeper then flushes into the same table.
+    /// Throttles and batch counters still go through Redis, which is only connected once a
+    /// throttled or batched job runs; other jobs never touch it.
+    pub fn use_postgres_driver(&mut self, db: sqlx::PgPool) {
+        self.postgres = Some(
+            PgQueue::new(db)
+                .with_batch_store(self.queue())
+                .with_events(self.events.clone()),
+        );
+    }
+
     pub async fn run(self) -> anyhow::Result<()> {
         let concurrency = self.config.as_ref().map(|c| c.concurrency).unwrap_or(10);
         self.run_concurrent(concurrency).await
@@ -185,16 +320,28 @@ impl Worker {
         let mut consecutive_errors: u32 = 0;
 
         loop {
+            if self.context.is_cancelled() {
+                return Ok(());
+            }
+
             let mut keys = Vec::new();
-            for q in &self.queues {
+            for q in self.active_queues() {
                 // Standard queue: queue:default
                 keys.push(format!("{}:{}", self.prefix, q));
                 // Meta queue: queue:default:meta
                 keys.push(format!("{}:{}:meta", self.prefix, q));
             }
 
-            // BLPOP (blocking pop with timeout)
-            let result: Option<(String, String)> = match conn.blpop(&keys, 5.0).await {
+            if keys.is_empty() {
+                // Every queue is paused.
+                tokio::select! {
+                    _ = tokio::time::sleep(crate::manager::PAUSE_REFRESH_INTERVAL) => {}
+                    _ = self.context.cancelled() => return Ok(()),
+                }
+                continue;
+            }
+
+            let result = match self.fetch(&mut conn, &keys).await {
                 Ok(result) => {
                     consecutive_errors = 0;
                     result
@@ -202,11 +349,7 @@ impl Worker {
                 Err(err) => {
                     consecutive_errors += 1;
                     let backoff_secs = std::cmp::min(consecutive_errors, 30);
-                    tracing::error!(
-                        "Worker BLPOP error (retry in {}s): {}",
-                        backoff_secs,
-                        err
-                    );
+                    tracing::error!("Worker fetch error (retry in {}s): {}", backoff_secs, err);
                     tokio::time::sleep(std::time::Duration::from_secs(backoff_secs as u64)).await;
 
                     // Reconnect
@@ -221,10 +364,11 @@ impl Worker {
                 }
             };
 
-            if let Some((source_queue, payload_str)) = result {
+            if let Some(delivery) = result {
+                let source_queue = delivery.source.as_str();
                 // Check if it's a Meta Queue (Ordered Group)
                 if source_queue.ends_with(":meta") {
-                    let group_id = payload_str; // Payload is just the group string
+                    let group_id = delivery.payload.clone(); // Payload is just the group string
 
                     // Derive base queue name from source_queue (remove :meta)
                     // source_queue: queue:default:meta -> base: queue:default
@@ -251,19 +395,30 @@ impl Worker {
 
                     if is_locked {
                         tracing::info!("Locked group: {}, draining...", group_id);
+                        self.hold_lock(&lock_key, true);
 
                         // Drain Loop
                         loop {
-                            let item: Option<String> = conn.lpop(&group_list, None).await.ok();
-                            if let Some(payload_s) = item {
-                                let mut wrapper: JobPayload = match serde_json::from_str(&payload_s)
-                                {
-                                    Ok(w) => w,
-                                    Err(_) => continue, // Bad payload, drop
-                                };
-
-                                match self.process_wrapper(&wrapper).await {
+                            if self.context.is_cancelled() {
+                                // Shutting down: hand the rest of the group to another worker.
+                                let _: () = conn.rpush(source_queue, &group_id).await.unwrap_or(());
+                                break;
+                            }
+                            let item = self.fetch_group_item(&mut conn, &group_list).await;
+                            if let Some(item) = item {
+                                let mut wrapper: JobPayload =
+                                    match serde_json::from_str(&item.payload) {
+                                        Ok(w) => w,
+                                        Err(_) => {
+                                            // Bad payload, drop
+                                            self.ack(&mut conn, &item).await;
+                                            continue;
+                                        }
+                                    };
+
+                                match self.process_locked(&mut conn, &lock_key, &wrapper).await {
                                     Ok(JobResult::Success) => {
+                                        self.complete(&mut conn, &wrapper, true).await;
                                         let _: () = redis::cmd("EXPIRE")
                                             .arg(&lock_key)
                                             .arg(60)
@@ -280,6 +435,8 @@ impl Worker {
                                         if wrapper.attempts < max_retries {
                                             tracing::info!("Retrying in {}s...", backoff);
                                             wrapper.attempts += 1;
+                                            wrapper.available_at_ms =
+                                                Some((unix_now() + backoff) * 1000);
                                             let new_payload = serde_json::to_string(&wrapper)?;
 
                                             // 1. Push back to HEAD (Maintain Order)
@@ -308,6 +465,7 @@ impl Worker {
                                                 .unwrap_or(());
 
                                             // 3. Break (Release Lock)
+                                            self.ack(&mut conn, &item).await;
                                             break;
                                         } else {
                                             tracing::error!(
@@ -319,11 +477,7 @@ impl Worker {
                                                 self.registry.get(wrapper.job.as_str())
                                             {
                                                 if let Err(e) = handler
-                                                    .on_failed(
-                                                        wrapper.data.clone(),
-                                                        &self.context,
-                                                        &err,
-                                                    )
+                                                    .on_failed(&wrapper, &self.context, &err)
                                                     .await
                                                 {
                                                     tracing::error!(
@@ -335,16 +489,55 @@ impl Worker {
                                             // Permanently Failed
                                             self.persist_failure(&wrapper, Some(&group_id), &err)
                                                 .await;
+                                            self.complete(&mut conn, &wrapper, false).await;
                                         }
                                     }
+                                    Ok(JobResult::Throttled { retry_after }) => {
+                                        tracing::debug!(
+                                            "Job {} throttled (Group {}), retry in {}s",
+                                            wrapper.job,
+                                            group_id,
+                                            retry_after
+                                        );
+                                        // Back to HEAD and pause the group, like a retry without the attempt.
+                                        if let Err(e) =
+                                            conn.lpush::<_, _, ()>(&group_list, &item.payload).await
+                                        {
+                                            tracing::error!(
+                                                "Failed to requeue throttled job {} (Group {}): {}",
+                                                wrapper.job,
+                                                group_id,
+                                                e
+                                            );
+                                            break;
+                                        }
+                                        let signal = serde_json::to_string(&DelayedGroupSignal {
+                                            queue: queue_name.clone(),
+                                            group_id: group_id.clone(),
+                                        })?;
+                                        let _: () = conn
+                                            .zadd(
+                                                format!("{}:scheduler:groups", self.prefix),
+                                                signal,
+                                                unix_now() + retry_after,
+                                            )
+                                            .await
+                                            .unwrap_or(());
+                                        self.ack(&mut conn, &item).await;
+                                        break;
+                                    }
                                     Err(e) => {
                                         tracing::error!("System error processing job: {}", e);
                                         // Push back?
-                                        let _: () =
-                                            conn.lpush(&group_list, payload_s).await.unwrap_or(());
+                                        let _: () = conn
+                                            .lpush(&group_list, &item.payload)
+                                            .await
+                                            .unwrap_or(());
+                                        self.ack(&mut conn, &item).await;
                                         break;
                                     }
                                 }
+                                self.ack(&mut conn, &item).await;
                             } else {
                                 // Empty list
                                 break;
@@ -352,19 +545,24 @@ impl Worker {
                         }
                         // Unlock
                         let _: () = conn.del(&lock_key).await.unwrap_or(());
+                        self.hold_lock(&lock_key, false);
                         tracing::info!("Unlocked group: {}", group_id);
                     }
+                    self.ack(&mut conn, &delivery).await;
                 } else {
                     // --- STANDARD QUEUE ---
-                    let mut wrapper: JobPayload = match serde_json::from_str(&payload_str) {
+                    let mut wrapper: JobPayload = match serde_json::from_str(&delivery.payload) {
                         Ok(w) => w,
                         Err(e) => {
                             tracing::error!("Bad payload: {}", e);
+                            self.ack(&mut conn, &delivery).await;
                             continue;
                         }
                     };
-                    match self.process_wrapper(&wrapper).await {
-                        Ok(JobResult::Success) => {}
+                    match self.process_wrapper(Some(&mut conn), &wrapper).await {
+                        Ok(JobResult::Success) => {
+                            self.complete(&mut conn, &wrapper, true).await;
+                        }
                         Ok(JobResult::Failure {
                             backoff,
                             max_retries,
@@ -373,6 +571,7 @@ impl Worker {
                             tracing::error!("Standard Job failed: {}", err);
                             if wrapper.attempts < max_retries {
                                 wrapper.attempts += 1;
+                                wrapper.available_at_ms = Some((unix_now() + backoff) * 1000);
                                 let new_payload = serde_json::to_string(&wrapper)?;
                                 let now = std::time::SystemTime::now()
                                     .duration_since(std::time::UNIX_EPOCH)?
@@ -387,26 +586,369 @@ impl Worker {
                             } else {
                                 // Call job's failed() callback
                                 if let Some(handler) = self.registry.get(wrapper.job.as_str()) {
-                                    if let Err(e) = handler
-                                        .on_failed(wrapper.data.clone(), &self.context, &err)
-                                        .await
+                                    if let Err(e) =
+                                        handler.on_failed(&wrapper, &self.context, &err).await
                                     {
                                         tracing::error!("Job failed() callback error: {}", e);
                                     }
                                 }
                                 // Permanently Failed
                                 self.persist_failure(&wrapper, None, &err).await;
+                                self.complete(&mut conn, &wrapper, false).await;
+                            }
+                        }
+                        Ok(JobResult::Throttled { retry_after }) => {
+                            tracing::debug!(
+                                "Job {} throttled, retry in {}s",
+                                wrapper.job,
+                                retry_after
+                            );
+                            // Back to the delayed set, attempts unchanged. Only ack once it is
+                            // there, so a failed ZADD leaves it for the reaper.
+                            let at = time::OffsetDateTime::now_utc()
+                                + time::Duration::seconds(retry_after as i64);
+                            let rescheduled = match serde_json::to_string(&wrapper.available_at(at))
+                            {
+                                Ok(payload) => conn
+                                    .zadd::<_, _, _, ()>(
+                                        format!("{}:scheduler", self.prefix),
+                                        payload,
+                                        at.unix_timestamp(),
+                                    )
+                                    .await
+                                    .map_err(anyhow::Error::from),
+                                Err(e) => Err(e.into()),
+                            };
+                            if let Err(e) = rescheduled {
+                                tracing::error!(
+                                    "Failed to reschedule throttled job {}: {}",
+                                    wrapper.job,
+                                    e
+                                );
+                                continue;
                             }
                         }
                         Err(e) => {
                             tracing::error!("System error: {}", e);
+                            // The payload is dropped: unblock duplicates and settle its batch.
+                            self.complete(&mut conn, &wrapper, false).await;
+                        }
+                    }
+                    self.ack(&mut conn, &delivery).await;
+                }
+            }
+        }
+    }
+
+    // Internal run loop for a single thread (Postgres driver)
+    async fn run_postgres_internal(
+        self,
+        pg: PgQueue,
+        worker_id: Arc<str>,
+        wake: Arc<tokio::sync::Notify>,
+    ) -> anyhow::Result<()> {
+        let mut consecutive_errors: u32 = 0;
+        // Redis is only needed by throttled and batched jobs; connect on demand with backoff.
+        let mut redis_conn: Option<redis::aio::MultiplexedConnection> = None;
+        let mut redis_errors: u32 = 0;
+        let mut redis_retry_at: Option<std::time::Instant> = None;
+
+        loop {
+            if self.context.is_cancelled() {
+                return Ok(());
+            }
+
+            let queues: Vec<String> = self
+                .active_queues()
+                .into_iter()
+                .map(str::to_string)
+                .collect();
+            if queues.is_empty() {
+                // Every queue is paused.
+                tokio::select! {
+                    _ = tokio::time::sleep(crate::manager::PAUSE_REFRESH_INTERVAL) => {}
+                    _ = self.context.cancelled() => return Ok(()),
+                }
+                continue;
+            }
+
+            let reservation = match pg.reserve(&queues, &worker_id).await {
+                Ok(reservation) => {
+                    consecutive_errors = 0;
+                    reservation
+                }
+                Err(err) => {
+                    consecutive_errors += 1;
+                    let backoff_secs = std::cmp::min(consecutive_errors, 30);
+                    tracing::error!("Worker fetch error (retry in {}s): {}", backoff_secs, err);
+                    tokio::time::sleep(std::time::Duration::from_secs(backoff_secs as u64)).await;
+                    continue;
+                }
+            };
+
+            let Some(reservation) = reservation else {
+                // Idle: wait for a NOTIFY, or poll for delayed jobs that became due.
+                tokio::select! {
+                    _ = wake.notified() => {}
+                    _ = tokio::time::sleep(crate::postgres::IDLE_POLL_INTERVAL) => {}
+                    _ = self.context.cancelled() => return Ok(()),
+                }
+                continue;
+            };
+
+            let id = reservation.id;
+            let mut wrapper: JobPayload = match serde_json::from_value(reservation.payload) {
+                Ok(w) => w,
+                Err(e) => {
+                    tracing::error!("Bad payload: {}", e);
+                    pg.delete(id).await.unwrap_or(());
+                    continue;
+                }
+            };
+
+            let needs_redis = wrapper.batch_id.is_some()
+                || self
+                    .registry
+                    .get(wrapper.job.as_str())
+                    .is_some_and(|handler| handler.throttle(&wrapper).is_some());
+            if needs_redis
+                && redis_conn.is_none()
+                && redis_retry_at.is_none_or(|at| at <= std::time::Instant::now())
+            {
+                // Throttles fail open and batches are skipped until Redis is reachable again
+                match self.redis.get_multiplexed_async_connection().await {
+                    Ok(conn) => {
+                        redis_conn = Some(conn);
+                        redis_errors = 0;
+                        redis_retry_at = None;
+                    }
+                    Err(e) => {
+                        redis_errors += 1;
+                        let backoff_secs = std::cmp::min(redis_errors, 30);
+                        tracing::error!(
+                            "Worker Redis connection error (retry in {}s): {}",
+                            backoff_secs,
+                            e
+                        );
+                        redis_retry_at = Some(
+                            std::time::Instant::now()
+                                + std::time::Duration::from_secs(backoff_secs as u64),
+                        );
+                    }
+                }
+            }
+
+            let settled = match self.process_wrapper(redis_conn.as_mut(), &wrapper).await {
+                Ok(JobResult::Success) => {
+                    self.complete_postgres(&pg, redis_conn.as_mut(), &wrapper, true)
+                        .await;
+                    pg.delete(id).await
+                }
+                Ok(JobResult::Failure {
+                    backoff,
+                    max_retries,
+                    err,
+                }) => {
+                    tracing::error!("Job {} failed: {}", wrapper.job, err);
+                    if wrapper.attempts < max_retries {
+                        wrapper.attempts += 1;
+                        let at = time::OffsetDateTime::now_utc()
+                            + std::time::Duration::from_secs(backoff);
+                        pg.release(id, &wrapper.available_at(at), at).await
+                    } else {
+                        // Call job's failed() callback
+                        if let Some(handler) = self.registry.get(wrapper.job.as_str()) {
+                            if let Err(e) = handler.on_failed(&wrapper, &self.context, &err).await {
+                                tracing::error!("Job failed() callback error: {}", e);
+                            }
                         }
+                        // Permanently Failed
+                        self.persist_failure(&wrapper, wrapper.group_id.as_deref(), &err)
+                            .await;
+                        self.complete_postgres(&pg, redis_conn.as_mut(), &wrapper, false)
+                            .await;
+                        pg.delete(id).await
                     }
                 }
+                Ok(JobResult::Throttled { retry_after }) => {
+                    let at = time::OffsetDateTime::now_utc()
+                        + std::time::Duration::from_secs(retry_after);
+                    pg.release(id, &wrapper, at).await
+                }
+                Err(e) => {
+                    tracing::error!("System error: {}", e);
+                    // The payload is dropped, like on the Redis driver.
+                    self.complete_postgres(&pg, redis_conn.as_mut(), &wrapper, false)
+                        .await;
+                    pg.delete(id).await
+                }
+            };
+            if let Err(e) = settled {
+                // The reservation expires and the job runs again.
+                tracing::error!("Failed to settle job {} ({}): {}", wrapper.job, id, e);
+            }
+        }
+    }
+
+    /// Postgres counterpart of `complete`. Dedupe keys go away with the row;
+    /// batch counters are kept in Redis and callbacks are pushed into `pg`.
+    async fn complete_postgres(
+        &self,
+        pg: &PgQueue,
+        redis_conn: Option<&mut redis::aio::MultiplexedConnection>,
+        wrapper: &JobPayload,
+        succeeded: bool,
+    ) {
+        if !wrapper.chain.is_empty() {
+            if succeeded {
+                if let Err(e) = crate::batch::continue_chain(pg, wrapper).await {
+                    tracing::error!("Failed to continue job chain after {}: {}", wrapper.job, e);
+                }
+            } else {
+                tracing::warn!(
+                    "Job chain stopped at {}: {} remaining job(s) dropped",
+                    wrapper.job,
+                    wrapper.chain.len()
+                );
+            }
+        }
+
+        if let Some(batch_id) = &wrapper.batch_id {
+            let recorded = match redis_conn {
+                Some(conn) => {
+                    crate::batch::record_outcome(&self.queue(), conn, pg, batch_id, succeeded).await
+                }
+                None => Err(anyhow::anyhow!("no Redis connection")),
+            };
+            if let Err(e) = recorded {
+                tracing::error!("Failed to update batch {}: {}", batch_id, e);
             }
         }
     }
 
+    /// Registered queues minus the ones paused through `manager::JobManager::pause`.
+    fn active_queues(&self) -> Vec<&'static str> {
+        let paused = match self.paused.read() {
+            Ok(paused) => paused,
+            Err(_) => return self.queues.clone(),
+        };
+        self.queues
+            .iter()
+            .copied()
+            .filter(|queue| !paused.contains(*queue))
+            .collect()
+    }
+
+    /// Track group locks held by this process so shutdown can release them.
+    fn hold_lock(&self, lock_key: &str, held: bool) {
+        if let Ok(mut locks) = self.held_locks.lock() {
+            if held {
+                locks.insert(lock_key.to_string());
+            } else {
+                locks.remove(lock_key);
+            }
+        }
+    }
+
+    /// Pop the next item from the standard/meta lists.
+    /// Reliable mode records it in the processing list; otherwise a plain BLPOP.
+    async fn fetch(
+        &self,
+        conn: &mut redis::aio::MultiplexedConnection,
+        keys: &[String],
+    ) -> redis::RedisResult<Option<Delivery>> {
+        match &self.reliable {
+            Some(reliable) => {
+                let delivery = reliable.fetch(conn, keys).await?;
+                if delivery.is_none() {
+                    tokio::time::sleep(crate::reliable::IDLE_POLL_INTERVAL).await;
+                }
+                Ok(delivery)
+            }
+            None => {
+                // BLPOP (blocking pop with timeout)
+                let result: Option<(String, String)> = conn.blpop(keys, 5.0).await?;
+                Ok(result.map(|(source, payload)| Delivery {
+                    source,
+                    payload,
+                    entry: None,
+                }))
+            }
+        }
+    }
+
+    /// Pop the next job of an ordered group (the caller holds the group lock).
+    async fn fetch_group_item(
+        &self,
+        conn: &mut redis::aio::MultiplexedConnection,
+        group_list: &str,
+    ) -> Option<Delivery> {
+        match &self.reliable {
+            Some(reliable) => reliable
+                .fetch(conn, &[group_list.to_string()])
+                .await
+                .ok()
+                .flatten(),
+            None => {
+                let payload: Option<String> = conn.lpop(group_list, None).await.ok().flatten();
+                payload.map(|payload| Delivery {
+                    source: group_list.to_string(),
+                    payload,
+                    entry: None,
+                })
+            }
+        }
+    }
+
+    /// Mark a delivery as handled (no-op outside reliable mode).
+    async fn ack(&self, conn: &mut redis::aio::MultiplexedConnection, delivery: &Delivery) {
+        if let Some(reliable) = &self.reliable {
+            reliable.ack(conn, delivery).await;
+        }
+    }
+
+    /// Bookkeeping once a job finished (`succeeded`) or permanently failed:
+    /// clear its dedupe lock, continue its chain and settle its batch counters.
+    async fn complete(
+        &self,
+        conn: &mut redis::aio::MultiplexedConnection,
+        wrapper: &JobPayload,
+        succeeded: bool,
+    ) {
+        if let Some(unique_key) = &wrapper.unique_key {
+            let lock_key = format!("{}:unique:{}", self.prefix, unique_key);
+            let _: () = conn.del(&lock_key).await.unwrap_or(());
+        }
+
+        let queue = self.queue();
+        if !wrapper.chain.is_empty() {
+            if succeeded {
+                if let Err(e) = crate::batch::continue_chain(&queue, wrapper).await {
+                    tracing::error!("Failed to continue job chain after {}: {}", wrapper.job, e);
+                }
+            } else {
+                tracing::warn!(
+                    "Job chain stopped at {}: {} remaining job(s) dropped",
+                    wrapper.job,
+                    wrapper.chain.len()
+                );
+            }
+        }
+
+        if let Some(batch_id) = &wrapper.batch_id {
+            if let Err(e) =
+                crate::batch::record_outcome(&queue, conn, &queue, batch_id, succeeded).await
+            {
+                tracing::error!("Failed to update batch {}: {}", batch_id, e);
+            }
+        }
+    }
+
+    fn queue(&self) -> crate::queue::RedisQueue {
+        crate::queue::RedisQueue::from_client_with_prefix(self.redis.clone(), &self.prefix)
+            .with_events(self.events.clone())
+    }
+
     async fn persist_failure(&self, wrapper: &JobPayload, group_id: Option<&str>, err: &str) {
         let payload_json = serde_json::to_value(wrapper).unwrap_or(serde_json::json!({}));
 
@@ -426,29 +968,134 @@ impl Worker {
         }
     }
 
-    async fn process_wrapper(&self, wrapper: &JobPayload) -> anyhow::Result<JobResult> {
+    /// Run a group job while refreshing the group lock, so a job longer than the
+    /// lock TTL does not let another worker start draining the same group.
+    async fn process_locked(
+        &self,
+        conn: &mut redis::aio::MultiplexedConnection,
+        lock_key: &str,
+        wrapper: &JobPayload,
+    ) -> anyhow::Result<JobResult> {
+        let mut job_conn = conn.clone();
+        let job = self.process_wrapper(Some(&mut job_conn), wrapper);
+        tokio::pin!(job);
+        let mut refresh = tokio::time::interval(std::time::Duration::from_secs(20));
+        refresh.tick().await;
+        loop {
+            tokio::select! {
+                result = &mut job => return result,
+                _ = refresh.tick() => {
+                    let _: () = redis::cmd("EXPIRE")
+                        .arg(lock_key)
+                        .arg(60)
+                        .query_async(conn)
+                        .await
+                        .unwrap_or(());
+                }
+            }
+        }
+    }
+
+    /// Execute one job. Throttles are enforced through `conn` (Redis only).
+    async fn process_wrapper(
+        &self,
+        mut conn: Option<&mut redis::aio::MultiplexedConnection>,
+        wrapper: &JobPayload,
+    ) -> anyhow::Result<JobResult> {
         if let Some(handler) = self.registry.get(wrapper.job.as_str()) {
+            let throttle = handler.throttle(wrapper);
+            let slot = match (throttle, conn.as_deref_mut()) {
+                (Some(throttle), Some(conn)) => match throttle.acquire(&self.prefix, conn).await {
+                    Ok(Admission::Granted(slot)) => slot,
+                    Ok(Admission::Denied(retry_after)) => {
+                        return Ok(JobResult::Throttled { retry_after })
+                    }
+                    Err(e) => {
+                        // Fail open: a Redis hiccup should not stall the queue.
+                        tracing::error!("Throttle check failed for {}: {}", wrapper.job, e);
+                        None
+                    }
+                },
+                _ => None,
+            };
+
             tracing::info!(
                 "Processing job: {} (Attempt {})",
                 wrapper.job,
                 wrapper.attempts
             );
-            handler
-                .execute(wrapper.data.clone(), &self.context, wrapper.attempts)
-                .await
+            let mut started = JobEvent::new(JobEventKind::Started, wrapper);
+            started.wait = wrapper.available_at_ms.map(|at| {
+                std::time::Duration::from_millis(
+                    crate::unix_millis(time::OffsetDateTime::now_utc()).saturating_sub(at),
+                )
+            });
+            self.events.emit(started);
+
+            let clock = std::time::Instant::now();
+            let result = handler
+                .execute(wrapper, &self.context, &self.middlewares)
+                .await;
+            if let (Some(slot), Some(conn)) = (slot, conn) {
+                slot.release(conn).await;
+            }
+            self.emit_outcome(wrapper, &result, Some(clock.elapsed()));
+            result
         } else {
-            Err(anyhow::anyhow!("Unknown job type: {}", wrapper.job))
+            let result = Err(anyhow::anyhow!("Unknown job type: {}", wrapper.job));
+            self.emit_outcome(wrapper, &result, None);
+            result
         }
     }
 
+    /// Emit `Succeeded`, `Retried` or `Failed` for a finished attempt.
+    fn emit_outcome(
+        &self,
+        wrapper: &JobPayload,
+        result: &anyhow::Result<JobResult>,
+        runtime: Option<std::time::Duration>,
+    ) {
+        let mut event = match result {
+            Ok(JobResult::Success) => JobEvent::new(JobEventKind::Succeeded, wrapper),
+            Ok(JobResult::Failure {
+                backoff,
+                max_retries,
+                err,
+            }) => {
+                let retry = wrapper.attempts < *max_retries;
+                let kind = if retry {
+                    JobEventKind::Retried
+                } else {
+                    JobEventKind::Failed
+                };
+                let mut event = JobEvent::new(kind, wrapper);
+                event.retry_in = retry.then_some(*backoff);
+                event.error = Some(err.clone());
+                event
+            }
+            Ok(JobResult::Throttled { .. }) => return,
+            Err(err) => {
+                let mut event = JobEvent::new(JobEventKind::Failed, wrapper);
+                event.error = Some(err.to_string());
+                event
+            }
+        };
+        event.runtime = runtime;
+        self.events.emit(event);
+    }
+
     pub async fn run_scheduler(self) -> anyhow::Result<()> {
         tracing::info!("Scheduler started");
         let client = self.redis.clone();
         let mut conn = client.get_multiplexed_async_connection().await?;
         let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
+        let shutdown = self.cancellation_token();
 
         loop {
-            interval.tick().await;
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown.cancelled() => return Ok(()),
+            }
             let now = std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)?
                 .as_secs();
@@ -462,15 +1109,26 @@ impl Worker {
                 .unwrap_or_default();
 
             if !jobs.is_empty() {
-                // Remove from ZSET
-                let _: () = conn.zrem(&key, &jobs).await.unwrap_or(());
-
                 for j in jobs {
-                    let queue_name = serde_json::from_str::<JobPayload>(&j)
-                        .map(|wrapper| wrapper.queue)
-                        .unwrap_or_else(|_| "default".to_string());
-                    let dest = format!("{}:{}", self.prefix, queue_name);
-                    let _: () = conn.rpush(&dest, j).await.unwrap_or(());
+                    // Remove from ZSET; only the process that removed it moves it,
+                    // so several workers can run this loop without duplicating jobs.
+                    let removed: i64 = conn.zrem(&key, &j).await.unwrap_or(0);
+                    if removed == 0 {
+                        continue;
+                    }
+                    let (queue_name, group_id) = serde_json::from_str::<JobPayload>(&j)
+                        .map(|wrapper| (wrapper.queue, wrapper.group_id))
+                        .unwrap_or_else(|_| ("default".to_string(), None));
+                    if let Some(group_id) = group_id {
+                        // Delayed dispatch of an ordered job: route into its group list.
+                        let group_list = format!("{}:{}:{}", self.prefix, queue_name, group_id);
+                        let meta = format!("{}:{}:meta", self.prefix, queue_name);
+                        let _: () = conn.rpush(&group_list, j).await.unwrap_or(());
+                        let _: () = conn.rpush(&meta, group_id).await.unwrap_or(());
+                    } else {
+                        let dest = format!("{}:{}", self.prefix, queue_name);
+                        let _: () = conn.rpush(&dest, j).await.unwrap_or(());
+                    }
                 }
             }
 
@@ -482,9 +1140,11 @@ impl Worker {
                 .unwrap_or_default();
 
             if !groups.is_empty() {
-                let _: () = conn.zrem(&group_key, &groups).await.unwrap_or(());
-
                 for g in groups {
+                    let removed: i64 = conn.zrem(&group_key, &g).await.unwrap_or(0);
+                    if removed == 0 {
+                        continue;
+                    }
                     let signal = serde_json::from_str::<DelayedGroupSignal>(&g).unwrap_or(
                         DelayedGroupSignal {
                             queue: "default".to_string(),
@@ -498,33 +1158,59 @@ impl Worker {
         }
     }
 
+    /// Run the scheduler, sweeper and `concurrency` worker loops until the
+    /// cancellation token is cancelled, then drain:
+    /// stop pulling jobs, wait up to the shutdown grace period for running jobs,
+    /// abort the rest and release this process's group locks and processing list.
     pub async fn run_concurrent(self, concurrency: usize) -> anyhow::Result<()> {
         tracing::info!(
             "Worker started. Queues: {:?}, Jobs: {:?}",
             self.queues,
             self.registry.keys()
         );
+        let shutdown = self.cancellation_token();
         let mut set = tokio::task::JoinSet::new();
+        // Postgres driver: queue + this process's reservation id
+        let postgres = self.postgres.clone().map(|pg| {
+            let worker_id: Arc<str> =
+                format!("{}-{}", std::process::id(), uuid::Uuid::new_v4().simple()).into();
+            (pg, worker_id)
+        });
+        let wake = Arc::new(tokio::sync::Notify::new());
 
         // Spawn Scheduler (Single)
-        let s = self.clone();
-        set.spawn(async move {
-            if let Err(e) = s.run_scheduler().await {
-                tracing::error!("Scheduler crashed: {}", e);
-            }
-        });
+        // Postgres jobs carry their own `available_at`, so only Redis needs it.
+        if postgres.is_none() {
+            let s = self.clone();
+            set.spawn(async move {
+                if let Err(e) = s.run_scheduler().await {
+                    tracing::error!("Scheduler crashed: {}", e);
+                }
+            });
+        }
 
         // Spawn Outbox Sweeper (Single)
         if let Some((db, interval)) = self.sweeper_config.clone() {
             let client = self.redis.clone();
             let queue_prefix = self.prefix.clone();
+            let events = self.events.clone();
+            let pg = self.postgres.clone();
+            let shutdown = shutdown.clone();
             set.spawn(async move {
                 tracing::info!("Outbox sweeper started");
-                let queue = crate::queue::RedisQueue::from_client_with_prefix(client, queue_prefix);
+                let queue = crate::queue::RedisQueue::from_client_with_prefix(client, queue_prefix)
+                    .with_events(events);
                 let mut interval_timer = tokio::time::interval(interval);
                 loop {
-                    interval_timer.tick().await;
-                    match crate::buffer::OutboxFlusher::flush(&db, &queue).await {
+                    tokio::select! {
+                        _ = interval_timer.tick() => {}
+                        _ = shutdown.cancelled() => break,
+                    }
+                    let flushed = match &pg {
+                        Some(pg) => pg.flush_outbox().await,
+                        None => crate::buffer::OutboxFlusher::flush(&db, &queue).await,
+                    };
+                    match flushed {
                         Ok(n) if n > 0 => tracing::info!("Sweeper recovered {} jobs", n),
                         Ok(_) => {} // Empty
                         Err(e) => tracing::error!("Sweeper error: {}", e),
@@ -533,24 +1219,217 @@ impl Worker {
             });
         }
 
+        // Spawn Paused-Queue Refresh (Single)
+        {
+            let db = self.context.db.clone();
+            let paused = self.paused.clone();
+            let shutdown = shutdown.clone();
+            set.spawn(async move {
+                let mut interval_timer =
+                    tokio::time::interval(crate::manager::PAUSE_REFRESH_INTERVAL);
+                loop {
+                    tokio::select! {
+                        _ = interval_timer.tick() => {}
+                        _ = shutdown.cancelled() => break,
+                    }
+                    match crate::manager::paused_queues(&db).await {
+                        Ok(queues) => {
+                            if let Ok(mut current) = paused.write() {
+                                if *current != queues {
+                                    tracing::info!("Paused queues: {:?}", queues);
+                                    *current = queues;
+                                }
+                            }
+                        }
+                        Err(e) => tracing::warn!("Failed to load paused queues: {}", e),
+                    }
+                }
+            });
+        }
+
+        // Spawn Heartbeat + LISTEN (Postgres) or Heartbeat + Reaper (Reliable Mode)
+        // Kept alive until the drain is over so in-flight jobs are not reaped by other workers.
+        let heartbeat = match &postgres {
+            Some((pg, worker_id)) => {
+                let pg = pg.clone();
+                let worker_id = worker_id.clone();
+                let wake = wake.clone();
+                Some(tokio::spawn(async move {
+                    tracing::info!("Postgres queue driver enabled (worker {})", worker_id);
+                    let heartbeat = async {
+                        let mut heartbeat_timer =
+                            tokio::time::interval(crate::postgres::HEARTBEAT_INTERVAL);
+                        loop {
+                            heartbeat_timer.tick().await;
+                            if let Err(e) = pg.heartbeat(&worker_id).await {
+                                tracing::error!("Worker heartbeat error: {}", e);
+                            }
+                        }
+                    };
+                    tokio::join!(pg.forward_notifications(wake), heartbeat);
+                }))
+            }
+            None => self.reliable.clone().map(|reliable| {
+                let client = self.redis.clone();
+                tokio::spawn(async move {
+                    tracing::info!(
+                        "Reliable delivery enabled (worker {})",
+                        reliable.worker_id()
+                    );
+                    let mut conn = match client.get_multiplexed_async_connection().await {
+                        Ok(conn) => conn,
+                        Err(e) => {
+                            tracing::error!("Reliable delivery connection failed: {}", e);
+                            return;
+                        }
+                    };
+                    let mut heartbeat_timer =
+                        tokio::time::interval(crate::reliable::HEARTBEAT_INTERVAL);
+                    let mut reaper_timer = tokio::time::interval(crate::reliable::REAPER_INTERVAL);
+                    loop {
+                        tokio::select! {
+                            _ = heartbeat_timer.tick() => {
+                                if let Err(e) = reliable.heartbeat(&mut conn).await {
+                                    tracing::error!("Worker heartbeat error: {}", e);
+                                }
+                            }
+                            _ = reaper_timer.tick() => {
+                                if let Err(e) = reliable.reap(&mut conn).await {
+                                    tracing::error!("Reaper error: {}", e);
+                                }
+                            }
+                        }
+                    }
+                })
+            }),
+        };
+
         // Spawn Workers
+        let mut workers = tokio::task::JoinSet::new();
         for i in 0..concurrency {
             let w = self.clone();
-            set.spawn(async move {
+            let postgres = postgres.clone();
+            let wake = wake.clone();
+            workers.spawn(async move {
                 tracing::info!("Starting worker thread {}", i);
-                if let Err(e) = w.run_internal().await {
+                let result = match postgres {
+                    Some((pg, worker_id)) => w.run_postgres_internal(pg, worker_id, wake).await,
+                    None => w.run_internal().await,
+                };
+                if let Err(e) = result {
                     tracing::error!("Worker thread {} crashed: {}", i, e);
                 }
             });
         }
 
-        while let Some(res) = set.join_next().await {
-            // A worker finished (likely crashed or stopped).
-            // Ideally restart it? For now just log.
-            if let Err(e) = res {
-                tracing::error!("Join error: {}", e);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                res = workers.join_next() => match res {
+                    // A worker finished (likely crashed or stopped).
+                    // Ideally restart it? For now just log.
+                    Some(Err(e)) => tracing::error!("Join error: {}", e),
+                    Some(Ok(())) => {}
+                    None => {
+                        // Every worker thread exited: stop the background tasks too.
+                        shutdown.cancel();
+                        break;
+                    }
+                },
             }
         }
+
+        // --- Shutdown / Drain ---
+        let grace = self
+            .config
+            .as_ref()
+            .map(|c| c.shutdown_grace)
+            .unwrap_or(std::time::Duration::from_secs(30));
+        tracing::info!(
+            "Worker shutting down: waiting up to {}s for running jobs",
+            grace.as_secs()
+        );
+
+        let running = workers.len();
+        let drained = tokio::time::timeout(grace, async {
+            while let Some(res) = workers.join_next().await {
+                if let Err(e) = res {
+                    tracing::error!("Join error: {}", e);
+                }
+            }
+        })
+        .await;
+        let aborted = workers.len();
+        if drained.is_err() {
+            tracing::warn!(
+                "Grace period elapsed: aborting {} worker thread(s) with running jobs",
+                aborted
+            );
+            workers.shutdown().await;
+        }
+
+        // Scheduler + sweeper stop on the same token.
+        while set.join_next().await.is_some() {}
+        if let Some(heartbeat) = heartbeat {
+            heartbeat.abort();
+        }
+
+        let (requeued, released) = match &postgres {
+            Some((pg, worker_id)) => match pg.release_worker(worker_id).await {
+                Ok(n) => (n, 0),
+                Err(e) => {
+                    tracing::error!("Failed to re-queue in-flight jobs: {}", e);
+                    (0, 0)
+                }
+            },
+            None => self.release_resources().await,
+        };
+        tracing::info!(
+            "Worker stopped: {} of {} thread(s) finished in time, {} aborted, {} job(s) re-queued, {} group lock(s) released",
+            running - aborted,
+            running,
+            aborted,
+            requeued,
+            released
+        );
         Ok(())
     }
+
+    /// Give back what this process still holds after the drain:
+    /// group locks of aborted drains and (reliable mode) unacknowledged jobs.
+    async fn release_resources(&self) -> (u64, usize) {
+        let mut conn = match self.redis.get_multiplexed_async_connection().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                tracing::error!("Shutdown cleanup connection failed: {}", e);
+                return (0, 0);
+            }
+        };
+
+        let locks: Vec<String> = self
+            .held_locks
+            .lock()
+            .map(|mut locks| locks.drain().collect())
+            .unwrap_or_default();
+        if !locks.is_empty() {
+            let _: () = conn.del(&locks).await.unwrap_or(());
+        }
+
+        let mut requeued = 0;
+        if let Some(reliable) = &self.reliable {
+            match reliable.release(&mut conn).await {
+                Ok(n) => requeued = n,
+                Err(e) => tracing::error!("Failed to re-queue in-flight jobs: {}", e),
+            }
+        }
+
+        (requeued, locks.len())
+    }
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
 }
@@ -0,0 +1,14 @@
+use crate::log::cleanup_mail_logs;
+use core_jobs::Job;
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default)]
+pub struct CleanupMailLogs;
+
+#[async_trait::async_trait]
+impl Job for CleanupMailLogs {
+    const NAME: &'static str = "core:cleanup_mail_logs";
+
+    async fn handle(&self, ctx: &core_jobs::JobContext) -> anyhow::Result<()> {
+        cleanup_mail_logs(&ctx.db, ctx.settings.mail.log_retention_days).await
+    }
+}
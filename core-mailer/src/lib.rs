@@ -1,38 +1,80 @@
 use async_trait::async_trait;
-use core_jobs::{Job, JobContext};
+use core_db::infra::storage::Storage;
+use core_jobs::{driver::QueueDriver, Job, JobContext};
 use lettre::{
-    message::header, transport::smtp::authentication::Credentials, AsyncSmtpTransport,
-    AsyncTransport, Message, Tokio1Executor,
+    message::{
+        header::{self, ContentType, HeaderName, HeaderValue},
+        Attachment, Mailbox, MultiPart, MultiPartBuilder, SinglePart,
+    },
+    Message,
 };
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+pub mod jobs;
+pub mod log;
+pub mod message;
+pub mod preview;
+pub mod template;
+pub mod transport;
+
+pub use message::{AttachmentSource, MailAttachment, MailPayload};
+pub use template::{MailLayout, MailTemplate, TemplatedMailable};
+pub use transport::{Delivery, MailTransport, MemoryTransport, OutgoingAttachment, OutgoingMail};
 
 #[derive(Clone)]
 pub struct Mailer {
-    transport: Option<AsyncSmtpTransport<Tokio1Executor>>,
+    transport: Arc<dyn MailTransport>,
     from: String,
-    queue: Option<core_jobs::queue::RedisQueue>,
+    queue: Option<Arc<dyn QueueDriver>>, // `[worker] driver` queue for `queue`/`queue_raw`
+    storage: Option<Arc<dyn Storage>>,   // resolves `AttachmentSource::Storage`
+    log_db: Option<sqlx::PgPool>,        // `mail_logs` writes
 }
 
 #[async_trait]
 pub trait Mailable: Send + Sync {
     fn subject(&self) -> String;
+    /// HTML body.
     fn body(&self) -> String;
     fn to(&self) -> Vec<String>;
-}
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct MailPayload {
-    pub to: Vec<String>,
-    pub subject: String,
-    pub body: String,
-}
+    fn cc(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn bcc(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn reply_to(&self) -> Option<String> {
+        None
+    }
+
+    /// Plain-text alternative of `body`.
+    fn text(&self) -> Option<String> {
+        None
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+
+    fn attachments(&self) -> Vec<MailAttachment> {
+        Vec::new()
+    }
 
-impl MailPayload {
-    pub fn new(to: String, subject: String, body: String) -> Self {
-        Self {
-            to: vec![to],
-            subject,
-            body,
+    /// Render into a payload for `Mailer::send_raw` / `Mailer::queue_raw`.
+    fn to_payload(&self) -> MailPayload {
+        MailPayload {
+            to: self.to(),
+            cc: self.cc(),
+            bcc: self.bcc(),
+            reply_to: self.reply_to(),
+            subject: self.subject(),
+            body: self.body(),
+            text: self.text(),
+            headers: self.headers(),
+            attachments: self.attachments(),
         }
     }
 }
@@ -47,10 +89,18 @@ impl Job for SendMailJob {
     const NAME: &'static str = "SendMailJob";
 
     async fn handle(&self, ctx: &JobContext) -> anyhow::Result<()> {
-        // Use settings from context
-        let mail_settings = &ctx.settings.mail;
+        // Prefer the app's mailer (same transport and storage) over one built from settings
+        if let Some(mailer) = ctx.extensions.get::<Arc<Mailer>>() {
+            return mailer.send_raw(&self.payload).await;
+        }
 
-        let mailer = Mailer::from_settings(mail_settings)?;
+        let mut mailer = Mailer::from_settings(&ctx.settings.mail)?;
+        if let Some(storage) = ctx.extensions.get::<Arc<dyn Storage>>() {
+            mailer = mailer.with_storage(storage.clone());
+        }
+        if ctx.settings.mail.log_enabled {
+            mailer = mailer.with_log(ctx.db.clone());
+        }
         mailer.send_raw(&self.payload).await?;
         Ok(())
     }
@@ -60,104 +110,248 @@ impl Mailer {
     // For App usage (with queue support)
     pub fn new(
         settings: &core_config::MailSettings,
-        queue: Option<core_jobs::queue::RedisQueue>,
+        queue: Option<Arc<dyn QueueDriver>>,
     ) -> anyhow::Result<Self> {
-        let transport = Self::build_transport(settings)?;
+        let transport = transport::from_settings(settings)?;
         let from = settings.from_address.clone();
 
         Ok(Self {
             transport,
             from,
             queue,
+            storage: None,
+            log_db: None,
         })
     }
 
     // specific for Worker (stateless / from settings)
     pub fn from_settings(settings: &core_config::MailSettings) -> anyhow::Result<Self> {
-        let transport = Self::build_transport(settings)?;
+        let transport = transport::from_settings(settings)?;
         let from = settings.from_address.clone();
         Ok(Self {
             transport,
             from,
             queue: None,
+            storage: None,
+            log_db: None,
         })
     }
 
-    fn build_transport(
-        settings: &core_config::MailSettings,
-    ) -> anyhow::Result<Option<AsyncSmtpTransport<Tokio1Executor>>> {
-        if settings.driver == "log" {
-            return Ok(None);
-        }
-
-        // SMTP
-        let mut builder = AsyncSmtpTransport::<Tokio1Executor>::relay(&settings.host)?;
-        // Port? Lettre relay sets port automatically or via builder?
-        // relay() uses default. We might need `relay` with port option or separate builder.
-        // Actually `relay` is high level. `builder_starttls` is better for explicit port.
-        // But let's check if we can set port.
-        // builder.port(settings.port) - looks valid if exposed.
-        // If not, we use `AsyncSmtpTransport::<Tokio1Executor>::builder_starttls(host).port(port)`
+    /// Storage used to load `MailAttachment::from_storage` files at send time.
+    pub fn with_storage(mut self, storage: Arc<dyn Storage>) -> Self {
+        self.storage = Some(storage);
+        self
+    }
 
-        // Let's stick to `relay` and assume standard port or verify API.
-        // Actually, let's use the explicit builder for control.
+    /// Record every send attempt in `mail_logs`.
+    pub fn with_log(mut self, db: sqlx::PgPool) -> Self {
+        self.log_db = Some(db);
+        self
+    }
 
-        // Re-read lettre docs from memory:
-        // builder_starttls is safer.
+    /// Send through `transport` instead of the `[mail] driver` one
+    /// (e.g. a `MemoryTransport` in tests).
+    pub fn with_transport(mut self, transport: Arc<dyn MailTransport>) -> Self {
+        self.transport = transport;
+        self
+    }
 
-        // For now, let's assume `relay` returns a builder that has `.port()`.
-        builder = builder.port(settings.port);
+    pub async fn send<M: Mailable>(&self, mail: &M) -> anyhow::Result<()> {
+        self.send_raw(&mail.to_payload()).await
+    }
 
-        if let (Some(u), Some(p)) = (settings.username_opt(), settings.password_opt()) {
-            builder = builder.credentials(Credentials::new(u.to_string(), p.to_string()));
+    pub async fn send_raw(&self, payload: &MailPayload) -> anyhow::Result<()> {
+        let mail = match self.prepare(payload).await {
+            Ok(mail) => mail,
+            Err(e) => {
+                self.log(payload, self.transport.name(), None, Some(&e))
+                    .await;
+                return Err(e);
+            }
+        };
+        match self.transport.send(&mail).await {
+            Ok(delivery) => {
+                let message_id = delivery.message_id.as_deref().unwrap_or(&mail.message_id);
+                self.log(payload, delivery.transport, Some(message_id), None)
+                    .await;
+                tracing::info!("Email sent to {:?} via {}", payload.to, delivery.transport);
+                Ok(())
+            }
+            Err(e) => {
+                self.log(
+                    payload,
+                    self.transport.name(),
+                    Some(&mail.message_id),
+                    Some(&e),
+                )
+                .await;
+                Err(e)
+            }
         }
-
-        Ok(Some(builder.build()))
     }
 
-    pub async fn send<M: Mailable>(&self, mail: &M) -> anyhow::Result<()> {
-        let payload = MailPayload {
-            to: mail.to(),
-            subject: mail.subject(),
-            body: mail.body(),
+    /// Write a `mail_logs` row. Logging failures never fail the send.
+    async fn log(
+        &self,
+        payload: &MailPayload,
+        transport: &str,
+        message_id: Option<&str>,
+        error: Option<&anyhow::Error>,
+    ) {
+        let Some(db) = &self.log_db else {
+            return;
+        };
+        let recipient = payload.recipients().cloned().collect::<Vec<_>>().join(", ");
+        let (status, error) = match error {
+            Some(e) => (log::MailLog::FAILED, Some(format!("{:#}", e))),
+            None => (log::MailLog::SENT, None),
         };
-        self.send_raw(&payload).await
+        if let Err(e) = log::MailLog::insert(
+            db,
+            &recipient,
+            &payload.subject,
+            transport,
+            message_id,
+            status,
+            error.as_deref(),
+        )
+        .await
+        {
+            tracing::warn!("Failed to write mail log: {}", e);
+        }
     }
 
-    pub async fn send_raw(&self, payload: &MailPayload) -> anyhow::Result<()> {
-        if let Some(transport) = &self.transport {
-            let email = Message::builder()
-                .from(self.from.parse()?)
-                .to(payload.to[0].parse()?) // Simplify single recipient for now
-                .subject(&payload.subject)
+    /// Load attachments and build the MIME message.
+    async fn prepare(&self, payload: &MailPayload) -> anyhow::Result<OutgoingMail> {
+        let mut attachments = Vec::with_capacity(payload.attachments.len());
+        for attachment in &payload.attachments {
+            attachments.push(self.load_attachment(attachment).await?);
+        }
+        let from: Mailbox = self.from.parse()?;
+        let message_id = format!("<{}@{}>", uuid::Uuid::new_v4(), from.email.domain());
+        let message = self.build_message(payload, &attachments, &from, &message_id)?;
+        Ok(OutgoingMail {
+            from: self.from.clone(),
+            payload: payload.clone(),
+            attachments,
+            message_id,
+            message,
+        })
+    }
+
+    fn build_message(
+        &self,
+        payload: &MailPayload,
+        attachments: &[OutgoingAttachment],
+        from: &Mailbox,
+        message_id: &str,
+    ) -> anyhow::Result<Message> {
+        if payload.to.is_empty() {
+            anyhow::bail!("Mail '{}' has no recipients", payload.subject);
+        }
+
+        let mut builder = Message::builder()
+            .from(from.clone())
+            .message_id(Some(message_id.to_string()))
+            .subject(&payload.subject);
+        for address in &payload.to {
+            builder = builder.to(address.parse()?);
+        }
+        for address in &payload.cc {
+            builder = builder.cc(address.parse()?);
+        }
+        for address in &payload.bcc {
+            builder = builder.bcc(address.parse()?);
+        }
+        if let Some(address) = &payload.reply_to {
+            builder = builder.reply_to(address.parse()?);
+        }
+        for (name, value) in &payload.headers {
+            let name = HeaderName::new_from_ascii(name.clone())
+                .map_err(|e| anyhow::anyhow!("Invalid mail header '{}': {}", name, e))?;
+            builder = builder.raw_header(HeaderValue::new(name, value.clone()));
+        }
+
+        if payload.text.is_none() && attachments.is_empty() {
+            return Ok(builder
                 .header(header::ContentType::TEXT_HTML)
-                .body(payload.body.clone())?;
+                .body(payload.body.clone())?);
+        }
+
+        // mixed(alternative(text, related(html, inline...)), files...)
+        let mut inline = Vec::new();
+        let mut files = Vec::new();
+        for attachment in attachments {
+            let part = attachment_part(attachment);
+            if attachment.content_id.is_some() {
+                inline.push(part);
+            } else {
+                files.push(part);
+            }
+        }
 
-            transport.send(email).await?;
-            tracing::info!("Email sent to {}", payload.to[0]);
+        let html = SinglePart::html(payload.body.clone());
+        let html = if inline.is_empty() {
+            BodyPart::Single(html)
         } else {
-            // Log Driver
-            tracing::info!(
-                "[MAIL LOG] To: {:?}, Subject: {}, Body: {}",
-                payload.to,
-                payload.subject,
-                payload.body
-            );
+            let mut related = MultiPart::related().singlepart(html);
+            for part in inline {
+                related = related.singlepart(part);
+            }
+            BodyPart::Multi(related)
+        };
+
+        let content =
+            match &payload.text {
+                Some(text) => BodyPart::Multi(html.append_to(
+                    MultiPart::alternative().singlepart(SinglePart::plain(text.clone())),
+                )),
+                None => html,
+            };
+
+        if files.is_empty() {
+            return Ok(match content {
+                BodyPart::Single(part) => builder.singlepart(part)?,
+                BodyPart::Multi(part) => builder.multipart(part)?,
+            });
         }
-        Ok(())
+        let mut mixed = content.start(MultiPart::mixed());
+        for part in files {
+            mixed = mixed.singlepart(part);
+        }
+        Ok(builder.multipart(mixed)?)
+    }
+
+    async fn load_attachment(
+        &self,
+        attachment: &MailAttachment,
+    ) -> anyhow::Result<OutgoingAttachment> {
+        let data = match &attachment.source {
+            AttachmentSource::Bytes { data } => data.clone(),
+            AttachmentSource::Storage { key } => {
+                let storage = self.storage.as_ref().ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Attachment {} is in storage, but the mailer has no storage",
+                        attachment.filename
+                    )
+                })?;
+                storage.get(key).await?.to_vec()
+            }
+        };
+        Ok(OutgoingAttachment {
+            filename: attachment.filename.clone(),
+            content_type: attachment.content_type.clone(),
+            content_id: attachment.content_id.clone(),
+            data,
+        })
     }
 
     pub async fn queue<M: Mailable + Serialize>(&self, mail: &M) -> anyhow::Result<()> {
         if let Some(q) = &self.queue {
-            // Render to payload
-            let payload = MailPayload {
-                to: mail.to(),
-                subject: mail.subject(),
-                body: mail.body(),
+            let job = SendMailJob {
+                payload: mail.to_payload(),
             };
-
-            let job = SendMailJob { payload };
-            job.dispatch(q).await?;
+            job.dispatch(q.as_ref()).await?;
             tracing::info!("Email queued for {:?}", job.payload.to);
             Ok(())
         } else {
@@ -168,7 +362,7 @@ impl Mailer {
     pub async fn queue_raw(&self, payload: MailPayload) -> anyhow::Result<()> {
         if let Some(q) = &self.queue {
             let job = SendMailJob { payload };
-            job.dispatch(q).await?;
+            job.dispatch(q.as_ref()).await?;
             tracing::info!("Email queued for {:?}", job.payload.to);
             Ok(())
         } else {
@@ -176,3 +370,141 @@ impl Mailer {
         }
     }
 }
+
+fn attachment_part(attachment: &OutgoingAttachment) -> SinglePart {
+    let content_type = ContentType::parse(&attachment.content_type)
+        .unwrap_or_else(|_| ContentType::parse("application/octet-stream").unwrap());
+    let builder = match &attachment.content_id {
+        Some(content_id) => {
+            Attachment::new_inline_with_name(content_id.clone(), attachment.filename.clone())
+        }
+        None => Attachment::new(attachment.filename.clone()),
+    };
+    builder.body(attachment.data.clone(), content_type)
+}
+
+enum BodyPart {
+    Single(SinglePart),
+    Multi(MultiPart),
+}
+
+impl BodyPart {
+    fn start(self, builder: MultiPartBuilder) -> MultiPart {
+        match self {
+            BodyPart::Single(part) => builder.singlepart(part),
+            BodyPart::Multi(part) => builder.multipart(part),
+        }
+    }
+
+    fn append_to(self, multipart: MultiPart) -> MultiPart {
+        match self {
+            BodyPart::Single(part) => multipart.singlepart(part),
+            BodyPart::Multi(part) => multipart.multipart(part),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MailPayload, Mailer, OutgoingAttachment};
+    use lettre::message::Mailbox;
+
+    fn mailer() -> Mailer {
+        Mailer::from_settings(&core_config::MailSettings::default()).expect("mailer")
+    }
+
+    fn from() -> Mailbox {
+        "App <hello@example.com>".parse().expect("from")
+    }
+
+    fn attachment(filename: &str, content_id: Option<&str>) -> OutgoingAttachment {
+        OutgoingAttachment {
+            filename: filename.to_string(),
+            content_type: "image/png".to_string(),
+            content_id: content_id.map(str::to_string),
+            data: vec![0x89, b'P', b'N', b'G'],
+        }
+    }
+
+    fn formatted(payload: &MailPayload, attachments: &[OutgoingAttachment]) -> String {
+        let message = mailer()
+            .build_message(payload, attachments, &from(), "<id@example.com>")
+            .expect("build message");
+        String::from_utf8(message.formatted()).expect("utf-8 message")
+    }
+
+    #[test]
+    fn html_only_mail_is_a_single_part() {
+        let payload = MailPayload::new("ada@example.com".into(), "Hi".into(), "<p>Hi</p>".into());
+
+        let raw = formatted(&payload, &[]);
+        assert!(raw.contains("Content-Type: text/html; charset=utf-8"));
+        assert!(!raw.contains("multipart/"));
+        assert!(raw.contains("Message-ID: <id@example.com>"));
+    }
+
+    #[test]
+    fn text_inline_and_files_nest_mixed_alternative_related() {
+        let payload =
+            MailPayload::new("ada@example.com".into(), "Hi".into(), "<p>Hi</p>".into()).text("Hi");
+        let attachments = [
+            attachment("logo.png", Some("logo")),
+            attachment("report.png", None),
+        ];
+
+        let raw = formatted(&payload, &attachments);
+        let mixed = raw.find("multipart/mixed").expect("mixed");
+        let alternative = raw.find("multipart/alternative").expect("alternative");
+        let plain = raw.find("text/plain").expect("plain part");
+        let related = raw.find("multipart/related").expect("related");
+        let html = raw.find("text/html").expect("html part");
+        let inline = raw.find("Content-ID: <logo>").expect("inline part");
+        let file = raw
+            .find("Content-Disposition: attachment; filename=\"report.png\"")
+            .expect("file part");
+        assert!(mixed < alternative && alternative < plain && plain < related);
+        assert!(related < html && html < inline && inline < file);
+    }
+
+    #[test]
+    fn cc_and_bcc_reach_the_envelope_but_bcc_is_not_a_header() {
+        let payload = MailPayload::new("ada@example.com".into(), "Hi".into(), "<p>Hi</p>".into())
+            .cc("grace@example.com")
+            .bcc("audit@example.com")
+            .reply_to("support@example.com")
+            .header("X-Campaign", "spring");
+
+        let message = mailer()
+            .build_message(&payload, &[], &from(), "<id@example.com>")
+            .expect("build message");
+        let recipients: Vec<String> = message
+            .envelope()
+            .to()
+            .iter()
+            .map(|address| address.to_string())
+            .collect();
+        assert_eq!(
+            recipients,
+            ["ada@example.com", "grace@example.com", "audit@example.com"]
+        );
+
+        let raw = String::from_utf8(message.formatted()).expect("utf-8 message");
+        assert!(raw.contains("Cc: grace@example.com"));
+        assert!(raw.contains("Reply-To: support@example.com"));
+        assert!(raw.contains("X-Campaign: spring"));
+        assert!(!raw.contains("audit@example.com"));
+    }
+
+    #[test]
+    fn mail_without_recipients_is_rejected() {
+        let payload = MailPayload {
+            subject: "Hi".into(),
+            ..Default::default()
+        };
+
+        let err = mailer()
+            .build_message(&payload, &[], &from(), "<id@example.com>")
+            .expect_err("no recipients");
+        assert!(err.to_string().contains("has no recipients"));
+    }
+}
@@ -0,0 +1,56 @@
+//! `mail_logs` rows written by `Mailer` for every send attempt.
+
+use core_db::{
+    common::sql::{DbConn, Op},
+    generated::models::{MailLogCol, MailLogModel},
+};
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Sent-mail log API.
+pub struct MailLog;
+
+impl MailLog {
+    pub const SENT: &'static str = "sent";
+    pub const FAILED: &'static str = "failed";
+
+    /// Insert a log entry. `recipient` lists to, cc and bcc, comma-separated.
+    pub async fn insert(
+        pool: &PgPool,
+        recipient: &str,
+        subject: &str,
+        transport: &str,
+        message_id: Option<&str>,
+        status: &str,
+        error: Option<&str>,
+    ) -> anyhow::Result<Uuid> {
+        let row = MailLogModel::create()
+            .set(MailLogCol::RECIPIENT, recipient.to_string())?
+            .set(MailLogCol::SUBJECT, subject.to_string())?
+            .set(MailLogCol::TRANSPORT, transport.to_string())?
+            .set(MailLogCol::MESSAGE_ID, message_id.map(str::to_string))?
+            .set(MailLogCol::STATUS, status.to_string())?
+            .set(MailLogCol::ERROR, error.map(str::to_string))?
+            .save(DbConn::pool(pool))
+            .await?;
+
+        Ok(row.id)
+    }
+}
+
+/// Delete mail logs older than `retention_days` (0 keeps everything).
+pub async fn cleanup_mail_logs(db: &PgPool, retention_days: u64) -> anyhow::Result<()> {
+    if retention_days == 0 {
+        return Ok(());
+    }
+
+    let cutoff = OffsetDateTime::now_utc() - time::Duration::days(retention_days as i64);
+
+    MailLogModel::query()
+        .where_col(MailLogCol::CREATED_AT, Op::Lt, cutoff)
+        .delete(DbConn::pool(db))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+
+/// A rendered email, ready to send or queue through `SendMailJob`.
+///
+/// `body` is the HTML part; set `text` as well to send a text/HTML alternative.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct MailPayload {
+    pub to: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cc: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bcc: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<String>,
+    pub subject: String,
+    pub body: String,
+    /// Plain-text alternative of `body`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Extra headers such as `List-Unsubscribe` or `X-Campaign`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<(String, String)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<MailAttachment>,
+}
+
+impl MailPayload {
+    pub fn new(to: String, subject: String, body: String) -> Self {
+        Self {
+            to: vec![to],
+            subject,
+            body,
+            ..Default::default()
+        }
+    }
+
+    pub fn add_to(mut self, address: impl Into<String>) -> Self {
+        self.to.push(address.into());
+        self
+    }
+
+    pub fn cc(mut self, address: impl Into<String>) -> Self {
+        self.cc.push(address.into());
+        self
+    }
+
+    pub fn bcc(mut self, address: impl Into<String>) -> Self {
+        self.bcc.push(address.into());
+        self
+    }
+
+    pub fn reply_to(mut self, address: impl Into<String>) -> Self {
+        self.reply_to = Some(address.into());
+        self
+    }
+
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn attach(mut self, attachment: MailAttachment) -> Self {
+        self.attachments.push(attachment);
+        self
+    }
+
+    /// All recipients (to, cc and bcc), for logging.
+    pub fn recipients(&self) -> impl Iterator<Item = &String> {
+        self.to.iter().chain(&self.cc).chain(&self.bcc)
+    }
+}
+
+/// A file attached to a `MailPayload`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MailAttachment {
+    pub filename: String,
+    pub content_type: String,
+    pub source: AttachmentSource,
+    /// Content-ID for inline images, referenced from the HTML as `cid:<id>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_id: Option<String>,
+}
+
+/// Where attachment bytes come from.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AttachmentSource {
+    /// Bytes carried in the payload (base64 in queued jobs). Keep these small.
+    Bytes {
+        #[serde(with = "base64_bytes")]
+        data: Vec<u8>,
+    },
+    /// A `core_db::infra::storage::Storage` key, read when the mail is sent.
+    Storage { key: String },
+}
+
+impl MailAttachment {
+    pub fn from_bytes(
+        filename: impl Into<String>,
+        content_type: impl Into<String>,
+        data: impl Into<Vec<u8>>,
+    ) -> Self {
+        Self {
+            filename: filename.into(),
+            content_type: content_type.into(),
+            source: AttachmentSource::Bytes { data: data.into() },
+            content_id: None,
+        }
+    }
+
+    pub fn from_storage(
+        filename: impl Into<String>,
+        content_type: impl Into<String>,
+        key: impl Into<String>,
+    ) -> Self {
+        Self {
+            filename: filename.into(),
+            content_type: content_type.into(),
+            source: AttachmentSource::Storage { key: key.into() },
+            content_id: None,
+        }
+    }
+
+    /// Show inline (e.g. `<img src="cid:logo">`) instead of as a download.
+    pub fn inline(mut self, content_id: impl Into<String>) -> Self {
+        self.content_id = Some(content_id.into());
+        self
+    }
+}
+
+mod base64_bytes {
+    use base64::{engine::general_purpose, Engine as _};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&general_purpose::STANDARD.encode(data))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(serde::de::Error::custom)
+    }
+}
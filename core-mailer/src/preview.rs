@@ -0,0 +1,185 @@
+//! Browser previews of mailables for development, and a viewer for the `memory` driver's
+//! mailbox. Mount these routes only outside production: they need no auth.
+
+use crate::{transport::MemoryTransport, MailPayload};
+use axum::{
+    extract::{OriginalUri, Path, Query, State},
+    http::{header, StatusCode},
+    response::{Html, IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+type PreviewFn = Arc<dyn Fn() -> MailPayload + Send + Sync>;
+
+/// Named sample mails, e.g. `.add("welcome", || WelcomeMail::sample().to_payload())`.
+#[derive(Clone, Default)]
+pub struct MailPreviews {
+    previews: BTreeMap<String, PreviewFn>,
+}
+
+impl MailPreviews {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(
+        mut self,
+        name: impl Into<String>,
+        preview: impl Fn() -> MailPayload + Send + Sync + 'static,
+    ) -> Self {
+        self.previews.insert(name.into(), Arc::new(preview));
+        self
+    }
+
+    /// Render `name` in `locale` (or the current locale).
+    pub async fn render(&self, name: &str, locale: Option<&str>) -> Option<MailPayload> {
+        let preview = self.previews.get(name)?.clone();
+        let payload = match locale.and_then(core_i18n::match_supported_locale) {
+            Some(locale) => core_i18n::scope_locale(locale, || async move { preview() }).await,
+            None => preview(),
+        };
+        Some(payload)
+    }
+}
+
+#[derive(Deserialize)]
+struct PreviewQuery {
+    locale: Option<String>,
+}
+
+/// `GET /` lists previews, `GET /{name}` shows the HTML part and `GET /{name}/text`
+/// the plain-text part. `?locale=ms` renders in another locale.
+pub fn routes(previews: MailPreviews) -> Router {
+    Router::new()
+        .route("/", get(index))
+        .route("/{name}", get(preview_html))
+        .route("/{name}/text", get(preview_text))
+        .with_state(Arc::new(previews))
+}
+
+async fn index(
+    State(previews): State<Arc<MailPreviews>>,
+    OriginalUri(uri): OriginalUri,
+) -> Html<String> {
+    // Absolute links, so the index works with and without a trailing slash.
+    let base = uri.path().trim_end_matches('/');
+    let items: String = previews
+        .previews
+        .keys()
+        .map(|name| {
+            let links: String = core_i18n::supported_locales()
+                .iter()
+                .map(|locale| format!(" <a href=\"{base}/{name}?locale={locale}\">{locale}</a>"))
+                .collect();
+            format!("<li><a href=\"{base}/{name}\">{name}</a> &middot; <a href=\"{base}/{name}/text\">text</a> &middot;{links}</li>")
+        })
+        .collect();
+    Html(format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>Mail previews</title></head><body style=\"font-family:sans-serif;\"><h1>Mail previews</h1><ul>{}</ul></body></html>",
+        items
+    ))
+}
+
+async fn preview_html(
+    State(previews): State<Arc<MailPreviews>>,
+    Path(name): Path<String>,
+    Query(query): Query<PreviewQuery>,
+) -> Response {
+    match previews.render(&name, query.locale.as_deref()).await {
+        Some(payload) => Html(payload.body).into_response(),
+        None => not_found(&name),
+    }
+}
+
+async fn preview_text(
+    State(previews): State<Arc<MailPreviews>>,
+    Path(name): Path<String>,
+    Query(query): Query<PreviewQuery>,
+) -> Response {
+    match previews.render(&name, query.locale.as_deref()).await {
+        Some(payload) => {
+            let text = payload.text.unwrap_or(payload.body);
+            (
+                [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+                format!("Subject: {}\n\n{}", payload.subject, text),
+            )
+                .into_response()
+        }
+        None => not_found(&name),
+    }
+}
+
+fn not_found(name: &str) -> Response {
+    (
+        StatusCode::NOT_FOUND,
+        format!("No mail preview named '{}'", name),
+    )
+        .into_response()
+}
+
+#[derive(Serialize)]
+struct MailboxEntry {
+    index: usize,
+    from: String,
+    to: Vec<String>,
+    subject: String,
+    #[serde(with = "time::serde::rfc3339")]
+    sent_at: time::OffsetDateTime,
+}
+
+/// Inspect a `MemoryTransport`: `GET /` lists messages (JSON), `GET /{index}` shows the
+/// HTML part, `GET /{index}/raw` the MIME source, and `DELETE /` empties the mailbox.
+pub fn mailbox_routes(mailbox: MemoryTransport) -> Router {
+    Router::new()
+        .route("/", get(mailbox_list).delete(mailbox_clear))
+        .route("/{index}", get(mailbox_html))
+        .route("/{index}/raw", get(mailbox_raw))
+        .with_state(mailbox)
+}
+
+async fn mailbox_list(State(mailbox): State<MemoryTransport>) -> Json<Vec<MailboxEntry>> {
+    Json(
+        mailbox
+            .messages()
+            .into_iter()
+            .enumerate()
+            .map(|(index, mail)| MailboxEntry {
+                index,
+                from: mail.from,
+                to: mail.payload.to,
+                subject: mail.payload.subject,
+                sent_at: mail.sent_at,
+            })
+            .collect(),
+    )
+}
+
+async fn mailbox_clear(State(mailbox): State<MemoryTransport>) -> StatusCode {
+    mailbox.clear();
+    StatusCode::NO_CONTENT
+}
+
+async fn mailbox_html(
+    State(mailbox): State<MemoryTransport>,
+    Path(index): Path<usize>,
+) -> Response {
+    match mailbox.messages().into_iter().nth(index) {
+        Some(mail) => Html(mail.payload.body).into_response(),
+        None => (StatusCode::NOT_FOUND, format!("No mail at index {}", index)).into_response(),
+    }
+}
+
+async fn mailbox_raw(State(mailbox): State<MemoryTransport>, Path(index): Path<usize>) -> Response {
+    match mailbox.messages().into_iter().nth(index) {
+        Some(mail) => (
+            [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+            mail.raw,
+        )
+            .into_response(),
+        None => (StatusCode::NOT_FOUND, format!("No mail at index {}", index)).into_response(),
+    }
+}
@@ -0,0 +1,493 @@
+//! Template-rendered mail: content blocks (heading, line, button, panel, table) wrapped in a
+//! shared `MailLayout`, translated for the recipient's locale, with the plain-text part
+//! generated from the same blocks.
+//!
+//! Text passed to blocks is a `core_i18n` key (English text works as its own key).
+//! `:name` placeholders are replaced with values set through `MailTemplate::with`,
+//! HTML-escaped in the HTML part.
+
+use crate::MailPayload;
+use std::sync::{Arc, OnceLock, RwLock};
+
+const DEFAULT_HTML_LAYOUT: &str = r#"<!DOCTYPE html>
+<html lang="{{ locale }}">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{{ subject }}</title>
+</head>
+<body style="margin:0;padding:0;background:#f4f4f5;font-family:-apple-system,'Segoe UI',Roboto,Helvetica,Arial,sans-serif;color:#18181b;">
+<table role="presentation" width="100%" cellpadding="0" cellspacing="0" style="background:#f4f4f5;padding:24px 0;">
+<tr><td align="center">
+<table role="presentation" width="600" cellpadding="0" cellspacing="0" style="max-width:600px;width:100%;">
+<tr><td style="padding:0 24px 16px;font-size:18px;font-weight:bold;">{{ app_name }}</td></tr>
+<tr><td style="background:#ffffff;border-radius:8px;padding:32px 24px;font-size:15px;line-height:1.6;">
+{{ content }}
+</td></tr>
+<tr><td style="padding:16px 24px;font-size:12px;color:#71717a;text-align:center;">&copy; {{ app_name }}</td></tr>
+</table>
+</td></tr>
+</table>
+</body>
+</html>
+"#;
+
+const DEFAULT_TEXT_LAYOUT: &str = "{{ content }}\n\n-- \n{{ app_name }}\n";
+
+/// Page around the rendered blocks. Placeholders: `{{ content }}`, `{{ subject }}`,
+/// `{{ app_name }}` (from `[app] name`) and `{{ locale }}`.
+#[derive(Debug, Clone)]
+pub struct MailLayout {
+    html: String,
+    text: String,
+}
+
+impl Default for MailLayout {
+    fn default() -> Self {
+        Self::new(DEFAULT_HTML_LAYOUT, DEFAULT_TEXT_LAYOUT)
+    }
+}
+
+impl MailLayout {
+    pub fn new(html: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            html: html.into(),
+            text: text.into(),
+        }
+    }
+
+    /// Load the HTML and text layouts from files (e.g. `resources/mail/layout.html`).
+    pub fn from_files(
+        html_path: impl AsRef<std::path::Path>,
+        text_path: impl AsRef<std::path::Path>,
+    ) -> anyhow::Result<Self> {
+        let read = |path: &std::path::Path| {
+            std::fs::read_to_string(path).map_err(|e| {
+                anyhow::anyhow!("Failed to read mail layout {}: {}", path.display(), e)
+            })
+        };
+        Ok(Self::new(
+            read(html_path.as_ref())?,
+            read(text_path.as_ref())?,
+        ))
+    }
+
+    fn wrap(template: &str, content: &str, subject: &str, locale: &str, escape: bool) -> String {
+        let app_name = app_name();
+        let (subject, app_name) = if escape {
+            (escape_html(subject), escape_html(&app_name))
+        } else {
+            (subject.to_string(), app_name)
+        };
+        template
+            .replace("{{ subject }}", &subject)
+            .replace("{{ app_name }}", &app_name)
+            .replace("{{ locale }}", locale)
+            .replace("{{ content }}", content)
+    }
+}
+
+fn default_layout_slot() -> &'static RwLock<Arc<MailLayout>> {
+    static DEFAULT_LAYOUT: OnceLock<RwLock<Arc<MailLayout>>> = OnceLock::new();
+    DEFAULT_LAYOUT.get_or_init(|| RwLock::new(Arc::new(MailLayout::default())))
+}
+
+/// Replace the layout used by templates that do not pick one (call once at startup).
+pub fn set_default_layout(layout: MailLayout) {
+    if let Ok(mut slot) = default_layout_slot().write() {
+        *slot = Arc::new(layout);
+    }
+}
+
+pub fn default_layout() -> Arc<MailLayout> {
+    default_layout_slot()
+        .read()
+        .map(|slot| slot.clone())
+        .unwrap_or_else(|_| Arc::new(MailLayout::default()))
+}
+
+fn app_name() -> String {
+    core_config::global_config::get::<Arc<core_config::Settings>>()
+        .map(|settings| settings.app.name.clone())
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone)]
+enum Block {
+    Heading(String),
+    Line(String),
+    Button {
+        label: String,
+        url: String,
+    },
+    Panel(String),
+    Table {
+        headers: Vec<String>,
+        rows: Vec<Vec<String>>,
+    },
+    Html(String),
+}
+
+/// A mail built from translated blocks. Render it with `render` or `to_payload`.
+#[derive(Debug, Clone)]
+pub struct MailTemplate {
+    subject: String,
+    locale: Option<String>,
+    layout: Option<Arc<MailLayout>>,
+    vars: Vec<(String, String)>,
+    blocks: Vec<Block>,
+}
+
+/// Output of `MailTemplate::render`.
+#[derive(Debug, Clone)]
+pub struct RenderedMail {
+    pub subject: String,
+    pub html: String,
+    pub text: String,
+}
+
+impl RenderedMail {
+    pub fn into_payload(self, to: Vec<String>) -> MailPayload {
+        MailPayload {
+            to,
+            subject: self.subject,
+            body: self.html,
+            text: Some(self.text),
+            ..Default::default()
+        }
+    }
+}
+
+impl MailTemplate {
+    /// `subject` is a translation key like every block text.
+    pub fn new(subject: impl Into<String>) -> Self {
+        Self {
+            subject: subject.into(),
+            locale: None,
+            layout: None,
+            vars: Vec::new(),
+            blocks: Vec::new(),
+        }
+    }
+
+    /// Render in `locale` instead of the current one. Unsupported locales fall back
+    /// to `core_i18n::current_locale`.
+    pub fn locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Use `layout` instead of the default layout.
+    pub fn layout(mut self, layout: Arc<MailLayout>) -> Self {
+        self.layout = Some(layout);
+        self
+    }
+
+    /// Value for `:name` placeholders.
+    pub fn with(mut self, name: impl Into<String>, value: impl ToString) -> Self {
+        self.vars.push((name.into(), value.to_string()));
+        self
+    }
+
+    pub fn heading(mut self, text: impl Into<String>) -> Self {
+        self.blocks.push(Block::Heading(text.into()));
+        self
+    }
+
+    /// A paragraph.
+    pub fn line(mut self, text: impl Into<String>) -> Self {
+        self.blocks.push(Block::Line(text.into()));
+        self
+    }
+
+    /// A call-to-action button. The text part shows `label: url`.
+    pub fn button(mut self, label: impl Into<String>, url: impl Into<String>) -> Self {
+        self.blocks.push(Block::Button {
+            label: label.into(),
+            url: url.into(),
+        });
+        self
+    }
+
+    /// Highlighted box for notes and codes.
+    pub fn panel(mut self, text: impl Into<String>) -> Self {
+        self.blocks.push(Block::Panel(text.into()));
+        self
+    }
+
+    /// Headers are translated; cells are only checked for `:name` placeholders.
+    pub fn table(mut self, headers: Vec<String>, rows: Vec<Vec<String>>) -> Self {
+        self.blocks.push(Block::Table { headers, rows });
+        self
+    }
+
+    /// Raw HTML (not translated). The text part gets it with tags stripped.
+    pub fn html(mut self, html: impl Into<String>) -> Self {
+        self.blocks.push(Block::Html(html.into()));
+        self
+    }
+
+    pub fn render(&self) -> RenderedMail {
+        let locale = self
+            .locale
+            .as_deref()
+            .and_then(core_i18n::match_supported_locale)
+            .unwrap_or_else(core_i18n::current_locale);
+        let subject = self.text(locale, &self.subject);
+
+        let mut html = String::new();
+        let mut text = Vec::new();
+        for block in &self.blocks {
+            match block {
+                Block::Heading(key) => {
+                    let value = self.text(locale, key);
+                    html.push_str(&format!(
+                        "<h1 style=\"margin:0 0 16px;font-size:20px;\">{}</h1>\n",
+                        escape_html(&value)
+                    ));
+                    text.push(format!("{}\n{}", value, "=".repeat(value.chars().count())));
+                }
+                Block::Line(key) => {
+                    let value = self.text(locale, key);
+                    html.push_str(&format!(
+                        "<p style=\"margin:0 0 16px;\">{}</p>\n",
+                        escape_html(&value)
+                    ));
+                    text.push(value);
+                }
+                Block::Button { label, url } => {
+                    let label = self.text(locale, label);
+                    let url = self.fill(url);
+                    html.push_str(&format!(
+                        "<table role=\"presentation\" cellpadding=\"0\" cellspacing=\"0\" style=\"margin:8px 0 24px;\"><tr><td style=\"background:#ea580c;border-radius:6px;\"><a href=\"{}\" style=\"display:inline-block;padding:10px 20px;color:#ffffff;text-decoration:none;font-weight:bold;\">{}</a></td></tr></table>\n",
+                        escape_html(&url),
+                        escape_html(&label)
+                    ));
+                    text.push(format!("{}: {}", label, url));
+                }
+                Block::Panel(key) => {
+                    let value = self.text(locale, key);
+                    html.push_str(&format!(
+                        "<div style=\"margin:0 0 16px;padding:16px;background:#f4f4f5;border-left:4px solid #ea580c;\">{}</div>\n",
+                        escape_html(&value)
+                    ));
+                    text.push(
+                        value
+                            .lines()
+                            .map(|line| format!("> {}", line))
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                    );
+                }
+                Block::Table { headers, rows } => {
+                    let headers: Vec<String> =
+                        headers.iter().map(|key| self.text(locale, key)).collect();
+                    let rows: Vec<Vec<String>> = rows
+                        .iter()
+                        .map(|row| row.iter().map(|cell| self.fill(cell)).collect())
+                        .collect();
+                    html.push_str(&table_html(&headers, &rows));
+                    text.push(table_text(&headers, &rows));
+                }
+                Block::Html(raw) => {
+                    let value = self.fill_escaped(raw);
+                    text.push(strip_tags(&value));
+                    html.push_str(&value);
+                    html.push('\n');
+                }
+            }
+        }
+
+        let layout = self.layout.clone().unwrap_or_else(default_layout);
+        RenderedMail {
+            html: MailLayout::wrap(&layout.html, &html, &subject, locale, true),
+            text: MailLayout::wrap(&layout.text, &text.join("\n\n"), &subject, locale, false),
+            subject,
+        }
+    }
+
+    /// Render and address the mail.
+    pub fn to_payload(&self, to: Vec<String>) -> MailPayload {
+        self.render().into_payload(to)
+    }
+
+    fn text(&self, locale: &str, key: &str) -> String {
+        self.fill(&core_i18n::t_for_locale(locale, key))
+    }
+
+    fn fill(&self, template: &str) -> String {
+        self.replace_vars(template, |value| value.to_string())
+    }
+
+    fn fill_escaped(&self, template: &str) -> String {
+        self.replace_vars(template, escape_html)
+    }
+
+    fn replace_vars(&self, template: &str, convert: impl Fn(&str) -> String) -> String {
+        // Longest names first so `:user` does not eat the start of `:user_name`.
+        let mut vars: Vec<&(String, String)> = self.vars.iter().collect();
+        vars.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+        let mut out = template.to_string();
+        for (name, value) in vars {
+            out = out.replace(&format!(":{}", name), &convert(value));
+        }
+        out
+    }
+}
+
+/// A mailable rendered from a `MailTemplate`; gets `Mailable` for free.
+pub trait TemplatedMailable: Send + Sync {
+    fn to(&self) -> Vec<String>;
+    fn template(&self) -> MailTemplate;
+}
+
+impl<T: TemplatedMailable> crate::Mailable for T {
+    fn subject(&self) -> String {
+        self.template().render().subject
+    }
+
+    fn body(&self) -> String {
+        self.template().render().html
+    }
+
+    fn to(&self) -> Vec<String> {
+        TemplatedMailable::to(self)
+    }
+
+    fn text(&self) -> Option<String> {
+        Some(self.template().render().text)
+    }
+
+    fn to_payload(&self) -> MailPayload {
+        self.template().to_payload(TemplatedMailable::to(self))
+    }
+}
+
+fn table_html(headers: &[String], rows: &[Vec<String>]) -> String {
+    let cell = "padding:8px;border-bottom:1px solid #e4e4e7;text-align:left;";
+    let mut html = String::from(
+        "<table role=\"presentation\" width=\"100%\" cellpadding=\"0\" cellspacing=\"0\" style=\"margin:0 0 16px;border-collapse:collapse;\">\n",
+    );
+    if !headers.is_empty() {
+        html.push_str("<tr>");
+        for header in headers {
+            html.push_str(&format!(
+                "<th style=\"{}\">{}</th>",
+                cell,
+                escape_html(header)
+            ));
+        }
+        html.push_str("</tr>\n");
+    }
+    for row in rows {
+        html.push_str("<tr>");
+        for value in row {
+            html.push_str(&format!(
+                "<td style=\"{}\">{}</td>",
+                cell,
+                escape_html(value)
+            ));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+    html
+}
+
+fn table_text(headers: &[String], rows: &[Vec<String>]) -> String {
+    let mut lines = Vec::new();
+    if !headers.is_empty() {
+        lines.push(headers.join(" | "));
+        lines.push("-".repeat(lines[0].chars().count()));
+    }
+    for row in rows {
+        lines.push(row.join(" | "));
+    }
+    lines.join("\n")
+}
+
+fn escape_html(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escape_html, strip_tags, MailLayout, MailTemplate};
+    use std::sync::Arc;
+
+    #[test]
+    fn escape_html_escapes_markup_and_quotes() {
+        assert_eq!(
+            escape_html(r#"<a href="x">Tom & Jerry's</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+        assert_eq!(escape_html("plain"), "plain");
+    }
+
+    #[test]
+    fn strip_tags_removes_markup_and_decodes_entities() {
+        assert_eq!(
+            strip_tags("<p>Tom &amp; Jerry&nbsp;<b>&lt;3</b></p>"),
+            "Tom & Jerry <3"
+        );
+        // `&amp;` is decoded last, so escaped entities stay literal.
+        assert_eq!(strip_tags("&amp;lt;"), "&lt;");
+        assert_eq!(strip_tags("  <br/>  "), "");
+    }
+
+    #[test]
+    fn layout_escapes_subject_in_html_only() {
+        let layout = Arc::new(MailLayout::new(
+            "<title>{{ subject }}</title><html lang=\"{{ locale }}\">{{ content }}</html>",
+            "{{ subject }}\n{{ content }}",
+        ));
+        let rendered = MailTemplate::new("Hello :name")
+            .locale("en")
+            .layout(layout)
+            .with("name", "<Ada>")
+            .line("Tom & Jerry")
+            .html("<b>:name</b>")
+            .render();
+
+        assert_eq!(rendered.subject, "Hello <Ada>");
+        assert_eq!(
+            rendered.html,
+            "<title>Hello &lt;Ada&gt;</title><html lang=\"en\">\
+             <p style=\"margin:0 0 16px;\">Tom &amp; Jerry</p>\n\
+             <b>&lt;Ada&gt;</b>\n</html>"
+        );
+        assert_eq!(rendered.text, "Hello <Ada>\nTom & Jerry\n\n<Ada>");
+    }
+}
@@ -0,0 +1,943 @@
+//! Delivery backends for `Mailer` (`[mail] driver`).
+//!
+//! `smtp` and `sendmail` hand the MIME message to a relay, `file` writes `.eml` files,
+//! `memory` keeps a mailbox for tests and local dev, `log` only logs, and `postmark`,
+//! `mailgun` and `http` call a provider's HTTP API. `failover` tries a list of those in order,
+//! and `[mail] rate_limits_per_process` caps how fast each one sends.
+
+use crate::MailPayload;
+use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine as _};
+use lettre::{
+    transport::smtp::authentication::Credentials, AsyncSendmailTransport, AsyncSmtpTransport,
+    AsyncTransport, Message, Tokio1Executor,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use time::OffsetDateTime;
+
+/// A mail ready for delivery: the payload, its attachment bytes and the built MIME message.
+#[derive(Debug, Clone)]
+pub struct OutgoingMail {
+    pub from: String,
+    pub payload: MailPayload,
+    /// `payload.attachments` with their bytes loaded (storage keys resolved).
+    pub attachments: Vec<OutgoingAttachment>,
+    /// `Message-ID` header of `message`, with angle brackets.
+    pub message_id: String,
+    pub message: Message,
+}
+
+#[derive(Debug, Clone)]
+pub struct OutgoingAttachment {
+    pub filename: String,
+    pub content_type: String,
+    pub content_id: Option<String>,
+    pub data: Vec<u8>,
+}
+
+impl OutgoingMail {
+    /// Every envelope recipient (to, cc and bcc).
+    pub fn recipients(&self) -> Vec<String> {
+        self.payload.recipients().cloned().collect()
+    }
+
+    /// The message as RFC 5322 bytes (`.eml` contents).
+    pub fn formatted(&self) -> Vec<u8> {
+        self.message.formatted()
+    }
+}
+
+/// Outcome of a successful `MailTransport::send`.
+#[derive(Debug, Clone)]
+pub struct Delivery {
+    /// Transport that accepted the mail (the inner one for `FailoverTransport`).
+    pub transport: &'static str,
+    /// Provider-assigned id, when the API returns one instead of keeping our `Message-ID`.
+    pub message_id: Option<String>,
+}
+
+impl Delivery {
+    pub fn via(transport: &'static str) -> Self {
+        Self {
+            transport,
+            message_id: None,
+        }
+    }
+}
+
+#[async_trait]
+pub trait MailTransport: Send + Sync {
+    /// Driver name for logs (e.g. `"smtp"`).
+    fn name(&self) -> &'static str;
+
+    async fn send(&self, mail: &OutgoingMail) -> anyhow::Result<Delivery>;
+}
+
+/// Build the transport selected by `[mail] driver`, with `[mail] rate_limits_per_process` applied.
+pub fn from_settings(
+    settings: &core_config::MailSettings,
+) -> anyhow::Result<Arc<dyn MailTransport>> {
+    let limits = parse_rate_limits(&settings.rate_limits_per_process)?;
+    let build = |driver: &str| -> anyhow::Result<Arc<dyn MailTransport>> {
+        let transport = driver_from_settings(driver, settings)?;
+        Ok(match limits.get(driver) {
+            Some(&(max, window)) => Arc::new(RateLimitedTransport::new(transport, max, window)),
+            None => transport,
+        })
+    };
+
+    if settings.driver != "failover" {
+        return build(&settings.driver);
+    }
+    let transports = settings
+        .failover
+        .split(',')
+        .map(str::trim)
+        .filter(|driver| !driver.is_empty())
+        .map(|driver| match driver {
+            "failover" => anyhow::bail!("[mail] failover cannot contain 'failover'"),
+            driver => build(driver),
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    if transports.is_empty() {
+        anyhow::bail!("Mail driver 'failover' needs [mail] failover, e.g. \"smtp,postmark\"");
+    }
+    Ok(Arc::new(FailoverTransport::new(transports)))
+}
+
+fn driver_from_settings(
+    driver: &str,
+    settings: &core_config::MailSettings,
+) -> anyhow::Result<Arc<dyn MailTransport>> {
+    match driver {
+        "log" => Ok(Arc::new(LogTransport)),
+        "smtp" => Ok(Arc::new(SmtpTransport::from_settings(settings)?)),
+        "file" => Ok(Arc::new(FileTransport::new(&settings.file_dir))),
+        "memory" => Ok(Arc::new(MemoryTransport::shared())),
+        "sendmail" => Ok(Arc::new(SendmailTransport::new(&settings.sendmail_path))),
+        "postmark" => Ok(Arc::new(
+            HttpApiTransport::postmark(&settings.api_key).endpoint_opt(settings.api_url_opt()),
+        )),
+        "mailgun" => Ok(Arc::new(
+            HttpApiTransport::mailgun(&settings.api_key, &settings.api_domain)
+                .endpoint_opt(settings.api_url_opt()),
+        )),
+        "http" => {
+            let url = settings.api_url_opt().ok_or_else(|| {
+                anyhow::anyhow!("Mail driver 'http' needs [mail] api_url")
+            })?;
+            Ok(Arc::new(HttpApiTransport::json(url, &settings.api_key)))
+        }
+        other => anyhow::bail!(
+            "Unknown mail driver '{other}' (expected smtp, log, file, memory, sendmail, postmark, mailgun, http or failover)"
+        ),
+    }
+}
+
+/// Logs the mail instead of sending it.
+pub struct LogTransport;
+
+#[async_trait]
+impl MailTransport for LogTransport {
+    fn name(&self) -> &'static str {
+        "log"
+    }
+
+    async fn send(&self, mail: &OutgoingMail) -> anyhow::Result<Delivery> {
+        let payload = &mail.payload;
+        tracing::info!(
+            "[MAIL LOG] To: {:?}, Cc: {:?}, Bcc: {:?}, Subject: {}, Attachments: {:?}, Body: {}",
+            payload.to,
+            payload.cc,
+            payload.bcc,
+            payload.subject,
+            mail.attachments
+                .iter()
+                .map(|attachment| attachment.filename.as_str())
+                .collect::<Vec<_>>(),
+            payload.body
+        );
+        Ok(Delivery::via(self.name()))
+    }
+}
+
+pub struct SmtpTransport {
+    inner: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl SmtpTransport {
+    pub fn from_settings(settings: &core_config::MailSettings) -> anyhow::Result<Self> {
+        let mut builder = AsyncSmtpTransport::<Tokio1Executor>::relay(&settings.host)?;
+        builder = builder.port(settings.port);
+
+        if let (Some(u), Some(p)) = (settings.username_opt(), settings.password_opt()) {
+            builder = builder.credentials(Credentials::new(u.to_string(), p.to_string()));
+        }
+
+        Ok(Self {
+            inner: builder.build(),
+        })
+    }
+}
+
+#[async_trait]
+impl MailTransport for SmtpTransport {
+    fn name(&self) -> &'static str {
+        "smtp"
+    }
+
+    async fn send(&self, mail: &OutgoingMail) -> anyhow::Result<Delivery> {
+        self.inner.send(mail.message.clone()).await?;
+        Ok(Delivery::via(self.name()))
+    }
+}
+
+/// Pipes the message to a local `sendmail`-compatible binary.
+pub struct SendmailTransport {
+    inner: AsyncSendmailTransport<Tokio1Executor>,
+}
+
+impl SendmailTransport {
+    pub fn new(command: &str) -> Self {
+        Self {
+            inner: AsyncSendmailTransport::new_with_command(command),
+        }
+    }
+}
+
+#[async_trait]
+impl MailTransport for SendmailTransport {
+    fn name(&self) -> &'static str {
+        "sendmail"
+    }
+
+    async fn send(&self, mail: &OutgoingMail) -> anyhow::Result<Delivery> {
+        self.inner.send(mail.message.clone()).await?;
+        Ok(Delivery::via(self.name()))
+    }
+}
+
+/// Writes each message to `<dir>/<unix_ms>-<uuid>.eml`.
+pub struct FileTransport {
+    dir: PathBuf,
+}
+
+impl FileTransport {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl MailTransport for FileTransport {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    async fn send(&self, mail: &OutgoingMail) -> anyhow::Result<Delivery> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let now = OffsetDateTime::now_utc();
+        let path = self.dir.join(format!(
+            "{}-{}.eml",
+            now.unix_timestamp_nanos() / 1_000_000,
+            uuid::Uuid::new_v4()
+        ));
+        tokio::fs::write(&path, mail.formatted())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to write mail to {}: {}", path.display(), e))?;
+        tracing::info!("Email written to {}", path.display());
+        Ok(Delivery::via(self.name()))
+    }
+}
+
+/// Messages kept by the process-wide memory mailbox; older ones are dropped.
+const MEMORY_MAILBOX_LIMIT: usize = 500;
+
+/// A mail captured by `MemoryTransport`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SentMail {
+    pub from: String,
+    pub payload: MailPayload,
+    /// The full MIME message.
+    pub raw: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub sent_at: OffsetDateTime,
+}
+
+/// Keeps sent mail in memory. Clones share the mailbox.
+///
+/// The `memory` driver uses `MemoryTransport::shared()`, so the web process and embedded
+/// workers fill one mailbox; browse it with `preview::mailbox_routes`.
+#[derive(Clone, Default)]
+pub struct MemoryTransport {
+    sent: Arc<Mutex<Vec<SentMail>>>,
+}
+
+impl MemoryTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The process-wide mailbox behind the `memory` driver.
+    pub fn shared() -> Self {
+        static SHARED: OnceLock<MemoryTransport> = OnceLock::new();
+        SHARED.get_or_init(MemoryTransport::new).clone()
+    }
+
+    /// Everything sent so far, oldest first.
+    pub fn messages(&self) -> Vec<SentMail> {
+        self.sent
+            .lock()
+            .map(|sent| sent.clone())
+            .unwrap_or_default()
+    }
+
+    /// Mail with `address` among its to, cc or bcc recipients.
+    pub fn sent_to(&self, address: &str) -> Vec<SentMail> {
+        self.messages()
+            .into_iter()
+            .filter(|mail| mail.payload.recipients().any(|to| to == address))
+            .collect()
+    }
+
+    pub fn last(&self) -> Option<SentMail> {
+        self.sent.lock().ok().and_then(|sent| sent.last().cloned())
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut sent) = self.sent.lock() {
+            sent.clear();
+        }
+    }
+
+    /// Panics unless a mail matching `matches` was sent.
+    #[track_caller]
+    pub fn assert_sent(&self, matches: impl Fn(&MailPayload) -> bool) {
+        if !self.messages().iter().any(|mail| matches(&mail.payload)) {
+            panic!(
+                "expected a matching mail to be sent; sent: {:?}",
+                self.summaries()
+            );
+        }
+    }
+
+    /// Panics unless a mail was sent to `address`.
+    #[track_caller]
+    pub fn assert_sent_to(&self, address: &str) {
+        if self.sent_to(address).is_empty() {
+            panic!(
+                "expected a mail to be sent to {}; sent: {:?}",
+                address,
+                self.summaries()
+            );
+        }
+    }
+
+    /// Panics unless exactly `count` mails were sent.
+    #[track_caller]
+    pub fn assert_sent_count(&self, count: usize) {
+        let sent = self.messages().len();
+        if sent != count {
+            panic!("expected {} mail(s) to be sent, got {}", count, sent);
+        }
+    }
+
+    #[track_caller]
+    pub fn assert_nothing_sent(&self) {
+        let summaries = self.summaries();
+        if !summaries.is_empty() {
+            panic!("expected no mail to be sent; sent: {:?}", summaries);
+        }
+    }
+
+    fn summaries(&self) -> Vec<String> {
+        self.messages()
+            .iter()
+            .map(|mail| format!("{:?}: {}", mail.payload.to, mail.payload.subject))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl MailTransport for MemoryTransport {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn send(&self, mail: &OutgoingMail) -> anyhow::Result<Delivery> {
+        let sent_mail = SentMail {
+            from: mail.from.clone(),
+            payload: mail.payload.clone(),
+            raw: String::from_utf8_lossy(&mail.formatted()).into_owned(),
+            sent_at: OffsetDateTime::now_utc(),
+        };
+        if let Ok(mut sent) = self.sent.lock() {
+            if sent.len() >= MEMORY_MAILBOX_LIMIT {
+                sent.remove(0);
+            }
+            sent.push(sent_mail);
+        }
+        Ok(Delivery::via(self.name()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpApiProvider {
+    /// `POST {endpoint}/email` with Postmark's JSON body and `X-Postmark-Server-Token`.
+    Postmark,
+    /// `POST {endpoint}/v3/{domain}/messages.mime` (multipart, basic auth `api:<key>`).
+    Mailgun,
+    /// `POST {endpoint}` with the framework's JSON body and a bearer token,
+    /// for relays and provider adapters (e.g. an SES forwarding function).
+    Json,
+}
+
+/// Time allowed to open a connection to the provider API.
+const HTTP_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Time allowed for a whole API call, so a hung provider fails over instead of blocking.
+const HTTP_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Sends through a provider's HTTP API. Override `endpoint` to point at a local stub server.
+pub struct HttpApiTransport {
+    provider: HttpApiProvider,
+    client: reqwest::Client,
+    endpoint: String,
+    api_key: String,
+    domain: String,
+}
+
+impl HttpApiTransport {
+    pub fn postmark(api_key: &str) -> Self {
+        Self::new(
+            HttpApiProvider::Postmark,
+            "https://api.postmarkapp.com",
+            api_key,
+        )
+    }
+
+    pub fn mailgun(api_key: &str, domain: &str) -> Self {
+        let mut transport = Self::new(HttpApiProvider::Mailgun, "https://api.mailgun.net", api_key);
+        transport.domain = domain.to_string();
+        transport
+    }
+
+    pub fn json(endpoint: &str, api_key: &str) -> Self {
+        Self::new(HttpApiProvider::Json, endpoint, api_key)
+    }
+
+    fn new(provider: HttpApiProvider, endpoint: &str, api_key: &str) -> Self {
+        // Same failure mode as `reqwest::Client::new`: only fails if TLS cannot initialize.
+        let client = reqwest::Client::builder()
+            .connect_timeout(HTTP_CONNECT_TIMEOUT)
+            .timeout(HTTP_REQUEST_TIMEOUT)
+            .build()
+            .expect("TLS backend for the mail HTTP client");
+        Self {
+            provider,
+            client,
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+            domain: String::new(),
+        }
+    }
+
+    /// Base URL of the API (e.g. `http://127.0.0.1:8025` in tests).
+    pub fn endpoint(mut self, endpoint: &str) -> Self {
+        self.endpoint = endpoint.trim_end_matches('/').to_string();
+        self
+    }
+
+    fn endpoint_opt(self, endpoint: Option<&str>) -> Self {
+        match endpoint {
+            Some(endpoint) => self.endpoint(endpoint),
+            None => self,
+        }
+    }
+
+    fn request(&self, mail: &OutgoingMail) -> anyhow::Result<reqwest::RequestBuilder> {
+        Ok(match self.provider {
+            HttpApiProvider::Postmark => self
+                .client
+                .post(format!("{}/email", self.endpoint))
+                .header("Accept", "application/json")
+                .header("X-Postmark-Server-Token", &self.api_key)
+                .json(&postmark_body(mail)),
+            HttpApiProvider::Mailgun => {
+                if self.domain.is_empty() {
+                    anyhow::bail!("Mailgun transport needs [mail] api_domain");
+                }
+                let form = reqwest::multipart::Form::new()
+                    .text("to", mail.recipients().join(","))
+                    .part(
+                        "message",
+                        reqwest::multipart::Part::bytes(mail.formatted()).file_name("message.mime"),
+                    );
+                self.client
+                    .post(format!(
+                        "{}/v3/{}/messages.mime",
+                        self.endpoint, self.domain
+                    ))
+                    .basic_auth("api", Some(&self.api_key))
+                    .multipart(form)
+            }
+            HttpApiProvider::Json => {
+                let request = self.client.post(&self.endpoint).json(&json_body(mail));
+                if self.api_key.is_empty() {
+                    request
+                } else {
+                    request.bearer_auth(&self.api_key)
+                }
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl MailTransport for HttpApiTransport {
+    fn name(&self) -> &'static str {
+        match self.provider {
+            HttpApiProvider::Postmark => "postmark",
+            HttpApiProvider::Mailgun => "mailgun",
+            HttpApiProvider::Json => "http",
+        }
+    }
+
+    async fn send(&self, mail: &OutgoingMail) -> anyhow::Result<Delivery> {
+        let response = self.request(mail)?.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "{} API returned {}: {}",
+                self.name(),
+                status,
+                body.chars().take(500).collect::<String>()
+            );
+        }
+        // Postmark answers `MessageID`, Mailgun `id`; the generic API may send either.
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let message_id = ["MessageID", "id", "message_id"]
+            .iter()
+            .find_map(|key| body.get(*key).and_then(|id| id.as_str()))
+            .map(str::to_string);
+        Ok(Delivery {
+            transport: self.name(),
+            message_id,
+        })
+    }
+}
+
+/// Tries each transport in order until one accepts the mail.
+pub struct FailoverTransport {
+    transports: Vec<Arc<dyn MailTransport>>,
+}
+
+impl FailoverTransport {
+    pub fn new(transports: Vec<Arc<dyn MailTransport>>) -> Self {
+        Self { transports }
+    }
+}
+
+#[async_trait]
+impl MailTransport for FailoverTransport {
+    fn name(&self) -> &'static str {
+        "failover"
+    }
+
+    async fn send(&self, mail: &OutgoingMail) -> anyhow::Result<Delivery> {
+        let mut errors = Vec::new();
+        for transport in &self.transports {
+            match transport.send(mail).await {
+                Ok(delivery) => return Ok(delivery),
+                Err(e) => {
+                    tracing::warn!(
+                        "Mail transport {} failed, trying the next one: {:#}",
+                        transport.name(),
+                        e
+                    );
+                    errors.push(format!("{}: {:#}", transport.name(), e));
+                }
+            }
+        }
+        anyhow::bail!("All mail transports failed ({})", errors.join("; "))
+    }
+}
+
+/// Allows at most `max` sends per `window` through `inner` in this process; other processes
+/// keep their own count. Sends over the limit wait for the next window.
+pub struct RateLimitedTransport {
+    inner: Arc<dyn MailTransport>,
+    max: u32,
+    window: Duration,
+    state: tokio::sync::Mutex<(Instant, u32)>,
+}
+
+impl RateLimitedTransport {
+    pub fn new(inner: Arc<dyn MailTransport>, max: u32, window: Duration) -> Self {
+        Self {
+            inner,
+            max: max.max(1),
+            window,
+            state: tokio::sync::Mutex::new((Instant::now(), 0)),
+        }
+    }
+
+    async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().await;
+                let (started, sent) = &mut *state;
+                if started.elapsed() >= self.window {
+                    *started = Instant::now();
+                    *sent = 0;
+                }
+                if *sent < self.max {
+                    *sent += 1;
+                    return;
+                }
+                self.window.saturating_sub(started.elapsed())
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[async_trait]
+impl MailTransport for RateLimitedTransport {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    async fn send(&self, mail: &OutgoingMail) -> anyhow::Result<Delivery> {
+        self.acquire().await;
+        self.inner.send(mail).await
+    }
+}
+
+/// Parse `[mail] rate_limits_per_process` (`smtp=10/1,postmark=50/60`: count per seconds).
+fn parse_rate_limits(raw: &str) -> anyhow::Result<HashMap<String, (u32, Duration)>> {
+    let mut limits = HashMap::new();
+    for entry in raw
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let parsed = entry.split_once('=').and_then(|(driver, limit)| {
+            let (max, secs) = limit.split_once('/')?;
+            let max = max.trim().parse::<u32>().ok()?;
+            let secs = secs.trim().parse::<u64>().ok().filter(|secs| *secs > 0)?;
+            Some((driver.trim().to_string(), (max, Duration::from_secs(secs))))
+        });
+        let (driver, limit) = parsed.ok_or_else(|| {
+            anyhow::anyhow!(
+                "Invalid [mail] rate_limits_per_process entry '{entry}' (expected driver=count/seconds)"
+            )
+        })?;
+        limits.insert(driver, limit);
+    }
+    Ok(limits)
+}
+
+fn postmark_body(mail: &OutgoingMail) -> serde_json::Value {
+    let payload = &mail.payload;
+    let mut body = serde_json::json!({
+        "From": mail.from,
+        "To": payload.to.join(","),
+        "Subject": payload.subject,
+        "HtmlBody": payload.body,
+        "Headers": payload
+            .headers
+            .iter()
+            .map(|(name, value)| serde_json::json!({ "Name": name, "Value": value }))
+            .collect::<Vec<_>>(),
+        "Attachments": mail
+            .attachments
+            .iter()
+            .map(|attachment| serde_json::json!({
+                "Name": attachment.filename,
+                "Content": general_purpose::STANDARD.encode(&attachment.data),
+                "ContentType": attachment.content_type,
+                "ContentID": attachment.content_id.as_ref().map(|id| format!("cid:{id}")),
+            }))
+            .collect::<Vec<_>>(),
+    });
+    if !payload.cc.is_empty() {
+        body["Cc"] = payload.cc.join(",").into();
+    }
+    if !payload.bcc.is_empty() {
+        body["Bcc"] = payload.bcc.join(",").into();
+    }
+    if let Some(reply_to) = &payload.reply_to {
+        body["ReplyTo"] = reply_to.clone().into();
+    }
+    if let Some(text) = &payload.text {
+        body["TextBody"] = text.clone().into();
+    }
+    body
+}
+
+fn json_body(mail: &OutgoingMail) -> serde_json::Value {
+    let payload = &mail.payload;
+    serde_json::json!({
+        "from": mail.from,
+        "to": payload.to,
+        "cc": payload.cc,
+        "bcc": payload.bcc,
+        "reply_to": payload.reply_to,
+        "subject": payload.subject,
+        "html": payload.body,
+        "text": payload.text,
+        "headers": payload.headers,
+        "attachments": mail
+            .attachments
+            .iter()
+            .map(|attachment| serde_json::json!({
+                "filename": attachment.filename,
+                "content_type": attachment.content_type,
+                "content_id": attachment.content_id,
+                "content": general_purpose::STANDARD.encode(&attachment.data),
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parse_rate_limits, Delivery, FailoverTransport, HttpApiTransport, MailTransport,
+        OutgoingMail,
+    };
+    use crate::MailPayload;
+    use async_trait::async_trait;
+    use axum::{body::Bytes, extract::State, http::HeaderMap, routing::post, Json, Router};
+    use base64::{engine::general_purpose, Engine as _};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// Requests seen by the stub: path, headers and raw body.
+    type Seen = Arc<Mutex<Vec<(String, HeaderMap, Bytes)>>>;
+
+    async fn stub_server() -> (String, Seen) {
+        fn record(seen: &Seen, path: &str, headers: HeaderMap, body: Bytes) {
+            seen.lock()
+                .expect("seen")
+                .push((path.to_string(), headers, body));
+        }
+        let seen: Seen = Arc::default();
+        let app = Router::new()
+            .route(
+                "/email",
+                post(
+                    |State(seen): State<Seen>, headers: HeaderMap, body: Bytes| async move {
+                        record(&seen, "/email", headers, body);
+                        Json(serde_json::json!({ "MessageID": "pm-1", "ErrorCode": 0 }))
+                    },
+                ),
+            )
+            .route(
+                "/v3/mg.example.com/messages.mime",
+                post(
+                    |State(seen): State<Seen>, headers: HeaderMap, body: Bytes| async move {
+                        record(&seen, "/mailgun", headers, body);
+                        Json(serde_json::json!({ "id": "<mg-1@mg.example.com>" }))
+                    },
+                ),
+            )
+            .route(
+                "/relay",
+                post(
+                    |State(seen): State<Seen>, headers: HeaderMap, body: Bytes| async move {
+                        record(&seen, "/relay", headers, body);
+                        Json(serde_json::json!({ "message_id": "relay-1" }))
+                    },
+                ),
+            )
+            .with_state(seen.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind stub");
+        let addr = listener.local_addr().expect("stub addr");
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (format!("http://{addr}"), seen)
+    }
+
+    fn mail() -> OutgoingMail {
+        let payload = MailPayload::new("ada@example.com".into(), "Hi".into(), "<p>Hi</p>".into())
+            .cc("grace@example.com")
+            .text("Hi");
+        let message = lettre::Message::builder()
+            .from("App <hello@example.com>".parse().expect("from"))
+            .to("ada@example.com".parse().expect("to"))
+            .cc("grace@example.com".parse().expect("cc"))
+            .subject("Hi")
+            .body("<p>Hi</p>".to_string())
+            .expect("message");
+        OutgoingMail {
+            from: "App <hello@example.com>".to_string(),
+            payload,
+            attachments: Vec::new(),
+            message_id: "<id@example.com>".to_string(),
+            message,
+        }
+    }
+
+    fn header<'a>(headers: &'a HeaderMap, name: &str) -> &'a str {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn http_api_providers_send_body_auth_and_read_message_id() {
+        let (base, seen) = stub_server().await;
+
+        let postmark = HttpApiTransport::postmark("pm-token").endpoint(&base);
+        let delivery = postmark.send(&mail()).await.expect("postmark");
+        assert_eq!(delivery.transport, "postmark");
+        assert_eq!(delivery.message_id.as_deref(), Some("pm-1"));
+
+        let mailgun = HttpApiTransport::mailgun("key-1", "mg.example.com").endpoint(&base);
+        let delivery = mailgun.send(&mail()).await.expect("mailgun");
+        assert_eq!(delivery.transport, "mailgun");
+        assert_eq!(
+            delivery.message_id.as_deref(),
+            Some("<mg-1@mg.example.com>")
+        );
+
+        let relay = HttpApiTransport::json(&format!("{base}/relay"), "relay-secret");
+        let delivery = relay.send(&mail()).await.expect("json");
+        assert_eq!(delivery.transport, "http");
+        assert_eq!(delivery.message_id.as_deref(), Some("relay-1"));
+
+        let seen = seen.lock().expect("seen");
+        assert_eq!(seen.len(), 3);
+
+        let (path, headers, body) = &seen[0];
+        assert_eq!(path, "/email");
+        assert_eq!(header(headers, "x-postmark-server-token"), "pm-token");
+        let body: serde_json::Value = serde_json::from_slice(body).expect("postmark json");
+        assert_eq!(body["From"], "App <hello@example.com>");
+        assert_eq!(body["To"], "ada@example.com");
+        assert_eq!(body["Cc"], "grace@example.com");
+        assert_eq!(body["Subject"], "Hi");
+        assert_eq!(body["HtmlBody"], "<p>Hi</p>");
+        assert_eq!(body["TextBody"], "Hi");
+
+        let (path, headers, body) = &seen[1];
+        assert_eq!(path, "/mailgun");
+        let basic = general_purpose::STANDARD.encode("api:key-1");
+        assert_eq!(header(headers, "authorization"), format!("Basic {basic}"));
+        assert!(header(headers, "content-type").starts_with("multipart/form-data"));
+        let body = String::from_utf8_lossy(body);
+        assert!(body.contains("name=\"to\"\r\n\r\nada@example.com,grace@example.com\r\n"));
+        assert!(body.contains("filename=\"message.mime\""));
+        assert!(body.contains("Subject: Hi"));
+
+        let (path, headers, body) = &seen[2];
+        assert_eq!(path, "/relay");
+        assert_eq!(header(headers, "authorization"), "Bearer relay-secret");
+        let body: serde_json::Value = serde_json::from_slice(body).expect("relay json");
+        assert_eq!(body["from"], "App <hello@example.com>");
+        assert_eq!(body["to"], serde_json::json!(["ada@example.com"]));
+        assert_eq!(body["cc"], serde_json::json!(["grace@example.com"]));
+        assert_eq!(body["html"], "<p>Hi</p>");
+        assert_eq!(body["text"], "Hi");
+    }
+
+    #[tokio::test]
+    async fn http_api_error_status_fails_the_send() {
+        let (base, _seen) = stub_server().await;
+        let relay = HttpApiTransport::json(&format!("{base}/missing"), "");
+
+        let err = relay.send(&mail()).await.expect_err("404");
+        assert!(err.to_string().contains("http API returned 404"));
+    }
+
+    /// Appends its name to a shared call log, then fails or accepts.
+    struct Recording {
+        name: &'static str,
+        fail: bool,
+        calls: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    #[async_trait]
+    impl MailTransport for Recording {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        async fn send(&self, _mail: &OutgoingMail) -> anyhow::Result<Delivery> {
+            self.calls.lock().expect("calls").push(self.name);
+            if self.fail {
+                anyhow::bail!("{} is down", self.name);
+            }
+            Ok(Delivery::via(self.name))
+        }
+    }
+
+    fn failover(
+        transports: &[(&'static str, bool)],
+    ) -> (FailoverTransport, Arc<Mutex<Vec<&'static str>>>) {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let transports = transports
+            .iter()
+            .map(|(name, fail)| {
+                Arc::new(Recording {
+                    name,
+                    fail: *fail,
+                    calls: calls.clone(),
+                }) as Arc<dyn MailTransport>
+            })
+            .collect();
+        (FailoverTransport::new(transports), calls)
+    }
+
+    #[tokio::test]
+    async fn failover_stops_at_the_first_transport_that_accepts() {
+        let (transport, calls) = failover(&[("smtp", true), ("postmark", false), ("log", false)]);
+
+        let delivery = transport.send(&mail()).await.expect("delivered");
+        assert_eq!(delivery.transport, "postmark");
+        assert_eq!(*calls.lock().expect("calls"), ["smtp", "postmark"]);
+    }
+
+    #[tokio::test]
+    async fn failover_reports_every_error_when_all_fail() {
+        let (transport, calls) = failover(&[("smtp", true), ("postmark", true)]);
+
+        let err = transport.send(&mail()).await.expect_err("all down");
+        assert_eq!(
+            err.to_string(),
+            "All mail transports failed (smtp: smtp is down; postmark: postmark is down)"
+        );
+        assert_eq!(*calls.lock().expect("calls"), ["smtp", "postmark"]);
+    }
+
+    #[test]
+    fn parse_rate_limits_reads_count_per_seconds() {
+        let limits = parse_rate_limits(" smtp=10/1, postmark = 50/60 ,").expect("limits");
+        assert_eq!(limits.len(), 2);
+        assert_eq!(limits["smtp"], (10, Duration::from_secs(1)));
+        assert_eq!(limits["postmark"], (50, Duration::from_secs(60)));
+        assert!(parse_rate_limits("").expect("empty").is_empty());
+    }
+
+    #[test]
+    fn parse_rate_limits_rejects_malformed_entries() {
+        for raw in [
+            "smtp",
+            "smtp=10",
+            "smtp=ten/1",
+            "smtp=10/0",
+            "smtp=10/1,mailgun=5",
+        ] {
+            let err = parse_rate_limits(raw).expect_err(raw);
+            assert!(err.to_string().contains("expected driver=count/seconds"));
+        }
+    }
+}
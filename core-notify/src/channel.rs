@@ -1,18 +1,52 @@
-use crate::{Mailable, Notifiable};
+use crate::{Mailable, Notifiable, Notification};
 use anyhow::Result;
 use async_trait::async_trait;
+use core_mailer::{MailPayload, Mailer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// A notification rendered for one channel. This is what `SendNotificationJob` carries,
+/// so `deliver` never needs the original notification or notifiable.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChannelMessage {
+    pub channel: String,
+    /// Shared by every channel of one send; the `notifications.id` of the database row.
+    pub notification_id: Uuid,
+    pub notification_type: String,
+    pub notifiable_type: String,
+    pub notifiable_id: String,
+    pub content: Value,
+}
 
 #[async_trait]
 pub trait Channel: Send + Sync {
     /// The driver name (e.g. "mail", "database")
     fn name(&self) -> &'static str;
+
+    /// Render `notification` for this channel. `None` skips the notifiable.
+    fn render(
+        &self,
+        notification: &dyn Notification,
+        notifiable: &dyn Notifiable,
+    ) -> Result<Option<Value>>;
+
+    /// Deliver a rendered message, in-request or from the queue.
+    async fn deliver(&self, message: &ChannelMessage) -> Result<()>;
 }
 
 // --- Mail Channel ---
 
-pub struct MailChannel;
+pub struct MailChannel {
+    mailer: Arc<Mailer>,
+}
 
 impl MailChannel {
+    pub fn new(mailer: Arc<Mailer>) -> Self {
+        Self { mailer }
+    }
+
     /// Send immediately
     pub async fn dispatch_now(
         mailer: &core_mailer::Mailer,
@@ -37,3 +71,133 @@ impl MailChannel {
         Ok(())
     }
 }
+
+#[async_trait]
+impl Channel for MailChannel {
+    fn name(&self) -> &'static str {
+        "mail"
+    }
+
+    fn render(
+        &self,
+        notification: &dyn Notification,
+        notifiable: &dyn Notifiable,
+    ) -> Result<Option<Value>> {
+        let Some(mut payload) = notification.to_mail(notifiable) else {
+            return Ok(None);
+        };
+        if payload.to.is_empty() {
+            let Some(email) = notifiable.email() else {
+                return Ok(None);
+            };
+            payload.to.push(email);
+        }
+        Ok(Some(serde_json::to_value(payload)?))
+    }
+
+    async fn deliver(&self, message: &ChannelMessage) -> Result<()> {
+        let payload: MailPayload = serde_json::from_value(message.content.clone())?;
+        self.mailer.send_raw(&payload).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Member {
+        email: Option<&'static str>,
+    }
+
+    impl Notifiable for Member {
+        fn route_notification_for(&self, driver: &str) -> Option<String> {
+            match driver {
+                "mail" => self.email.map(str::to_string),
+                _ => None,
+            }
+        }
+
+        fn id(&self) -> String {
+            "7".to_string()
+        }
+    }
+
+    struct Welcome {
+        to: Option<&'static str>,
+    }
+
+    impl Notification for Welcome {
+        fn notification_type(&self) -> &'static str {
+            "welcome"
+        }
+
+        fn via(&self, _notifiable: &dyn Notifiable) -> Vec<&'static str> {
+            vec!["mail"]
+        }
+
+        fn to_mail(&self, _notifiable: &dyn Notifiable) -> Option<MailPayload> {
+            let mut payload = MailPayload::new(
+                String::new(),
+                "Welcome".to_string(),
+                "<p>Hi</p>".to_string(),
+            );
+            payload.to = self.to.iter().map(|to| to.to_string()).collect();
+            Some(payload)
+        }
+    }
+
+    fn channel() -> MailChannel {
+        let mailer = Mailer::new(&core_config::MailSettings::default(), None).expect("log mailer");
+        MailChannel::new(Arc::new(mailer))
+    }
+
+    fn recipients(content: Option<Value>) -> Option<Vec<String>> {
+        content.map(|content| {
+            serde_json::from_value::<MailPayload>(content)
+                .expect("mail payload")
+                .to
+        })
+    }
+
+    #[test]
+    fn mail_falls_back_to_the_notifiable_email() {
+        let content = channel()
+            .render(
+                &Welcome { to: None },
+                &Member {
+                    email: Some("ada@example.com"),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            recipients(content),
+            Some(vec!["ada@example.com".to_string()])
+        );
+    }
+
+    #[test]
+    fn mail_keeps_explicit_recipients() {
+        let content = channel()
+            .render(
+                &Welcome {
+                    to: Some("billing@example.com"),
+                },
+                &Member {
+                    email: Some("ada@example.com"),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            recipients(content),
+            Some(vec!["billing@example.com".to_string()])
+        );
+    }
+
+    #[test]
+    fn mail_without_any_address_is_skipped() {
+        let content = channel()
+            .render(&Welcome { to: None }, &Member { email: None })
+            .unwrap();
+        assert!(content.is_none());
+    }
+}
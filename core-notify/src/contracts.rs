@@ -0,0 +1,44 @@
+//! Request/response types for a notification preferences page.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+pub struct NotificationChannelPreferenceDto {
+    pub channel: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+pub struct NotificationTypePreferenceDto {
+    pub notification_type: String,
+    /// Always delivered; render the toggles disabled.
+    pub mandatory: bool,
+    pub channels: Vec<NotificationChannelPreferenceDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+pub struct NotificationPreferencesOutput {
+    pub types: Vec<NotificationTypePreferenceDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, JsonSchema, TS)]
+pub struct NotificationPreferenceUpdateItem {
+    #[validate(length(min = 1, max = 128))]
+    #[schemars(length(min = 1, max = 128))]
+    pub notification_type: String,
+    #[validate(length(min = 1, max = 64))]
+    #[schemars(length(min = 1, max = 64))]
+    pub channel: String,
+    pub enabled: bool,
+}
+
+/// Only the listed pairs change; the rest keep their current state.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, JsonSchema, TS)]
+pub struct NotificationPreferenceUpdateInput {
+    #[validate(length(max = 500), nested)]
+    #[schemars(length(max = 500))]
+    pub preferences: Vec<NotificationPreferenceUpdateItem>,
+}
@@ -0,0 +1,157 @@
+//! Database channel and the read/unread API over the `notifications` table.
+
+use crate::{Channel, ChannelMessage, Notifiable, Notification};
+use anyhow::Result;
+use async_trait::async_trait;
+use core_db::{
+    common::{
+        model_api::{Page, Query},
+        sql::{DbConn, Op, OrderDir},
+    },
+    generated::models::{NotificationCol, NotificationModel, NotificationRecord},
+};
+use serde_json::Value;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Stores `Notification::to_database` as a `notifications` row.
+pub struct DatabaseChannel {
+    db: PgPool,
+}
+
+impl DatabaseChannel {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl Channel for DatabaseChannel {
+    fn name(&self) -> &'static str {
+        "database"
+    }
+
+    fn render(
+        &self,
+        notification: &dyn Notification,
+        notifiable: &dyn Notifiable,
+    ) -> Result<Option<Value>> {
+        Ok(notification.to_database(notifiable))
+    }
+
+    async fn deliver(&self, message: &ChannelMessage) -> Result<()> {
+        // Conflict on id: a retried job already stored the row
+        NotificationModel::create()
+            .set(NotificationCol::ID, message.notification_id)?
+            .set(
+                NotificationCol::NOTIFIABLE_TYPE,
+                message.notifiable_type.clone(),
+            )?
+            .set(
+                NotificationCol::NOTIFIABLE_ID,
+                message.notifiable_id.clone(),
+            )?
+            .set(
+                NotificationCol::NOTIFICATION_TYPE,
+                message.notification_type.clone(),
+            )?
+            .set(NotificationCol::DATA, message.content.clone())?
+            .on_conflict_do_nothing(&[NotificationCol::ID])
+            .save(DbConn::pool(&self.db))
+            .await?;
+        Ok(())
+    }
+}
+
+/// Database notifications of one notifiable, newest first.
+pub struct DatabaseNotifications;
+
+impl DatabaseNotifications {
+    pub async fn list(
+        db: &PgPool,
+        notifiable: &dyn Notifiable,
+        page: i64,
+        per_page: i64,
+    ) -> Result<Page<NotificationRecord>> {
+        Self::query(notifiable)
+            .order_by(NotificationCol::CREATED_AT, OrderDir::Desc)
+            .paginate(DbConn::pool(db), page, per_page)
+            .await
+    }
+
+    pub async fn unread(
+        db: &PgPool,
+        notifiable: &dyn Notifiable,
+        page: i64,
+        per_page: i64,
+    ) -> Result<Page<NotificationRecord>> {
+        Self::query(notifiable)
+            .where_null(NotificationCol::READ_AT)
+            .order_by(NotificationCol::CREATED_AT, OrderDir::Desc)
+            .paginate(DbConn::pool(db), page, per_page)
+            .await
+    }
+
+    pub async fn unread_count(db: &PgPool, notifiable: &dyn Notifiable) -> Result<i64> {
+        Self::query(notifiable)
+            .where_null(NotificationCol::READ_AT)
+            .count(DbConn::pool(db))
+            .await
+    }
+
+    /// Returns `false` when the notification does not exist, belongs to someone else
+    /// or is already read.
+    pub async fn mark_as_read(db: &PgPool, notifiable: &dyn Notifiable, id: Uuid) -> Result<bool> {
+        let updated = Self::query(notifiable)
+            .where_col(NotificationCol::ID, Op::Eq, id)
+            .where_null(NotificationCol::READ_AT)
+            .patch()
+            .assign(NotificationCol::READ_AT, Some(OffsetDateTime::now_utc()))?
+            .save(DbConn::pool(db))
+            .await?;
+        Ok(updated > 0)
+    }
+
+    pub async fn mark_as_unread(
+        db: &PgPool,
+        notifiable: &dyn Notifiable,
+        id: Uuid,
+    ) -> Result<bool> {
+        let updated = Self::query(notifiable)
+            .where_col(NotificationCol::ID, Op::Eq, id)
+            .patch()
+            .assign(NotificationCol::READ_AT, None::<OffsetDateTime>)?
+            .save(DbConn::pool(db))
+            .await?;
+        Ok(updated > 0)
+    }
+
+    /// Returns the number of notifications marked.
+    pub async fn mark_all_as_read(db: &PgPool, notifiable: &dyn Notifiable) -> Result<u64> {
+        Self::query(notifiable)
+            .where_null(NotificationCol::READ_AT)
+            .patch()
+            .assign(NotificationCol::READ_AT, Some(OffsetDateTime::now_utc()))?
+            .save(DbConn::pool(db))
+            .await
+    }
+
+    pub async fn delete(db: &PgPool, notifiable: &dyn Notifiable, id: Uuid) -> Result<bool> {
+        let deleted = Self::query(notifiable)
+            .where_col(NotificationCol::ID, Op::Eq, id)
+            .delete(DbConn::pool(db))
+            .await?;
+        Ok(deleted > 0)
+    }
+
+    fn query<'db>(notifiable: &dyn Notifiable) -> Query<'db, NotificationModel> {
+        NotificationModel::query()
+            .where_col(
+                NotificationCol::NOTIFIABLE_TYPE,
+                Op::Eq,
+                notifiable.notifiable_type(),
+            )
+            .where_col(NotificationCol::NOTIFIABLE_ID, Op::Eq, notifiable.id())
+    }
+}
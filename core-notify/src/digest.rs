@@ -0,0 +1,351 @@
+//! Bundling of noisy notification types into one summary per window.
+
+use crate::{Notifiable, Notification};
+use anyhow::Result;
+use async_trait::async_trait;
+use core_db::{
+    common::sql::{DbConn, Op, OrderDir},
+    generated::models::{
+        NotificationDigestItemCol, NotificationDigestItemModel, NotificationDigestItemRecord,
+    },
+};
+use core_jobs::{Job, JobContext};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use time::{Duration, OffsetDateTime, Time, UtcOffset};
+use uuid::Uuid;
+
+/// Digest windows, aligned to the UTC hour or day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestWindow {
+    Hourly,
+    Daily,
+}
+
+impl DigestWindow {
+    pub fn duration(self) -> Duration {
+        match self {
+            DigestWindow::Hourly => Duration::hours(1),
+            DigestWindow::Daily => Duration::days(1),
+        }
+    }
+
+    /// End of the window containing `at`.
+    pub fn end_of(self, at: OffsetDateTime) -> OffsetDateTime {
+        let at = at.to_offset(UtcOffset::UTC);
+        let start = match self {
+            DigestWindow::Hourly => {
+                at.replace_time(Time::MIDNIGHT) + Duration::hours(at.hour() as i64)
+            }
+            DigestWindow::Daily => at.replace_time(Time::MIDNIGHT),
+        };
+        start + self.duration()
+    }
+}
+
+/// Bundling rules for one notification type (`NotificationManager::register_digest`).
+pub trait Digest: Send + Sync {
+    fn notification_type(&self) -> &'static str;
+
+    fn window(&self) -> DigestWindow;
+
+    /// Notifications delivered one by one per window and notifiable before the
+    /// rest is bundled. 0 bundles everything.
+    fn max_per_window(&self) -> u32 {
+        0
+    }
+
+    /// The summary for the window's `Notification::to_digest` values, oldest first.
+    fn summarize(&self, notifiable: &dyn Notifiable, items: &[Value]) -> Box<dyn Notification>;
+}
+
+/// A notifiable's routes, captured when an item is collected so the digest job
+/// can address the summary without loading the model.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StoredNotifiable {
+    pub notifiable_type: String,
+    pub id: String,
+    pub routes: HashMap<String, String>,
+    pub locale: Option<String>,
+}
+
+impl StoredNotifiable {
+    pub fn capture(notifiable: &dyn Notifiable, channels: &[&str]) -> Self {
+        let mut routes = HashMap::new();
+        for &channel in channels {
+            let route = match channel {
+                "mail" => notifiable.email(),
+                "sms" => notifiable.phone(),
+                _ => notifiable.route_notification_for(channel),
+            };
+            if let Some(route) = route {
+                routes.insert(channel.to_string(), route);
+            }
+        }
+        Self {
+            notifiable_type: notifiable.notifiable_type(),
+            id: notifiable.id(),
+            routes,
+            locale: notifiable.preferred_locale(),
+        }
+    }
+}
+
+impl Notifiable for StoredNotifiable {
+    fn route_notification_for(&self, driver: &str) -> Option<String> {
+        self.routes.get(driver).cloned()
+    }
+
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn notifiable_type(&self) -> String {
+        self.notifiable_type.clone()
+    }
+
+    fn preferred_locale(&self) -> Option<String> {
+        self.locale.clone()
+    }
+}
+
+/// `notification_digest_items`: pending items and the per-window counters of
+/// notifications already delivered one by one.
+#[derive(Clone)]
+pub struct DigestStore {
+    db: PgPool,
+}
+
+impl DigestStore {
+    pub const SENT: &'static str = "sent";
+    pub const PENDING: &'static str = "pending";
+    /// Items whose stored notifiable cannot be decoded; kept for inspection.
+    pub const FAILED: &'static str = "failed";
+
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+
+    /// Record one notification. Returns `true` when it was bundled and must not
+    /// be delivered now.
+    pub async fn collect(
+        &self,
+        notifiable: &dyn Notifiable,
+        notification: &dyn Notification,
+        digest: &dyn Digest,
+    ) -> Result<bool> {
+        let deliver_after = digest.window().end_of(OffsetDateTime::now_utc());
+        let max = digest.max_per_window() as i64;
+        let scope = DbConn::pool(&self.db).begin_scope().await?;
+        let conn = scope.conn();
+        let sent = if max > 0 {
+            // Concurrent collectors of the same notifiable and type wait here until this
+            // transaction commits, so the count below includes their rows.
+            conn.execute(
+                sqlx::query("SELECT pg_advisory_xact_lock(hashtextextended($1, 0))").bind(format!(
+                    "notification_digest:{}:{}:{}",
+                    notifiable.notifiable_type(),
+                    notifiable.id(),
+                    notification.notification_type()
+                )),
+            )
+            .await?;
+            NotificationDigestItemModel::query()
+                .where_col(
+                    NotificationDigestItemCol::NOTIFIABLE_TYPE,
+                    Op::Eq,
+                    notifiable.notifiable_type(),
+                )
+                .where_col(
+                    NotificationDigestItemCol::NOTIFIABLE_ID,
+                    Op::Eq,
+                    notifiable.id(),
+                )
+                .where_col(
+                    NotificationDigestItemCol::NOTIFICATION_TYPE,
+                    Op::Eq,
+                    notification.notification_type().to_string(),
+                )
+                .where_col(
+                    NotificationDigestItemCol::STATUS,
+                    Op::Eq,
+                    Self::SENT.to_string(),
+                )
+                .where_col(
+                    NotificationDigestItemCol::DELIVER_AFTER,
+                    Op::Eq,
+                    deliver_after,
+                )
+                .count(conn.clone())
+                .await?
+        } else {
+            0
+        };
+        let status = if sent < max {
+            Self::SENT
+        } else {
+            Self::PENDING
+        };
+
+        let snapshot = StoredNotifiable::capture(notifiable, &notification.via(notifiable));
+        NotificationDigestItemModel::create()
+            .set(
+                NotificationDigestItemCol::NOTIFIABLE_TYPE,
+                snapshot.notifiable_type.clone(),
+            )?
+            .set(
+                NotificationDigestItemCol::NOTIFIABLE_ID,
+                snapshot.id.clone(),
+            )?
+            .set(
+                NotificationDigestItemCol::NOTIFICATION_TYPE,
+                notification.notification_type().to_string(),
+            )?
+            .set(
+                NotificationDigestItemCol::NOTIFIABLE,
+                serde_json::to_value(&snapshot)?,
+            )?
+            .set(
+                NotificationDigestItemCol::DATA,
+                notification
+                    .to_digest(notifiable)
+                    .unwrap_or_else(|| Value::Object(Default::default())),
+            )?
+            .set(NotificationDigestItemCol::STATUS, status.to_string())?
+            .set(NotificationDigestItemCol::DELIVER_AFTER, deliver_after)?
+            .save(conn)
+            .await?;
+        scope.commit().await?;
+
+        Ok(status == Self::PENDING)
+    }
+
+    /// Pending items whose window has ended, oldest first.
+    pub async fn due(&self, limit: i64) -> Result<Vec<NotificationDigestItemRecord>> {
+        NotificationDigestItemModel::query()
+            .where_col(
+                NotificationDigestItemCol::STATUS,
+                Op::Eq,
+                Self::PENDING.to_string(),
+            )
+            .where_col(
+                NotificationDigestItemCol::DELIVER_AFTER,
+                Op::Le,
+                OffsetDateTime::now_utc(),
+            )
+            .order_by(NotificationDigestItemCol::CREATED_AT, OrderDir::Asc)
+            .limit(limit)
+            .all(DbConn::pool(&self.db))
+            .await
+    }
+
+    /// Remember the channels a summary went out on, so the next run skips them.
+    pub async fn mark_delivered(&self, ids: Vec<Uuid>, channels: &[String]) -> Result<u64> {
+        NotificationDigestItemModel::query()
+            .where_in(NotificationDigestItemCol::ID, ids)
+            .patch()
+            .assign(
+                NotificationDigestItemCol::DELIVERED_CHANNELS,
+                serde_json::to_value(channels)?,
+            )?
+            .save(DbConn::pool(&self.db))
+            .await
+    }
+
+    /// Take items out of `due` for good.
+    pub async fn mark_failed(&self, ids: Vec<Uuid>) -> Result<u64> {
+        NotificationDigestItemModel::query()
+            .where_in(NotificationDigestItemCol::ID, ids)
+            .patch()
+            .assign(NotificationDigestItemCol::STATUS, Self::FAILED.to_string())?
+            .save(DbConn::pool(&self.db))
+            .await
+    }
+
+    pub async fn delete(&self, ids: Vec<Uuid>) -> Result<u64> {
+        NotificationDigestItemModel::query()
+            .where_in(NotificationDigestItemCol::ID, ids)
+            .delete(DbConn::pool(&self.db))
+            .await
+    }
+
+    /// Drop the counters of windows that are over.
+    pub async fn prune(&self) -> Result<u64> {
+        NotificationDigestItemModel::query()
+            .where_col(
+                NotificationDigestItemCol::STATUS,
+                Op::Eq,
+                Self::SENT.to_string(),
+            )
+            .where_col(
+                NotificationDigestItemCol::DELIVER_AFTER,
+                Op::Le,
+                OffsetDateTime::now_utc(),
+            )
+            .delete(DbConn::pool(&self.db))
+            .await
+    }
+}
+
+/// Sends the summaries of every ended window. Scheduled hourly by the framework.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SendNotificationDigests;
+
+#[async_trait]
+impl Job for SendNotificationDigests {
+    const NAME: &'static str = "core:send_notification_digests";
+
+    async fn handle(&self, ctx: &JobContext) -> anyhow::Result<()> {
+        let manager = ctx
+            .extensions
+            .get::<Arc<crate::NotificationManager>>()
+            .ok_or_else(|| {
+                anyhow::anyhow!("NotificationManager is not in the worker extensions")
+            })?;
+        let sent = manager.send_digests().await?;
+        if sent > 0 {
+            tracing::info!("Sent {} notification digests", sent);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DigestWindow;
+    use time::macros::datetime;
+
+    #[test]
+    fn hourly_window_ends_at_the_next_utc_hour() {
+        assert_eq!(
+            DigestWindow::Hourly.end_of(datetime!(2026-03-04 10:15:30 UTC)),
+            datetime!(2026-03-04 11:00 UTC)
+        );
+        assert_eq!(
+            DigestWindow::Hourly.end_of(datetime!(2026-03-04 23:59:59 UTC)),
+            datetime!(2026-03-05 00:00 UTC)
+        );
+        // The start of an hour belongs to that hour.
+        assert_eq!(
+            DigestWindow::Hourly.end_of(datetime!(2026-03-04 10:00 UTC)),
+            datetime!(2026-03-04 11:00 UTC)
+        );
+    }
+
+    #[test]
+    fn daily_window_ends_at_the_next_utc_midnight() {
+        assert_eq!(
+            DigestWindow::Daily.end_of(datetime!(2026-03-04 10:15 UTC)),
+            datetime!(2026-03-05 00:00 UTC)
+        );
+        // 01:30 at +08:00 is still the previous UTC day.
+        assert_eq!(
+            DigestWindow::Daily.end_of(datetime!(2026-03-05 01:30 +08:00)),
+            datetime!(2026-03-05 00:00 UTC)
+        );
+    }
+}
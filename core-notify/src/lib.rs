@@ -1,12 +1,24 @@
-// use async_trait::async_trait;
-
 pub mod channel;
-// pub mod manager;
+pub mod contracts;
+pub mod database;
+pub mod digest;
+pub mod manager;
 pub mod notifiable;
-// pub use manager::ChannelManager;
+pub mod notification;
+pub mod preferences;
+pub mod realtime;
+pub mod sms;
+pub mod ts_exports;
 
-pub use channel::Channel;
+pub use channel::{Channel, ChannelMessage, MailChannel};
+pub use database::{DatabaseChannel, DatabaseNotifications};
+pub use digest::{Digest, DigestStore, DigestWindow, SendNotificationDigests, StoredNotifiable};
+pub use manager::{NotificationManager, SendNotificationJob};
 pub use notifiable::Notifiable;
+pub use notification::{Notification, RealtimeMessage, SmsMessage};
+pub use preferences::{DatabasePreferences, NotificationPreferences, NotificationTypeInfo};
+pub use realtime::RealtimeChannel;
+pub use sms::{LogSmsDriver, SmsChannel, SmsDriver};
 
 /// Trait for objects that can be sent via Email
 pub trait Mailable: Send + Sync {
@@ -0,0 +1,558 @@
+use crate::digest::{Digest, DigestStore, StoredNotifiable};
+use crate::{
+    Channel, ChannelMessage, Notifiable, Notification, NotificationPreferences,
+    NotificationTypeInfo,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use core_jobs::{driver::QueueDriver, Job, JobContext};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+use uuid::Uuid;
+
+/// Routes notifications to the registered channels, inline or through the queue.
+#[derive(Default)]
+pub struct NotificationManager {
+    channels: RwLock<HashMap<&'static str, Arc<dyn Channel>>>,
+    queue: Option<Arc<dyn QueueDriver>>,
+    preferences: Option<Arc<dyn NotificationPreferences>>,
+    types: RwLock<Vec<NotificationTypeInfo>>,
+    digests: RwLock<HashMap<&'static str, Arc<dyn Digest>>>,
+    digest_store: Option<DigestStore>,
+}
+
+impl NotificationManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue used by `queue`.
+    pub fn with_queue(mut self, queue: Arc<dyn QueueDriver>) -> Self {
+        self.queue = Some(queue);
+        self
+    }
+
+    /// Opt-outs checked before each channel; without it every `via` channel is used.
+    pub fn with_preferences(mut self, preferences: Arc<dyn NotificationPreferences>) -> Self {
+        self.preferences = Some(preferences);
+        self
+    }
+
+    /// Storage for bundled notifications; required once a digest is registered.
+    pub fn with_digest_store(mut self, store: DigestStore) -> Self {
+        self.digest_store = Some(store);
+        self
+    }
+
+    /// Bundle a notification type, replacing its previous digest.
+    pub fn register_digest(&self, digest: impl Digest + 'static) {
+        self.digests
+            .write()
+            .unwrap()
+            .insert(digest.notification_type(), Arc::new(digest));
+    }
+
+    /// List a notification type on the preferences page.
+    pub fn describe(
+        &self,
+        notification_type: &'static str,
+        channels: &[&'static str],
+        mandatory: bool,
+    ) {
+        let mut types = self.types.write().unwrap();
+        types.retain(|info| info.notification_type != notification_type);
+        types.push(NotificationTypeInfo {
+            notification_type,
+            channels: channels.to_vec(),
+            mandatory,
+        });
+    }
+
+    /// Described notification types, in `describe` order.
+    pub fn types(&self) -> Vec<NotificationTypeInfo> {
+        self.types.read().unwrap().clone()
+    }
+
+    /// Add a channel, replacing any channel with the same name.
+    pub fn register(&self, channel: impl Channel + 'static) {
+        self.channels
+            .write()
+            .unwrap()
+            .insert(channel.name(), Arc::new(channel));
+    }
+
+    pub fn channel(&self, name: &str) -> Option<Arc<dyn Channel>> {
+        self.channels.read().unwrap().get(name).cloned()
+    }
+
+    /// Render `notification` for every channel in `via` the notifiable has not
+    /// opted out of, in its preferred locale. Channels whose renderer returns
+    /// `None` are left out.
+    pub async fn render(
+        &self,
+        notifiable: &dyn Notifiable,
+        notification: &dyn Notification,
+    ) -> Result<Vec<ChannelMessage>> {
+        let mut channels = notification.via(notifiable);
+        if let (Some(preferences), false) = (&self.preferences, notification.mandatory()) {
+            let mut allowed = Vec::with_capacity(channels.len());
+            for channel in channels {
+                if preferences
+                    .allows(notifiable, notification.notification_type(), channel)
+                    .await?
+                {
+                    allowed.push(channel);
+                }
+            }
+            channels = allowed;
+        }
+
+        let locale = notifiable
+            .preferred_locale()
+            .and_then(|locale| core_i18n::match_supported_locale(&locale));
+        match locale {
+            Some(locale) => {
+                core_i18n::scope_locale(locale, || async {
+                    self.render_now(notifiable, notification, &channels)
+                })
+                .await
+            }
+            None => self.render_now(notifiable, notification, &channels),
+        }
+    }
+
+    fn render_now(
+        &self,
+        notifiable: &dyn Notifiable,
+        notification: &dyn Notification,
+        channels: &[&'static str],
+    ) -> Result<Vec<ChannelMessage>> {
+        let notification_id = Uuid::new_v4();
+        let mut messages = Vec::new();
+        for &name in channels {
+            let channel = self.channel(name).ok_or_else(|| {
+                anyhow::anyhow!("Notification channel '{}' is not registered", name)
+            })?;
+            if let Some(content) = channel.render(notification, notifiable)? {
+                messages.push(ChannelMessage {
+                    channel: name.to_string(),
+                    notification_id,
+                    notification_type: notification.notification_type().to_string(),
+                    notifiable_type: notifiable.notifiable_type(),
+                    notifiable_id: notifiable.id(),
+                    content,
+                });
+            }
+        }
+        Ok(messages)
+    }
+
+    /// Deliver on every channel now. A failing channel does not stop the others;
+    /// their errors are returned together.
+    /// Bundled types are collected for their digest instead.
+    pub async fn send(
+        &self,
+        notifiable: &dyn Notifiable,
+        notification: &dyn Notification,
+    ) -> Result<()> {
+        if self.bundle(notifiable, notification).await? {
+            return Ok(());
+        }
+        self.send_now(notifiable, notification).await
+    }
+
+    async fn send_now(
+        &self,
+        notifiable: &dyn Notifiable,
+        notification: &dyn Notification,
+    ) -> Result<()> {
+        let mut errors = Vec::new();
+        for message in self.render(notifiable, notification).await? {
+            if let Err(e) = self.deliver(&message).await {
+                tracing::error!(
+                    "Notification {} failed on channel {}: {:#}",
+                    message.notification_type,
+                    message.channel,
+                    e
+                );
+                errors.push(format!("{}: {:#}", message.channel, e));
+            }
+        }
+        if !errors.is_empty() {
+            anyhow::bail!(
+                "Notification {} failed: {}",
+                notification.notification_type(),
+                errors.join("; ")
+            );
+        }
+        Ok(())
+    }
+
+    /// Dispatch one `SendNotificationJob` per channel, so a failing channel
+    /// retries on its own. Bundled types are collected for their digest instead.
+    pub async fn queue(
+        &self,
+        notifiable: &dyn Notifiable,
+        notification: &dyn Notification,
+    ) -> Result<()> {
+        if self.bundle(notifiable, notification).await? {
+            return Ok(());
+        }
+        self.queue_now(notifiable, notification).await
+    }
+
+    async fn queue_now(
+        &self,
+        notifiable: &dyn Notifiable,
+        notification: &dyn Notification,
+    ) -> Result<()> {
+        let queue = self
+            .queue
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Queue not configured for notifications"))?;
+        for message in self.render(notifiable, notification).await? {
+            SendNotificationJob { message }.dispatch(&**queue).await?;
+        }
+        Ok(())
+    }
+
+    /// Collect `notification` for its digest. `true` means it must not be delivered now.
+    async fn bundle(
+        &self,
+        notifiable: &dyn Notifiable,
+        notification: &dyn Notification,
+    ) -> Result<bool> {
+        if notification.mandatory() {
+            return Ok(false);
+        }
+        let digest = self
+            .digests
+            .read()
+            .unwrap()
+            .get(notification.notification_type())
+            .cloned();
+        let Some(digest) = digest else {
+            return Ok(false);
+        };
+        let store = self.digest_store.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Digest for {} needs a digest store",
+                notification.notification_type()
+            )
+        })?;
+        store
+            .collect(notifiable, notification, digest.as_ref())
+            .await
+    }
+
+    /// Send one summary per notifiable and type for every ended window, through
+    /// the queue when one is configured. Returns the number of summaries.
+    /// Channels that fail are retried on the next run; the others are not sent again.
+    /// A group that cannot be built is logged and does not stop the others: a bad
+    /// notifiable snapshot marks its items failed, a render error leaves them for the
+    /// next run. Only store errors are returned.
+    pub async fn send_digests(&self) -> Result<usize> {
+        let Some(store) = &self.digest_store else {
+            return Ok(0);
+        };
+
+        // Items of a partly delivered summary stay apart from items collected since.
+        let mut groups: BTreeMap<(String, String, String, Vec<String>), Vec<_>> = BTreeMap::new();
+        for item in store.due(10_000).await? {
+            let mut delivered: Vec<String> =
+                serde_json::from_value(item.delivered_channels.clone()).unwrap_or_default();
+            delivered.sort();
+            let key = (
+                item.notifiable_type.clone(),
+                item.notifiable_id.clone(),
+                item.notification_type.clone(),
+                delivered,
+            );
+            groups.entry(key).or_default().push(item);
+        }
+
+        let mut sent = 0;
+        for ((notifiable_type, notifiable_id, notification_type, mut delivered), items) in groups {
+            let ids = items.iter().map(|item| item.id).collect::<Vec<_>>();
+            let digest = self
+                .digests
+                .read()
+                .unwrap()
+                .get(notification_type.as_str())
+                .cloned();
+            let Some(digest) = digest else {
+                tracing::warn!(
+                    "Dropping {} digest items of {}: no digest registered",
+                    ids.len(),
+                    notification_type
+                );
+                store.delete(ids).await?;
+                continue;
+            };
+
+            // Latest routes win
+            let notifiable: StoredNotifiable = match serde_json::from_value(
+                items.last().unwrap().notifiable.clone(),
+            ) {
+                Ok(notifiable) => notifiable,
+                Err(e) => {
+                    tracing::error!(
+                            "Digest {} for {} {} has an unreadable notifiable, marking {} items failed: {}",
+                            notification_type,
+                            notifiable_type,
+                            notifiable_id,
+                            ids.len(),
+                            e
+                        );
+                    store.mark_failed(ids).await?;
+                    continue;
+                }
+            };
+            let data = items
+                .iter()
+                .map(|item| item.data.clone())
+                .collect::<Vec<_>>();
+            let summary = digest.summarize(&notifiable, &data);
+            let messages = match self.render(&notifiable, summary.as_ref()).await {
+                Ok(messages) => messages,
+                Err(e) => {
+                    tracing::error!(
+                        "Digest {} for {} {} could not be rendered, retrying next run: {:#}",
+                        notification_type,
+                        notifiable_type,
+                        notifiable_id,
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            let mut failed = false;
+            for message in messages {
+                if delivered.contains(&message.channel) {
+                    continue;
+                }
+                let channel = message.channel.clone();
+                let result = match &self.queue {
+                    Some(queue) => SendNotificationJob { message }.dispatch(&**queue).await,
+                    None => self.deliver(&message).await,
+                };
+                match result {
+                    Ok(()) => delivered.push(channel),
+                    Err(e) => {
+                        failed = true;
+                        tracing::error!(
+                            "Digest {} for {} {} failed on channel {}: {:#}",
+                            notification_type,
+                            notifiable.notifiable_type,
+                            notifiable.id,
+                            channel,
+                            e
+                        );
+                    }
+                }
+            }
+
+            if failed {
+                store.mark_delivered(ids, &delivered).await?;
+            } else {
+                store.delete(ids).await?;
+                sent += 1;
+            }
+        }
+
+        store.prune().await?;
+        Ok(sent)
+    }
+
+    /// Deliver a rendered message on its channel.
+    pub async fn deliver(&self, message: &ChannelMessage) -> Result<()> {
+        let channel = self.channel(&message.channel).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Notification channel '{}' is not registered",
+                message.channel
+            )
+        })?;
+        channel.deliver(message).await
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SendNotificationJob {
+    pub message: ChannelMessage,
+}
+
+#[async_trait]
+impl Job for SendNotificationJob {
+    const NAME: &'static str = "SendNotificationJob";
+
+    async fn handle(&self, ctx: &JobContext) -> anyhow::Result<()> {
+        let manager = ctx
+            .extensions
+            .get::<Arc<NotificationManager>>()
+            .ok_or_else(|| {
+                anyhow::anyhow!("NotificationManager is not in the worker extensions")
+            })?;
+        manager.deliver(&self.message).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core_jobs::testing::FakeQueue;
+    use serde_json::{json, Value};
+    use std::sync::Mutex;
+
+    /// Renders `to_channel(name)` and records every delivery attempt.
+    struct StubChannel {
+        name: &'static str,
+        fail: bool,
+        attempts: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Channel for StubChannel {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn render(
+            &self,
+            notification: &dyn Notification,
+            notifiable: &dyn Notifiable,
+        ) -> Result<Option<Value>> {
+            Ok(notification.to_channel(self.name, notifiable))
+        }
+
+        async fn deliver(&self, message: &ChannelMessage) -> Result<()> {
+            self.attempts.lock().unwrap().push(message.channel.clone());
+            if self.fail {
+                anyhow::bail!("{} is down", self.name);
+            }
+            Ok(())
+        }
+    }
+
+    struct User;
+
+    impl Notifiable for User {
+        fn route_notification_for(&self, _driver: &str) -> Option<String> {
+            None
+        }
+
+        fn id(&self) -> String {
+            "42".to_string()
+        }
+    }
+
+    /// Has content for every channel except "silent".
+    struct InvoicePaid {
+        via: Vec<&'static str>,
+    }
+
+    impl Notification for InvoicePaid {
+        fn notification_type(&self) -> &'static str {
+            "invoice_paid"
+        }
+
+        fn via(&self, _notifiable: &dyn Notifiable) -> Vec<&'static str> {
+            self.via.clone()
+        }
+
+        fn to_channel(&self, channel: &str, _notifiable: &dyn Notifiable) -> Option<Value> {
+            (channel != "silent").then(|| json!({ "channel": channel }))
+        }
+    }
+
+    fn manager(
+        channels: &[(&'static str, bool)],
+    ) -> (NotificationManager, Arc<Mutex<Vec<String>>>) {
+        let attempts = Arc::new(Mutex::new(Vec::new()));
+        let manager = NotificationManager::new();
+        for &(name, fail) in channels {
+            manager.register(StubChannel {
+                name,
+                fail,
+                attempts: attempts.clone(),
+            });
+        }
+        (manager, attempts)
+    }
+
+    #[tokio::test]
+    async fn render_leaves_out_channels_without_content() {
+        let (manager, _) = manager(&[("push", false), ("silent", false)]);
+        let notification = InvoicePaid {
+            via: vec!["push", "silent"],
+        };
+
+        let messages = manager.render(&User, &notification).await.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].channel, "push");
+        assert_eq!(messages[0].notification_type, "invoice_paid");
+        assert_eq!(messages[0].notifiable_type, "user");
+        assert_eq!(messages[0].notifiable_id, "42");
+        assert_eq!(messages[0].content, json!({ "channel": "push" }));
+    }
+
+    #[tokio::test]
+    async fn unregistered_channel_is_an_error() {
+        let (manager, attempts) = manager(&[("push", false)]);
+        let notification = InvoicePaid {
+            via: vec!["push", "pager"],
+        };
+
+        let err = manager.send(&User, &notification).await.unwrap_err();
+        assert!(err.to_string().contains("'pager' is not registered"));
+        assert!(attempts.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn send_tries_every_channel_and_collects_errors() {
+        let (manager, attempts) = manager(&[("chat", true), ("push", false), ("pager", true)]);
+        let notification = InvoicePaid {
+            via: vec!["chat", "push", "pager"],
+        };
+
+        let err = manager.send(&User, &notification).await.unwrap_err();
+        assert_eq!(*attempts.lock().unwrap(), ["chat", "push", "pager"]);
+        let err = err.to_string();
+        assert!(err.contains("chat: chat is down"));
+        assert!(err.contains("pager: pager is down"));
+        assert!(!err.contains("push:"));
+    }
+
+    #[tokio::test]
+    async fn queue_dispatches_one_job_per_channel() {
+        let queue = Arc::new(FakeQueue::new());
+        let (manager, attempts) = manager(&[("chat", false), ("push", false)]);
+        let manager = manager.with_queue(queue.clone());
+        let notification = InvoicePaid {
+            via: vec!["chat", "push"],
+        };
+
+        manager.queue(&User, &notification).await.unwrap();
+        let jobs = queue.pushed::<SendNotificationJob>();
+        let channels: Vec<_> = jobs
+            .iter()
+            .map(|job| job.message.channel.as_str())
+            .collect();
+        assert_eq!(channels, ["chat", "push"]);
+        assert_eq!(
+            jobs[0].message.notification_id,
+            jobs[1].message.notification_id
+        );
+        assert!(attempts.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn queue_without_a_queue_is_an_error() {
+        let (manager, _) = manager(&[("push", false)]);
+        let notification = InvoicePaid { via: vec!["push"] };
+
+        let err = manager.queue(&User, &notification).await.unwrap_err();
+        assert!(err.to_string().contains("Queue not configured"));
+    }
+}
@@ -11,4 +11,15 @@ pub trait Notifiable: Send + Sync {
         self.route_notification_for("sms")
     }
     fn id(&self) -> String; // For database notifications
+
+    /// Stored with database notifications next to `id`. Override for non-user models.
+    fn notifiable_type(&self) -> String {
+        "user".to_string()
+    }
+
+    /// Locale to render notifications in (e.g. a stored user preference).
+    /// `None` uses the current locale.
+    fn preferred_locale(&self) -> Option<String> {
+        None
+    }
 }
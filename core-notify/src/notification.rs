@@ -0,0 +1,99 @@
+use crate::Notifiable;
+use core_mailer::MailPayload;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A message delivered to a `Notifiable` through one or more channels.
+///
+/// `via` picks the channels; each channel calls its renderer and skips the
+/// notifiable when it returns `None`.
+pub trait Notification: Send + Sync {
+    /// Stable type name stored with database notifications (e.g. "invoice_paid").
+    fn notification_type(&self) -> &'static str;
+
+    /// Channels to deliver through: "mail", "database", "realtime", "sms" or a custom one.
+    fn via(&self, notifiable: &dyn Notifiable) -> Vec<&'static str>;
+
+    /// Security and legal notices: delivered on every `via` channel, ignoring
+    /// the notifiable's opt-outs.
+    fn mandatory(&self) -> bool {
+        false
+    }
+
+    /// Recipients default to `notifiable.email()` when `to` is empty.
+    fn to_mail(&self, _notifiable: &dyn Notifiable) -> Option<MailPayload> {
+        None
+    }
+
+    /// JSON stored in `notifications.data`.
+    fn to_database(&self, _notifiable: &dyn Notifiable) -> Option<Value> {
+        None
+    }
+
+    /// The room defaults to `notifiable.route_notification_for("realtime")`.
+    fn to_realtime(&self, _notifiable: &dyn Notifiable) -> Option<RealtimeMessage> {
+        None
+    }
+
+    /// The number defaults to `notifiable.phone()`.
+    fn to_sms(&self, _notifiable: &dyn Notifiable) -> Option<SmsMessage> {
+        None
+    }
+
+    /// Item data kept for the digest summary when the type is bundled.
+    /// Defaults to `to_database`.
+    fn to_digest(&self, notifiable: &dyn Notifiable) -> Option<Value> {
+        self.to_database(notifiable)
+    }
+
+    /// Content for app-defined channels, by channel name.
+    fn to_channel(&self, _channel: &str, _notifiable: &dyn Notifiable) -> Option<Value> {
+        None
+    }
+}
+
+/// Event published by the realtime channel.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RealtimeMessage {
+    pub channel: String,
+    pub event: String,
+    pub room: Option<String>,
+    pub payload: Value,
+}
+
+impl RealtimeMessage {
+    pub fn new(channel: impl Into<String>, event: impl Into<String>, payload: Value) -> Self {
+        Self {
+            channel: channel.into(),
+            event: event.into(),
+            room: None,
+            payload,
+        }
+    }
+
+    pub fn room(mut self, room: impl Into<String>) -> Self {
+        self.room = Some(room.into());
+        self
+    }
+}
+
+/// Text message sent by the SMS channel.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SmsMessage {
+    pub to: Option<String>,
+    pub body: String,
+}
+
+impl SmsMessage {
+    pub fn new(body: impl Into<String>) -> Self {
+        Self {
+            to: None,
+            body: body.into(),
+        }
+    }
+
+    pub fn to(mut self, phone: impl Into<String>) -> Self {
+        self.to = Some(phone.into());
+        self
+    }
+}
@@ -0,0 +1,265 @@
+//! Per-notifiable channel opt-out, consulted by `NotificationManager` before rendering.
+
+use crate::contracts::{
+    NotificationChannelPreferenceDto, NotificationPreferenceUpdateInput,
+    NotificationPreferencesOutput, NotificationTypePreferenceDto,
+};
+use crate::Notifiable;
+use anyhow::Result;
+use async_trait::async_trait;
+use core_db::{
+    common::sql::{DbConn, Op},
+    generated::models::{
+        NotificationPreferenceCol, NotificationPreferenceModel, NotificationPreferenceRecord,
+    },
+};
+use core_web::error::AppError;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+
+/// Decides whether a notifiable receives a notification type on a channel.
+/// Not consulted for `Notification::mandatory` notifications.
+#[async_trait]
+pub trait NotificationPreferences: Send + Sync {
+    async fn allows(
+        &self,
+        notifiable: &dyn Notifiable,
+        notification_type: &str,
+        channel: &str,
+    ) -> Result<bool>;
+}
+
+/// A notification type shown on the preferences page
+/// (see `NotificationManager::describe`).
+#[derive(Debug, Clone)]
+pub struct NotificationTypeInfo {
+    pub notification_type: &'static str,
+    pub channels: Vec<&'static str>,
+    pub mandatory: bool,
+}
+
+/// Preferences stored in `notification_preferences`.
+///
+/// Everything is enabled until a row says otherwise. A row for `ALL_TYPES`
+/// applies to every type on its channel; a row for the type itself wins over it.
+#[derive(Clone)]
+pub struct DatabasePreferences {
+    db: PgPool,
+}
+
+impl DatabasePreferences {
+    pub const ALL_TYPES: &'static str = "*";
+
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+
+    /// Stored rows of one notifiable.
+    pub async fn list(
+        &self,
+        notifiable: &dyn Notifiable,
+    ) -> Result<Vec<NotificationPreferenceRecord>> {
+        NotificationPreferenceModel::query()
+            .where_col(
+                NotificationPreferenceCol::NOTIFIABLE_TYPE,
+                Op::Eq,
+                notifiable.notifiable_type(),
+            )
+            .where_col(
+                NotificationPreferenceCol::NOTIFIABLE_ID,
+                Op::Eq,
+                notifiable.id(),
+            )
+            .all(DbConn::pool(&self.db))
+            .await
+    }
+
+    pub async fn set(
+        &self,
+        notifiable: &dyn Notifiable,
+        notification_type: &str,
+        channel: &str,
+        enabled: bool,
+    ) -> Result<()> {
+        NotificationPreferenceModel::create()
+            .set(
+                NotificationPreferenceCol::NOTIFIABLE_TYPE,
+                notifiable.notifiable_type(),
+            )?
+            .set(NotificationPreferenceCol::NOTIFIABLE_ID, notifiable.id())?
+            .set(
+                NotificationPreferenceCol::NOTIFICATION_TYPE,
+                notification_type.to_string(),
+            )?
+            .set(NotificationPreferenceCol::CHANNEL, channel.to_string())?
+            .set(NotificationPreferenceCol::ENABLED, enabled)?
+            .set(
+                NotificationPreferenceCol::UPDATED_AT,
+                OffsetDateTime::now_utc(),
+            )?
+            .on_conflict_update(&[
+                NotificationPreferenceCol::NOTIFIABLE_TYPE,
+                NotificationPreferenceCol::NOTIFIABLE_ID,
+                NotificationPreferenceCol::NOTIFICATION_TYPE,
+                NotificationPreferenceCol::CHANNEL,
+            ])
+            .save(DbConn::pool(&self.db))
+            .await?;
+        Ok(())
+    }
+
+    /// The preferences page: every described type and channel with its current state.
+    pub async fn settings(
+        &self,
+        notifiable: &dyn Notifiable,
+        types: &[NotificationTypeInfo],
+    ) -> Result<NotificationPreferencesOutput> {
+        let rows = self.list(notifiable).await?;
+        let types = types
+            .iter()
+            .map(|info| NotificationTypePreferenceDto {
+                notification_type: info.notification_type.to_string(),
+                mandatory: info.mandatory,
+                channels: info
+                    .channels
+                    .iter()
+                    .map(|channel| NotificationChannelPreferenceDto {
+                        channel: channel.to_string(),
+                        enabled: info.mandatory || resolve(&rows, info.notification_type, channel),
+                    })
+                    .collect(),
+            })
+            .collect();
+        Ok(NotificationPreferencesOutput { types })
+    }
+
+    /// Save the preferences page. Unknown type/channel pairs and opting out of
+    /// a mandatory type are rejected before anything is written.
+    pub async fn update(
+        &self,
+        notifiable: &dyn Notifiable,
+        types: &[NotificationTypeInfo],
+        input: &NotificationPreferenceUpdateInput,
+    ) -> Result<(), AppError> {
+        for item in &input.preferences {
+            let info = types.iter().find(|info| {
+                info.notification_type == item.notification_type
+                    && info.channels.contains(&item.channel.as_str())
+            });
+            match info {
+                None => {
+                    return Err(AppError::UnprocessableEntity(format!(
+                        "Unknown notification preference {}/{}",
+                        item.notification_type, item.channel
+                    )))
+                }
+                Some(info) if info.mandatory && !item.enabled => {
+                    return Err(AppError::UnprocessableEntity(format!(
+                        "Notification {} cannot be turned off",
+                        item.notification_type
+                    )))
+                }
+                Some(_) => {}
+            }
+        }
+        for item in &input.preferences {
+            self.set(
+                notifiable,
+                &item.notification_type,
+                &item.channel,
+                item.enabled,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl NotificationPreferences for DatabasePreferences {
+    async fn allows(
+        &self,
+        notifiable: &dyn Notifiable,
+        notification_type: &str,
+        channel: &str,
+    ) -> Result<bool> {
+        let rows = NotificationPreferenceModel::query()
+            .where_col(
+                NotificationPreferenceCol::NOTIFIABLE_TYPE,
+                Op::Eq,
+                notifiable.notifiable_type(),
+            )
+            .where_col(
+                NotificationPreferenceCol::NOTIFIABLE_ID,
+                Op::Eq,
+                notifiable.id(),
+            )
+            .where_col(
+                NotificationPreferenceCol::CHANNEL,
+                Op::Eq,
+                channel.to_string(),
+            )
+            .where_in(
+                NotificationPreferenceCol::NOTIFICATION_TYPE,
+                [notification_type.to_string(), Self::ALL_TYPES.to_string()],
+            )
+            .all(DbConn::pool(&self.db))
+            .await?;
+        Ok(resolve(&rows, notification_type, channel))
+    }
+}
+
+fn resolve(rows: &[NotificationPreferenceRecord], notification_type: &str, channel: &str) -> bool {
+    let find = |notification_type: &str| {
+        rows.iter()
+            .find(|row| row.notification_type == notification_type && row.channel == channel)
+            .map(|row| row.enabled)
+    };
+    find(notification_type)
+        .or_else(|| find(DatabasePreferences::ALL_TYPES))
+        .unwrap_or(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{resolve, DatabasePreferences};
+    use core_db::generated::models::NotificationPreferenceRecord;
+
+    fn row(notification_type: &str, channel: &str, enabled: bool) -> NotificationPreferenceRecord {
+        NotificationPreferenceRecord {
+            id: uuid::Uuid::new_v4(),
+            notifiable_type: "user".to_string(),
+            notifiable_id: "1".to_string(),
+            notification_type: notification_type.to_string(),
+            channel: channel.to_string(),
+            enabled,
+            updated_at: time::OffsetDateTime::now_utc(),
+            __relation_counts: Default::default(),
+            __relation_aggregates: Default::default(),
+        }
+    }
+
+    #[test]
+    fn specific_type_wins_over_wildcard() {
+        let rows = [
+            row(DatabasePreferences::ALL_TYPES, "mail", false),
+            row("order_shipped", "mail", true),
+        ];
+        assert!(resolve(&rows, "order_shipped", "mail"));
+        assert!(!resolve(&rows, "newsletter", "mail"));
+    }
+
+    #[test]
+    fn wildcard_applies_per_channel() {
+        let rows = [row(DatabasePreferences::ALL_TYPES, "mail", false)];
+        assert!(!resolve(&rows, "order_shipped", "mail"));
+        assert!(resolve(&rows, "order_shipped", "database"));
+    }
+
+    #[test]
+    fn no_matching_row_means_enabled() {
+        assert!(resolve(&[], "order_shipped", "mail"));
+        let rows = [row("order_shipped", "mail", false)];
+        assert!(resolve(&rows, "order_shipped", "broadcast"));
+    }
+}
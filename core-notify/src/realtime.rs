@@ -0,0 +1,117 @@
+use crate::{Channel, ChannelMessage, Notifiable, Notification, RealtimeMessage};
+use anyhow::Result;
+use async_trait::async_trait;
+use core_realtime::RealtimePublisher;
+use serde_json::Value;
+
+/// Publishes `Notification::to_realtime` through `core_realtime`.
+pub struct RealtimeChannel {
+    publisher: RealtimePublisher,
+}
+
+impl RealtimeChannel {
+    pub fn new(publisher: RealtimePublisher) -> Self {
+        Self { publisher }
+    }
+}
+
+#[async_trait]
+impl Channel for RealtimeChannel {
+    fn name(&self) -> &'static str {
+        "realtime"
+    }
+
+    fn render(
+        &self,
+        notification: &dyn Notification,
+        notifiable: &dyn Notifiable,
+    ) -> Result<Option<Value>> {
+        let Some(mut message) = notification.to_realtime(notifiable) else {
+            return Ok(None);
+        };
+        if message.room.is_none() {
+            message.room = notifiable.route_notification_for("realtime");
+        }
+        Ok(Some(serde_json::to_value(message)?))
+    }
+
+    async fn deliver(&self, message: &ChannelMessage) -> Result<()> {
+        let event: RealtimeMessage = serde_json::from_value(message.content.clone())?;
+        self.publisher
+            .publish_raw(
+                &event.channel,
+                &event.event,
+                event.room.as_deref(),
+                event.payload,
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Member;
+
+    impl Notifiable for Member {
+        fn route_notification_for(&self, driver: &str) -> Option<String> {
+            (driver == "realtime").then(|| "user:7".to_string())
+        }
+
+        fn id(&self) -> String {
+            "7".to_string()
+        }
+    }
+
+    struct OrderShipped {
+        room: Option<&'static str>,
+    }
+
+    impl Notification for OrderShipped {
+        fn notification_type(&self) -> &'static str {
+            "order_shipped"
+        }
+
+        fn via(&self, _notifiable: &dyn Notifiable) -> Vec<&'static str> {
+            vec!["realtime"]
+        }
+
+        fn to_realtime(&self, _notifiable: &dyn Notifiable) -> Option<RealtimeMessage> {
+            let message = RealtimeMessage::new("orders", "shipped", serde_json::json!({ "id": 1 }));
+            Some(match self.room {
+                Some(room) => message.room(room),
+                None => message,
+            })
+        }
+    }
+
+    fn channel() -> RealtimeChannel {
+        // The client only connects on publish.
+        RealtimeChannel::new(RealtimePublisher::new("redis://127.0.0.1/").expect("redis url"))
+    }
+
+    #[test]
+    fn room_falls_back_to_the_realtime_route() {
+        let content = channel()
+            .render(&OrderShipped { room: None }, &Member)
+            .unwrap()
+            .expect("rendered");
+        assert_eq!(content["room"], "user:7");
+        assert_eq!(content["event"], "shipped");
+    }
+
+    #[test]
+    fn explicit_room_is_kept() {
+        let content = channel()
+            .render(
+                &OrderShipped {
+                    room: Some("admins"),
+                },
+                &Member,
+            )
+            .unwrap()
+            .expect("rendered");
+        assert_eq!(content["room"], "admins");
+    }
+}
@@ -0,0 +1,205 @@
+use crate::{Channel, ChannelMessage, Notifiable, Notification, SmsMessage};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// SMS provider behind the "sms" channel (Twilio, Vonage, ...).
+#[async_trait]
+pub trait SmsDriver: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn send(&self, to: &str, body: &str) -> Result<()>;
+}
+
+/// Writes messages to the log instead of sending them (development).
+pub struct LogSmsDriver;
+
+#[async_trait]
+impl SmsDriver for LogSmsDriver {
+    fn name(&self) -> &'static str {
+        "log"
+    }
+
+    async fn send(&self, to: &str, body: &str) -> Result<()> {
+        tracing::info!("SMS to {}: {}", to, body);
+        Ok(())
+    }
+}
+
+pub struct SmsChannel {
+    driver: Arc<dyn SmsDriver>,
+}
+
+impl SmsChannel {
+    pub fn new(driver: impl SmsDriver + 'static) -> Self {
+        Self {
+            driver: Arc::new(driver),
+        }
+    }
+}
+
+#[async_trait]
+impl Channel for SmsChannel {
+    fn name(&self) -> &'static str {
+        "sms"
+    }
+
+    fn render(
+        &self,
+        notification: &dyn Notification,
+        notifiable: &dyn Notifiable,
+    ) -> Result<Option<Value>> {
+        let Some(mut message) = notification.to_sms(notifiable) else {
+            return Ok(None);
+        };
+        if message.to.is_none() {
+            message.to = notifiable.phone();
+        }
+        if message.to.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::to_value(message)?))
+    }
+
+    async fn deliver(&self, message: &ChannelMessage) -> Result<()> {
+        let sms: SmsMessage = serde_json::from_value(message.content.clone())?;
+        let to = sms
+            .to
+            .ok_or_else(|| anyhow::anyhow!("SMS notification has no recipient"))?;
+        self.driver.send(&to, &sms.body).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Clone, Default)]
+    struct RecordingDriver {
+        sent: Arc<Mutex<Vec<(String, String)>>>,
+    }
+
+    #[async_trait]
+    impl SmsDriver for RecordingDriver {
+        fn name(&self) -> &'static str {
+            "recording"
+        }
+
+        async fn send(&self, to: &str, body: &str) -> Result<()> {
+            self.sent
+                .lock()
+                .unwrap()
+                .push((to.to_string(), body.to_string()));
+            Ok(())
+        }
+    }
+
+    struct Member {
+        phone: Option<&'static str>,
+    }
+
+    impl Notifiable for Member {
+        fn route_notification_for(&self, driver: &str) -> Option<String> {
+            match driver {
+                "sms" => self.phone.map(str::to_string),
+                _ => None,
+            }
+        }
+
+        fn id(&self) -> String {
+            "7".to_string()
+        }
+    }
+
+    struct LoginCode {
+        to: Option<&'static str>,
+    }
+
+    impl Notification for LoginCode {
+        fn notification_type(&self) -> &'static str {
+            "login_code"
+        }
+
+        fn via(&self, _notifiable: &dyn Notifiable) -> Vec<&'static str> {
+            vec!["sms"]
+        }
+
+        fn to_sms(&self, _notifiable: &dyn Notifiable) -> Option<SmsMessage> {
+            let message = SmsMessage::new("Your code is 123456");
+            Some(match self.to {
+                Some(to) => message.to(to),
+                None => message,
+            })
+        }
+    }
+
+    fn message(content: Value) -> ChannelMessage {
+        ChannelMessage {
+            channel: "sms".to_string(),
+            notification_id: uuid::Uuid::new_v4(),
+            notification_type: "login_code".to_string(),
+            notifiable_type: "user".to_string(),
+            notifiable_id: "7".to_string(),
+            content,
+        }
+    }
+
+    #[tokio::test]
+    async fn sms_falls_back_to_the_notifiable_phone() {
+        let driver = RecordingDriver::default();
+        let channel = SmsChannel::new(driver.clone());
+        let content = channel
+            .render(
+                &LoginCode { to: None },
+                &Member {
+                    phone: Some("+15550100"),
+                },
+            )
+            .unwrap()
+            .expect("rendered");
+
+        channel.deliver(&message(content)).await.unwrap();
+        assert_eq!(
+            *driver.sent.lock().unwrap(),
+            [("+15550100".to_string(), "Your code is 123456".to_string())]
+        );
+    }
+
+    #[test]
+    fn sms_keeps_an_explicit_number() {
+        let channel = SmsChannel::new(LogSmsDriver);
+        let content = channel
+            .render(
+                &LoginCode {
+                    to: Some("+15550199"),
+                },
+                &Member {
+                    phone: Some("+15550100"),
+                },
+            )
+            .unwrap()
+            .expect("rendered");
+        assert_eq!(content["to"], "+15550199");
+    }
+
+    #[test]
+    fn sms_without_a_number_is_skipped() {
+        let channel = SmsChannel::new(LogSmsDriver);
+        let content = channel
+            .render(&LoginCode { to: None }, &Member { phone: None })
+            .unwrap();
+        assert!(content.is_none());
+    }
+
+    #[tokio::test]
+    async fn sms_delivery_without_a_number_fails() {
+        let channel = SmsChannel::new(LogSmsDriver);
+        let err = channel
+            .deliver(&message(serde_json::json!({ "to": null, "body": "hi" })))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("no recipient"));
+    }
+}
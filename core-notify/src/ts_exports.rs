@@ -0,0 +1,37 @@
+use core_web::ts_exports::TsExportFile;
+use ts_rs::TS;
+
+use crate::contracts::{
+    NotificationChannelPreferenceDto, NotificationPreferenceUpdateInput,
+    NotificationPreferenceUpdateItem, NotificationPreferencesOutput, NotificationTypePreferenceDto,
+};
+
+pub fn ts_export_files() -> Vec<TsExportFile> {
+    vec![TsExportFile {
+        rel_path: "shared/types/notifications.ts",
+        rust_path: "core_notify::ts_exports::notifications",
+        definition: render_notifications_ts(),
+    }]
+}
+
+fn render_notifications_ts() -> String {
+    [
+        export_decl(NotificationChannelPreferenceDto::decl()),
+        export_decl(NotificationTypePreferenceDto::decl()),
+        export_decl(NotificationPreferencesOutput::decl()),
+        export_decl(NotificationPreferenceUpdateItem::decl()),
+        export_decl(NotificationPreferenceUpdateInput::decl()),
+    ]
+    .join("\n\n")
+}
+
+fn export_decl(mut decl: String) -> String {
+    if decl.trim_start().starts_with("export ") {
+        return decl;
+    }
+    if decl.starts_with("interface ") || decl.starts_with("type ") {
+        decl.insert_str(0, "export ");
+        return decl;
+    }
+    format!("export {decl}")
+}
@@ -2156,8 +2156,11 @@ mod tests {
             cdn: CdnSettings { base_url: None },
             worker: WorkerSettings {
                 enabled: false,
+                driver: "redis".into(),
                 concurrency: 1,
                 sweep_interval: 30,
+                reliable: false,
+                shutdown_grace: 30,
             },
             i18n: core_i18n::config::I18nSettings {
                 default_locale: "en",
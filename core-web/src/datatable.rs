@@ -1112,7 +1112,9 @@ where
                     .queue_raw(MailPayload {
                         to: recipients,
                         subject,
-                        body,
+                        text: Some(body.clone()),
+                        body: body.replace('\n', "<br>"),
+                        ..Default::default()
                     })
                     .await
                     .map_err(AppError::from)?;
@@ -0,0 +1,396 @@
+//! Job management endpoints for admin APIs: failed-job search, payload view,
+//! bulk retry and forget, and pausing queues.
+//!
+//! ```text
+//! .nest(
+//!     "/jobs",
+//!     core_web::jobs::routes(
+//!         AdminGuard,
+//!         JobManager::new(ctx.db.clone(), ctx.jobs.clone()),
+//!         JobAdminPermissions::new(["job.read"], ["job.manage"]),
+//!     ),
+//! )
+//! ```
+
+use axum::extract::{Path, Query, State};
+use core_db::generated::models::FailedJobRecord;
+use core_jobs::manager::{BulkOutcome, FailedJobFilter, JobManager, PausedQueue};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    auth::Guard,
+    authz::PermissionMode,
+    contracts::ContractJson,
+    datetime::DateTime,
+    error::AppError,
+    openapi::{
+        with_permission_check_delete_with, with_permission_check_get_with,
+        with_permission_check_post_with, ApiRouter,
+    },
+    response::ApiResponse,
+};
+
+const TAG: &str = "Jobs";
+
+/// Permissions checked by `routes`. Read endpoints accept `read` or `manage`.
+#[derive(Debug, Clone)]
+pub struct JobAdminPermissions {
+    pub read: Vec<String>,
+    pub manage: Vec<String>,
+}
+
+impl JobAdminPermissions {
+    pub fn new<R, M>(read: impl IntoIterator<Item = R>, manage: impl IntoIterator<Item = M>) -> Self
+    where
+        R: Into<String>,
+        M: Into<String>,
+    {
+        Self {
+            read: read.into_iter().map(Into::into).collect(),
+            manage: manage.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    fn read_or_manage(&self) -> Vec<String> {
+        self.read.iter().chain(&self.manage).cloned().collect()
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+pub struct FailedJobQuery {
+    /// Exact job name.
+    pub job: Option<String>,
+    pub queue: Option<String>,
+    /// Case-insensitive substring of the error message.
+    pub error: Option<String>,
+    /// Failed at or after (RFC 3339).
+    pub from: Option<DateTime>,
+    /// Failed at or before (RFC 3339).
+    pub to: Option<DateTime>,
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate, JsonSchema)]
+pub struct FailedJobIdsInput {
+    #[validate(length(min = 1, max = 500))]
+    #[schemars(length(min = 1, max = 500))]
+    pub ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate, JsonSchema)]
+pub struct RetryJobInput {
+    /// Retry every failed job with this name.
+    #[validate(length(min = 1))]
+    #[schemars(length(min = 1))]
+    pub job: String,
+    /// Only failures on this queue.
+    pub queue: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct FailedJobOutput {
+    pub id: Uuid,
+    pub job_name: String,
+    pub queue: String,
+    pub error: String,
+    pub attempts: i32,
+    pub group_id: Option<String>,
+    pub failed_at: DateTime,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct FailedJobDetailOutput {
+    #[serde(flatten)]
+    pub job: FailedJobOutput,
+    pub payload: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct FailedJobListOutput {
+    pub data: Vec<FailedJobOutput>,
+    pub total: i64,
+    pub per_page: i64,
+    pub current_page: i64,
+    pub last_page: i64,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JobBulkOutput {
+    pub affected: u64,
+    pub skipped: u64,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PausedQueueOutput {
+    pub queue: String,
+    pub paused_at: DateTime,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PausedQueueListOutput {
+    pub queues: Vec<PausedQueueOutput>,
+}
+
+/// Job management routes guarded by `G` (mount with `.nest("/jobs", ...)`).
+pub fn routes<G>(guard: G, manager: JobManager, permissions: JobAdminPermissions) -> ApiRouter
+where
+    G: Guard + Copy,
+{
+    let read = permissions.read_or_manage();
+    let manage = permissions.manage;
+
+    ApiRouter::new()
+        .api_route(
+            "/failed",
+            with_permission_check_get_with(
+                list_failed,
+                guard,
+                PermissionMode::Any,
+                read.clone(),
+                |op| op.summary("Search failed jobs").tag(TAG),
+            ),
+        )
+        .api_route(
+            "/failed/retry",
+            with_permission_check_post_with(
+                retry_failed,
+                guard,
+                PermissionMode::Any,
+                manage.clone(),
+                |op| op.summary("Retry failed jobs").tag(TAG),
+            ),
+        )
+        .api_route(
+            "/failed/retry_job",
+            with_permission_check_post_with(
+                retry_failed_job,
+                guard,
+                PermissionMode::Any,
+                manage.clone(),
+                |op| op.summary("Retry all failed jobs of a job type").tag(TAG),
+            ),
+        )
+        .api_route(
+            "/failed/delete",
+            with_permission_check_post_with(
+                forget_failed,
+                guard,
+                PermissionMode::Any,
+                manage.clone(),
+                |op| op.summary("Delete failed jobs").tag(TAG),
+            ),
+        )
+        .api_route(
+            "/failed/{id}",
+            with_permission_check_get_with(
+                show_failed,
+                guard,
+                PermissionMode::Any,
+                read.clone(),
+                |op| op.summary("Get failed job with payload").tag(TAG),
+            ),
+        )
+        .api_route(
+            "/failed/{id}",
+            with_permission_check_delete_with(
+                forget_one_failed,
+                guard,
+                PermissionMode::Any,
+                manage.clone(),
+                |op| op.summary("Delete failed job").tag(TAG),
+            ),
+        )
+        .api_route(
+            "/queues/paused",
+            with_permission_check_get_with(list_paused, guard, PermissionMode::Any, read, |op| {
+                op.summary("List paused queues").tag(TAG)
+            }),
+        )
+        .api_route(
+            "/queues/{name}/pause",
+            with_permission_check_post_with(
+                pause_queue,
+                guard,
+                PermissionMode::Any,
+                manage.clone(),
+                |op| op.summary("Pause queue").tag(TAG),
+            ),
+        )
+        .api_route(
+            "/queues/{name}/resume",
+            with_permission_check_post_with(
+                resume_queue,
+                guard,
+                PermissionMode::Any,
+                manage,
+                |op| op.summary("Resume queue").tag(TAG),
+            ),
+        )
+        .with_state(manager)
+}
+
+async fn list_failed(
+    State(manager): State<JobManager>,
+    Query(query): Query<FailedJobQuery>,
+) -> Result<ApiResponse<FailedJobListOutput>, AppError> {
+    let filter = FailedJobFilter {
+        job: non_empty(query.job),
+        queue: non_empty(query.queue),
+        error: non_empty(query.error),
+        from: query.from.map(Into::into),
+        to: query.to.map(Into::into),
+    };
+    let page = manager
+        .failed_jobs(
+            &filter,
+            query.page.unwrap_or(1),
+            query.per_page.unwrap_or(30),
+        )
+        .await?;
+
+    Ok(ApiResponse::success(
+        FailedJobListOutput {
+            data: page.data.into_iter().map(failed_job_output).collect(),
+            total: page.total,
+            per_page: page.per_page,
+            current_page: page.current_page,
+            last_page: page.last_page,
+        },
+        "ok",
+    ))
+}
+
+async fn show_failed(
+    State(manager): State<JobManager>,
+    Path(id): Path<Uuid>,
+) -> Result<ApiResponse<FailedJobDetailOutput>, AppError> {
+    let record = manager
+        .failed_job(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Failed job not found".to_string()))?;
+    let payload = record.payload.clone();
+
+    Ok(ApiResponse::success(
+        FailedJobDetailOutput {
+            job: failed_job_output(record),
+            payload,
+        },
+        "ok",
+    ))
+}
+
+async fn retry_failed(
+    State(manager): State<JobManager>,
+    ContractJson(input): ContractJson<FailedJobIdsInput>,
+) -> Result<ApiResponse<JobBulkOutput>, AppError> {
+    let outcome = manager.retry(&input.ids).await?;
+    Ok(ApiResponse::success(bulk_output(outcome), "Retried"))
+}
+
+async fn retry_failed_job(
+    State(manager): State<JobManager>,
+    ContractJson(input): ContractJson<RetryJobInput>,
+) -> Result<ApiResponse<JobBulkOutput>, AppError> {
+    let filter = FailedJobFilter {
+        job: Some(input.job),
+        queue: non_empty(input.queue),
+        ..Default::default()
+    };
+    let outcome = manager.retry_matching(&filter).await?;
+    Ok(ApiResponse::success(bulk_output(outcome), "Retried"))
+}
+
+async fn forget_failed(
+    State(manager): State<JobManager>,
+    ContractJson(input): ContractJson<FailedJobIdsInput>,
+) -> Result<ApiResponse<JobBulkOutput>, AppError> {
+    let affected = manager.forget(&input.ids).await?;
+    Ok(ApiResponse::success(
+        JobBulkOutput {
+            affected,
+            skipped: 0,
+        },
+        "Deleted",
+    ))
+}
+
+async fn forget_one_failed(
+    State(manager): State<JobManager>,
+    Path(id): Path<Uuid>,
+) -> Result<ApiResponse<JobBulkOutput>, AppError> {
+    let affected = manager.forget(&[id]).await?;
+    if affected == 0 {
+        return Err(AppError::NotFound("Failed job not found".to_string()));
+    }
+    Ok(ApiResponse::success(
+        JobBulkOutput {
+            affected,
+            skipped: 0,
+        },
+        "Deleted",
+    ))
+}
+
+async fn list_paused(
+    State(manager): State<JobManager>,
+) -> Result<ApiResponse<PausedQueueListOutput>, AppError> {
+    let queues = manager
+        .paused()
+        .await?
+        .into_iter()
+        .map(paused_queue_output)
+        .collect();
+    Ok(ApiResponse::success(PausedQueueListOutput { queues }, "ok"))
+}
+
+async fn pause_queue(
+    State(manager): State<JobManager>,
+    Path(name): Path<String>,
+) -> Result<ApiResponse<PausedQueueListOutput>, AppError> {
+    manager.pause(name.trim()).await?;
+    list_paused(State(manager)).await
+}
+
+async fn resume_queue(
+    State(manager): State<JobManager>,
+    Path(name): Path<String>,
+) -> Result<ApiResponse<PausedQueueListOutput>, AppError> {
+    manager.resume(name.trim()).await?;
+    list_paused(State(manager)).await
+}
+
+fn failed_job_output(record: FailedJobRecord) -> FailedJobOutput {
+    FailedJobOutput {
+        id: record.id,
+        job_name: record.job_name,
+        queue: record.queue,
+        error: record.error,
+        attempts: record.attempts,
+        group_id: record.group_id,
+        failed_at: record.failed_at.into(),
+    }
+}
+
+fn paused_queue_output(paused: PausedQueue) -> PausedQueueOutput {
+    PausedQueueOutput {
+        queue: paused.queue,
+        paused_at: paused.paused_at.into(),
+    }
+}
+
+fn bulk_output(outcome: BulkOutcome) -> JobBulkOutput {
+    JobBulkOutput {
+        affected: outcome.affected,
+        skipped: outcome.skipped,
+    }
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
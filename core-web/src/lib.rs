@@ -8,6 +8,7 @@ pub mod datetime;
 pub mod decimal;
 pub mod extract;
 pub mod ids;
+pub mod jobs;
 pub mod logging;
 pub mod middleware;
 pub mod openapi;
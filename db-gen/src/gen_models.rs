@@ -1489,7 +1489,7 @@ fn render_imports_and_constants(ctx: &ModelCtx) -> (String, String) {
     }
     writeln!(imports, "use core_db::platform::localized::types::LocalizedMap;").unwrap();
     writeln!(imports, "use crate::generated::models::common::{{FieldChange, FieldInput, Page, log_observer_error, renumber_placeholders}};").unwrap();
-    writeln!(imports, "use core_db::common::model_api::{{ColExpr, Column, Create, CreateState, ManyRelation, ModelDef, OneRelation, Patch, PatchState, Query, QueryState}};").unwrap();
+    writeln!(imports, "use core_db::common::model_api::{{ColExpr, Column, Create, CreateMany, CreateState, InsertSelect, ManyRelation, ModelDef, OneRelation, Patch, PatchState, Query, QueryState}};").unwrap();
     if ctx.has_meta {
         writeln!(imports, "use core_db::platform::meta::types::MetaMap;").unwrap();
     }
@@ -2460,6 +2460,27 @@ writeln!(
 .unwrap();
 writeln!(out, "        Create::new_with_base_url(base_url)").unwrap();
 writeln!(out, "    }}").unwrap();
+writeln!(
+    out,
+    "    pub fn create_many<'db>() -> CreateMany<'db, {model_title}Model> {{"
+)
+.unwrap();
+writeln!(out, "        CreateMany::new()").unwrap();
+writeln!(out, "    }}").unwrap();
+writeln!(
+    out,
+    "    pub fn create_many_with_base_url<'db>(base_url: Option<String>) -> CreateMany<'db, {model_title}Model> {{"
+)
+.unwrap();
+writeln!(out, "        CreateMany::new_with_base_url(base_url)").unwrap();
+writeln!(out, "    }}").unwrap();
+writeln!(
+    out,
+    "    pub fn insert_select<'db, F: ColExpr, S: core_db::common::model_api::QueryModel>(cols: &[F], select: core_db::common::sql::RawSelectExpr, source: Query<'db, S>) -> InsertSelect<'db, {model_title}Model> {{"
+)
+.unwrap();
+writeln!(out, "        InsertSelect::new(cols, select, source)").unwrap();
+writeln!(out, "    }}").unwrap();
 writeln!(
     out,
     "    pub fn patch<'db>() -> Patch<'db, {model_title}Model> {{"
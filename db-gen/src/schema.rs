@@ -288,6 +288,11 @@ pub fn framework_model_source_paths_from_core_db() -> Vec<PathBuf> {
         core_db_src.join("framework_models/personal_access_token.rs"),
         core_db_src.join("framework_models/webhook_log.rs"),
         core_db_src.join("framework_models/http_client_log.rs"),
+        core_db_src.join("framework_models/mail_log.rs"),
+        core_db_src.join("framework_models/notification.rs"),
+        core_db_src.join("framework_models/notification_preference.rs"),
+        core_db_src.join("framework_models/notification_digest_item.rs"),
+        core_db_src.join("framework_models/webhook_endpoint.rs"),
     ]
 }
 
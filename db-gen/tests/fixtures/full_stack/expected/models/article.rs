@@ -14,7 +14,7 @@ use core_db::platform::attachments::types::{Attachment, AttachmentInput, Attachm
 use uuid::Uuid;
 use core_db::platform::localized::types::LocalizedMap;
 use crate::generated::models::common::{FieldChange, FieldInput, Page, log_observer_error, renumber_placeholders};
-use core_db::common::model_api::{ColExpr, Column, Create, CreateState, ManyRelation, ModelDef, OneRelation, Patch, PatchState, Query, QueryState};
+use core_db::common::model_api::{ColExpr, Column, Create, CreateMany, CreateState, InsertSelect, ManyRelation, ModelDef, OneRelation, Patch, PatchState, Query, QueryState};
 use core_db::platform::meta::types::MetaMap;
 use crate::generated::localized;
 use core_i18n::current_locale;
@@ -692,6 +692,15 @@ impl ArticleModel {
     pub fn create_with_base_url<'db>(base_url: Option<String>) -> Create<'db, ArticleModel> {
         Create::new_with_base_url(base_url)
     }
+    pub fn create_many<'db>() -> CreateMany<'db, ArticleModel> {
+        CreateMany::new()
+    }
+    pub fn create_many_with_base_url<'db>(base_url: Option<String>) -> CreateMany<'db, ArticleModel> {
+        CreateMany::new_with_base_url(base_url)
+    }
+    pub fn insert_select<'db, F: ColExpr, S: core_db::common::model_api::QueryModel>(cols: &[F], select: core_db::common::sql::RawSelectExpr, source: Query<'db, S>) -> InsertSelect<'db, ArticleModel> {
+        InsertSelect::new(cols, select, source)
+    }
     pub fn patch<'db>() -> Patch<'db, ArticleModel> {
         Patch::new()
     }
@@ -276,6 +276,11 @@ fn framework_models_load_from_core_db_single_source_of_truth() {
         "personal_access_token",
         "webhook_log",
         "http_client_log",
+        "mail_log",
+        "notification",
+        "notification_preference",
+        "notification_digest_item",
+        "webhook_endpoint",
     ] {
         assert!(
             parsed_schema.models.contains_key(model),
@@ -72,10 +72,14 @@ fn register_seeders(seeders: &mut Vec<Box<dyn core_db::seeder::Seeder>>) {
 const TOKIO_THREAD_STACK_SIZE: usize = 8 * 1024 * 1024;
 
 async fn run() -> anyhow::Result<()> {
-    bootstrap::console::start_console::<
+    bootstrap::console::start_console_with_schedules::<
         ProjectCommands,
         fn(&mut Vec<Box<dyn core_db::seeder::Seeder>>),
-    >(Some(register_seeders))
+        fn(&mut core_jobs::cron::Scheduler),
+    >(
+        Some(register_seeders),
+        Some(app::internal::jobs::register_schedules),
+    )
     .await
 }
 
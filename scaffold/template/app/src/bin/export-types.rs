@@ -139,7 +139,10 @@ fn frontend_src_dir() -> PathBuf {
 fn framework_ts_files() -> Vec<FrameworkTsFile> {
     let mut files = Vec::new();
 
-    for file in generated::ts_exports::ts_export_files() {
+    for file in generated::ts_exports::ts_export_files()
+        .into_iter()
+        .chain(core_notify::ts_exports::ts_export_files())
+    {
         files.push(FrameworkTsFile {
             rel_path: file.rel_path.to_string(),
             rust_path: file.rust_path.to_string(),
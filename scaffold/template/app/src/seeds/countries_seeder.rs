@@ -16,6 +16,7 @@ impl Seeder for CountriesSeeder {
     async fn run(&self, db: &sqlx::PgPool) -> anyhow::Result<()> {
         let countries = load_builtin_country_seed()?;
 
+        let mut rows = Vec::with_capacity(countries.len());
         for seed in countries {
             let seed = normalize_country_seed(seed);
             let status =
@@ -28,7 +29,7 @@ impl Seeder for CountriesSeeder {
                 CountryIsDefault::No
             };
 
-            CountryModel::create()
+            let row = CountryModel::create()
                 .set(CountryCol::ISO2, seed.iso2)?
                 .set(CountryCol::ISO3, seed.iso3)?
                 .set(CountryCol::ISO_NUMERIC, seed.iso_numeric)?
@@ -52,11 +53,15 @@ impl Seeder for CountriesSeeder {
                 .set(CountryCol::IS_DEFAULT, is_default)?
                 .set(CountryCol::ASSIGNMENT_STATUS, seed.assignment_status)?
                 .set(CountryCol::UN_MEMBER, seed.un_member)?
-                .set(CountryCol::FLAG_EMOJI, seed.flag_emoji)?
-                .on_conflict_update(&[CountryCol::ISO2])
-                .save(DbConn::pool(db))
-                .await?;
+                .set(CountryCol::FLAG_EMOJI, seed.flag_emoji)?;
+            rows.push(row);
         }
+
+        CountryModel::create_many()
+            .rows(rows)
+            .on_conflict_update(&[CountryCol::ISO2])
+            .execute(DbConn::pool(db))
+            .await?;
         Ok(())
     }
 
@@ -1,4 +1,5 @@
 export * from "@shared/types/api";
 export * from "@shared/types/datatable";
 export * from "@shared/types/enums";
+export * from "@shared/types/notifications";
 export * from "@shared/types/platform";
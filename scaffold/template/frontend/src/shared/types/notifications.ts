@@ -0,0 +1,16 @@
+// Auto-generated by `cargo run -p app --bin export-types`.
+// Do not edit manually — run `make gen-types` to regenerate.
+
+export type NotificationChannelPreferenceDto = { channel: string, enabled: boolean, };
+
+export type NotificationTypePreferenceDto = { notification_type: string, 
+/**
+ * Always delivered; render the toggles disabled.
+ */
+mandatory: boolean, channels: Array<NotificationChannelPreferenceDto>, };
+
+export type NotificationPreferencesOutput = { types: Array<NotificationTypePreferenceDto>, };
+
+export type NotificationPreferenceUpdateItem = { notification_type: string, channel: string, enabled: boolean, };
+
+export type NotificationPreferenceUpdateInput = { preferences: Array<NotificationPreferenceUpdateItem>, };